@@ -11,7 +11,10 @@ pub(crate) fn run(cli: &cli::Opts) -> eyre::Result<ExitCode> {
     }
 
     let lock_path = super::paths::cclip_lock_path()?;
-    let is_non_interactive = cli.cclip_clear_tags || cli.cclip_tag_list || cli.cclip_wipe_tags;
+    let is_non_interactive = cli.cclip_clear_tags
+        || cli.cclip_tag_list
+        || cli.cclip_wipe_tags
+        || cli.filter_query.is_some();
     let _session = if is_non_interactive {
         None
     } else {
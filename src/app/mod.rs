@@ -23,6 +23,9 @@ pub(crate) fn run() -> eyre::Result<ExitCode> {
             println!("{}", env!("CARGO_PKG_VERSION"));
             return Ok(ExitCode::SUCCESS);
         }
+        Ok(cli::CliCommand::ValidateDesktop { paths }) => {
+            return Ok(modes::app_launcher::validate::run(&paths));
+        }
         Err(error) => {
             eprint!("{}", error.render());
             return Ok(error.exit_code());
@@ -38,6 +41,8 @@ pub(crate) fn run() -> eyre::Result<ExitCode> {
         return cclip::run(&cli);
     }
 
+    // The local UTC offset can only be read while the process is single-threaded.
+    crate::core::ranking::local_utc_offset_secs();
     let rt = tokio::runtime::Runtime::new().wrap_err("Failed to create tokio runtime")?;
     rt.block_on(modes::app_launcher::run(cli))?;
     Ok(ExitCode::SUCCESS)
@@ -2,7 +2,10 @@ use super::color::string_to_color;
 use super::launch::{set_launch_prefix, set_systemd_run, set_uwsm};
 use super::types::Opts;
 use crate::config::FselConfig;
+use crate::core::ranking::{TextFolding, builtin_transliteration};
+use crate::desktop::ExecCheck;
 use crate::ui::PanelPosition;
+use std::collections::HashMap;
 
 pub(super) fn apply_config_defaults(default: &mut Opts, fsel_config: &FselConfig) {
     apply_general_config(default, fsel_config);
@@ -27,10 +30,17 @@ fn apply_general_config(default: &mut Opts, fsel_config: &FselConfig) {
     default.pinned_order_mode = fsel_config.general.pinned_order;
     default.systemd_run = fsel_config.general.systemd_run;
     default.uwsm = fsel_config.general.uwsm;
+    // Invalid entries were already rejected by `FselConfig::validate`.
+    default.config_entries = fsel_config
+        .entries
+        .iter()
+        .filter_map(|entry| crate::desktop::App::from_config_entry(entry).ok())
+        .collect();
     default.detach = fsel_config.general.detach;
     default.no_exec = fsel_config.general.no_exec;
     default.confirm_first_launch = fsel_config.general.confirm_first_launch;
     default.prefix_depth = fsel_config.general.prefix_depth;
+    default.text_folding = text_folding(fsel_config);
 
     if default.systemd_run {
         set_systemd_run(default);
@@ -40,6 +50,25 @@ fn apply_general_config(default: &mut Opts, fsel_config: &FselConfig) {
     }
 }
 
+fn text_folding(fsel_config: &FselConfig) -> TextFolding {
+    let mut transliteration = if fsel_config.general.transliterate {
+        builtin_transliteration()
+    } else {
+        HashMap::new()
+    };
+    // Keys were checked to be single characters by `FselConfig::validate`.
+    transliteration.extend(
+        fsel_config
+            .transliteration
+            .iter()
+            .filter_map(|(from, to)| Some((from.chars().next()?, to.clone()))),
+    );
+    TextFolding {
+        normalize: fsel_config.general.normalize_unicode,
+        transliteration,
+    }
+}
+
 fn apply_app_launcher_overrides(default: &mut Opts, fsel_config: &FselConfig) {
     if let Some(filter) = fsel_config.app_launcher.filter_desktop {
         default.filter_desktop = filter;
@@ -71,9 +100,37 @@ fn apply_app_launcher_overrides(default: &mut Opts, fsel_config: &FselConfig) {
     if let Some(ranking_mode) = fsel_config.app_launcher.ranking_mode {
         default.ranking_mode = ranking_mode;
     }
+    if let Some(frecency_model) = fsel_config.app_launcher.frecency_model {
+        default.frecency_model = frecency_model;
+    }
+    if let Some(hours) = fsel_config.app_launcher.frecency_half_life_hours {
+        default.frecency_half_life_secs = hours.max(1).saturating_mul(3600);
+    }
+    if let Some(weight) = fsel_config.app_launcher.query_learning_weight {
+        default.query_learning_weight = weight;
+    }
     if let Some(pinned_order_mode) = fsel_config.app_launcher.pinned_order {
         default.pinned_order_mode = pinned_order_mode;
     }
+    if let Some(check_exec) = fsel_config.app_launcher.check_exec {
+        default.exec_check = if check_exec {
+            ExecCheck::TryExecOrExec
+        } else {
+            ExecCheck::TryExec
+        };
+    }
+    if let Some(show_icons) = fsel_config.app_launcher.show_icons {
+        default.show_icons = show_icons;
+    }
+    if let Some(icon_theme) = fsel_config.app_launcher.icon_theme.clone() {
+        default.icon_theme = Some(icon_theme);
+    }
+    if let Some(link_opener) = fsel_config.app_launcher.link_opener.clone() {
+        default.link_opener = link_opener;
+    }
+    if let Some(command) = fsel_config.app_launcher.activation_token_command.clone() {
+        default.activation_token_command = Some(command);
+    }
 }
 
 fn apply_ui_config(default: &mut Opts, fsel_config: &FselConfig) {
@@ -216,6 +273,7 @@ mod tests {
     use super::apply_config_defaults;
     use crate::cli::Opts;
     use crate::config::FselConfig;
+    use crate::core::ranking::TextFolding;
     use crate::ui::PanelPosition;
 
     #[test]
@@ -254,4 +312,22 @@ mod tests {
 
         assert!(opts.auto_hide_duplicates);
     }
+
+    #[test]
+    fn custom_transliterations_extend_the_builtin_table() {
+        let mut opts = Opts::default();
+        apply_config_defaults(&mut opts, &FselConfig::default());
+        assert_eq!(opts.text_folding, TextFolding::default());
+
+        let mut config = FselConfig::default();
+        config.general.normalize_unicode = true;
+        config.general.transliterate = true;
+        config
+            .transliteration
+            .insert("щ".to_string(), "sht".to_string());
+        apply_config_defaults(&mut opts, &config);
+
+        assert_eq!(opts.text_folding.fold("Щука Жук"), "shtuka zhuk");
+        assert_eq!(opts.text_folding.fold("Crème"), "Creme");
+    }
 }
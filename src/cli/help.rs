@@ -2,12 +2,15 @@ pub(crate) fn short_usage(program_name: &str) -> String {
     format!(
         "fsel — Fast terminal application launcher
 Usage:
-  {program_name} [OPTIONS]
+  {program_name} [OPTIONS] [-- FILE|URL...]
 
 ├─ Core Modes
 │  ├─ -p, --program <NAME>         Launch one app immediately; exact mode refuses near matches
+│  ├─ -- <FILE|URL>...             Hand files or URLs to the launched app via %f/%F/%u/%U
+│  ├─ --open-with <FILE|URL>       Pick from the apps that handle FILE's MIME type, then open it
 │  ├─ --dmenu                      Read choices from stdin and print the selection
-│  └─ --cclip                      Browse clipboard history and copy the selection
+│  ├─ --cclip                      Browse clipboard history and copy the selection
+│  └─ --filter <QUERY>             Print ranked matches for QUERY in any mode, then exit
 │
 ├─ Common Flags
 │  ├─ -c, --config <FILE>          Read config from FILE instead of ~/.config/fsel/config.toml
@@ -31,19 +34,22 @@ Usage:
 │  ├─ --list-hidden                List manually hidden launcher entries, then exit
 │  ├─ --unhide <ID>                Restore one manually hidden entry, then exit
 │  ├─ --unhide-all                 Restore every manually hidden entry, then exit
+│  ├─ --validate-desktop [PATH...] Report spec problems in desktop files, then exit
 │  ├─ --filter-desktop[=no]        Respect OnlyShowIn/NotShowIn; pass =no to ignore them
 │  ├─ --filter-actions[=no]        Hide desktop actions; pass =no to keep entries like new window
 │  ├─ --auto-hide-duplicates[=no]  Suppress duplicate IDs/names using XDG source precedence
 │  ├─ --hide-before-typing         Keep the list hidden until you type the first character
 │  ├─ --stdout                     Print filtered desktop entries to stdout in json form
+│  ├─ --category <NAME>            Only list entries in an XDG category such as Development
 │  ├─ --list-executables-in-path   Include executables from $PATH in launcher mode
-│  ├─ --match-mode <MODE>          Choose fuzzy or exact matching
+│  ├─ --match-mode <MODE>          Choose fuzzy, exact, or extended matching
 │  └─ --prefix-depth <N>           Tune how long prefix matches outrank fuzzy matches
 │
 ├─ Mode-Specific Flags
 │  ├─ Dmenu: --dmenu0 --password[=CHAR] --index --with-nth --accept-nth
 │  ├─        --match-nth --delimiter --only-match --exit-if-empty
 │  ├─        --select --select-index --auto-select --prompt-only
+│  ├─ Both:  -m, --multi[=N] (Tab marks rows)
 │  └─ Cclip: --tag <NAME|list|clear|wipe> --cclip-show-tag-color-names
 │
 └─ Help
@@ -58,12 +64,19 @@ pub(crate) fn detailed_usage(program_name: &str) -> String {
     format!(
         "fsel — Fast terminal application launcher
 Usage:
-  {program_name} [OPTIONS]
+  {program_name} [OPTIONS] [-- FILE|URL...]
 
 ├─ Core Modes
 │  ├─ -p, --program <NAME>         Launch one app immediately; exact mode requires an exact hit
+│  ├─ -- <FILE|URL>...             Hand files or URLs to the launched app; must follow all options
+│  ├─ --open-with <FILE|URL>       List only handlers of FILE's MIME type; mimeapps.list defaults first
 │  ├─ --cclip                      Browse clipboard history and copy the selected item
-│  └─ --dmenu                      Read choices from stdin and print the selection to stdout
+│  ├─ --dmenu                      Read choices from stdin and print the selection to stdout
+│  └─ --filter <QUERY>             Print every match for QUERY, best first, without the TUI
+│
+├─ Filter Output
+│  ├─ --format <FORMAT>            plain (default), tsv (rank, score, fields), or json
+│  └─ --explain                    Add each row's score breakdown or match positions
 │
 ├─ Startup and Output
 │  ├─ -c, --config <FILE>          Read config from FILE before applying CLI overrides
@@ -88,12 +101,14 @@ Usage:
 │  ├─ --list-hidden                List manually hidden launcher entries, then exit
 │  ├─ --unhide <ID>                Restore one manually hidden entry, then exit
 │  ├─ --unhide-all                 Restore every manually hidden entry, then exit
+│  ├─ --validate-desktop [PATH...] Check files or directories (default: installed entries); exit 1 on errors
 │  ├─ --filter-desktop[=no]        Respect OnlyShowIn/NotShowIn; pass =no to ignore them
 │  ├─ --filter-actions[=no]        Hide desktop actions; pass =no to keep entries like new window
 │  ├─ --auto-hide-duplicates[=no]  Suppress duplicate IDs/names using XDG source precedence
 │  ├─ --hide-before-typing         Keep the list hidden until you type the first character
+│  ├─ --category <NAME>            Only list entries in this XDG category (also for --stdout and -p)
 │  ├─ --list-executables-in-path   Include executables from $PATH in launcher mode
-│  ├─ --match-mode <MODE>          Choose fuzzy or exact matching (default: fuzzy)
+│  ├─ --match-mode <MODE>          Choose fuzzy, exact, or extended (fzf syntax) matching (default: fuzzy)
 │  └─ --prefix-depth <N>           Set how long prefix matches outrank fuzzy matches (default: 3)
 │
 ├─ Dmenu Mode Options
@@ -109,6 +124,7 @@ Usage:
 │  ├─ --select <STRING>            Start with the first matching row preselected
 │  ├─ --select-index <N>           Start with row N preselected
 │  ├─ --auto-select                Accept automatically when the filtered list reaches one row
+│  ├─ -m, --multi[=N]              Mark rows with Tab (at most N); Enter prints them in input order
 │  └─ --prompt-only                Show only the input prompt and hide the list pane
 │
 ├─ Clipboard Mode Options
@@ -117,6 +133,7 @@ Usage:
 │  ├─ --tag list <NAME>            List clipboard entries carrying NAME, then exit
 │  ├─ --tag clear                  Remove stored tag metadata
 │  ├─ --tag wipe                   Remove all tags from every clipboard entry
+│  ├─ -m, --multi[=N]              Mark entries with Tab; Enter copies them joined, Alt+Delete deletes them
 │  └─ --cclip-show-tag-color-names Show tag color names next to tags in cclip mode
 │
 ├─ General
@@ -128,7 +145,9 @@ Usage:
    ├─ Pick only one launch method: --launch-prefix, --systemd-run, or --uwsm
    ├─ --dmenu and --cclip both imply --no-exec
    ├─ --program respects --match-mode: exact requires an exact app or executable name
+   ├─ Entries using %f or %u start one process per file or URL passed after --
    ├─ --select and --select-index cannot be combined
+   ├─ --filter works with --dmenu and --cclip; it cannot be combined with -p, -ss, or --stdout
    └─ Default config path: ~/.config/fsel/config.toml
 "
     )
@@ -15,11 +15,12 @@ pub(crate) enum CliCommand {
     PrintShortHelp { program_name: String },
     PrintLongHelp { program_name: String },
     PrintVersion,
+    ValidateDesktop { paths: Vec<std::path::PathBuf> },
 }
 
 pub use crate::ui::PanelPosition;
 pub use color::string_to_color;
-pub use types::{MatchMode, Opts, PinnedOrderMode, RankingMode};
+pub use types::{FilterFormat, FrecencyModel, MatchMode, Opts, PinnedOrderMode, RankingMode};
 
 pub(crate) use help::{detailed_usage, short_usage};
 pub(crate) use parse::parse;
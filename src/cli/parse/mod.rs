@@ -65,12 +65,19 @@ fn find_config_path(args: &[String]) -> Option<PathBuf> {
 }
 
 fn build_parser(args: &[String], default: &mut super::types::Opts) -> lexopt::Parser {
+    let mut end = args.len();
     if let Some(search_pos) = args.iter().position(|arg| arg == "-ss") {
         default.search_string = Some(args[search_pos + 1..].join(" "));
-        return lexopt::Parser::from_args(args[..search_pos].iter().skip(1).cloned());
+        end = search_pos;
     }
 
-    lexopt::Parser::from_args(args.iter().skip(1).cloned())
+    // Everything after `--` is a file or URL for the launched entry.
+    if let Some(separator_pos) = args[..end].iter().position(|arg| arg == "--") {
+        default.launch_targets = args[separator_pos + 1..end].to_vec();
+        end = separator_pos;
+    }
+
+    lexopt::Parser::from_args(args[..end].iter().skip(1).cloned())
 }
 
 #[cfg(test)]
@@ -96,6 +103,108 @@ mod tests {
         assert!(matches!(command, CliCommand::PrintVersion));
     }
 
+    #[test]
+    fn category_is_limited_to_the_app_launcher() {
+        let command = parse_with_config(
+            &args(&["fsel", "--category", "Game"]),
+            FselConfig::default(),
+        )
+        .unwrap();
+        let CliCommand::Run(opts) = command else {
+            panic!("expected launcher run");
+        };
+        assert_eq!(opts.category.as_deref(), Some("Game"));
+
+        assert!(
+            parse_with_config(
+                &args(&["fsel", "--dmenu", "--category", "Game"]),
+                FselConfig::default(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn filter_takes_a_query_format_and_explain_flag() {
+        let command = parse_with_config(
+            &args(&[
+                "fsel",
+                "--dmenu",
+                "--filter",
+                "foo",
+                "--format",
+                "tsv",
+                "--explain",
+            ]),
+            FselConfig::default(),
+        )
+        .unwrap();
+        let CliCommand::Run(opts) = command else {
+            panic!("expected dmenu run");
+        };
+        assert_eq!(opts.filter_query.as_deref(), Some("foo"));
+        assert_eq!(opts.filter_format, Some(crate::cli::FilterFormat::Tsv));
+        assert!(opts.filter_explain);
+
+        for invalid in [
+            &["fsel", "--format", "json"][..],
+            &["fsel", "--filter", "foo", "--format", "csv"],
+            &["fsel", "--filter", "foo", "-p", "firefox"],
+            &["fsel", "--filter", "foo", "--stdout"],
+        ] {
+            assert!(
+                parse_with_config(&args(invalid), FselConfig::default()).is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn multi_takes_an_optional_positive_limit() {
+        for (arguments, limit) in [
+            (&["fsel", "--dmenu", "-m"][..], None),
+            (&["fsel", "--cclip", "--multi=3"], Some(3)),
+        ] {
+            let command = parse_with_config(&args(arguments), FselConfig::default()).unwrap();
+            let CliCommand::Run(opts) = command else {
+                panic!("expected run command");
+            };
+            assert!(opts.multi_select);
+            assert_eq!(opts.multi_select_limit, limit);
+        }
+
+        for invalid in [
+            &["fsel", "--multi"][..],
+            &["fsel", "--dmenu", "--multi=0"],
+            &["fsel", "--dmenu", "--multi=all"],
+        ] {
+            assert!(
+                parse_with_config(&args(invalid), FselConfig::default()).is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_desktop_collects_paths_until_the_next_option() {
+        let command = parse_with_config(
+            &args(&["fsel", "--validate-desktop", "a.desktop", "apps/", "-v"]),
+            FselConfig::default(),
+        )
+        .unwrap();
+        let CliCommand::ValidateDesktop { paths } = command else {
+            panic!("expected validate command");
+        };
+        assert_eq!(paths, ["a.desktop", "apps/"].map(std::path::PathBuf::from));
+
+        let command = parse_with_config(
+            &args(&["fsel", "--validate-desktop"]),
+            FselConfig::default(),
+        )
+        .unwrap();
+        assert!(matches!(command, CliCommand::ValidateDesktop { paths } if paths.is_empty()));
+    }
+
     #[test]
     fn invalid_tag_mode_returns_typed_error() {
         let error = parse_with_config(&args(&["fsel", "--tag", "list"]), FselConfig::default())
@@ -163,6 +272,34 @@ mod tests {
         assert!(!disabled_opts.auto_hide_duplicates);
     }
 
+    #[test]
+    fn arguments_after_separator_become_launch_targets() {
+        let command = parse_with_config(
+            &args(&["fsel", "-p", "imv", "--", "shot.png", "--not-a-flag"]),
+            FselConfig::default(),
+        )
+        .unwrap();
+        let CliCommand::Run(opts) = command else {
+            panic!("expected run command");
+        };
+
+        assert_eq!(opts.program.as_deref(), Some("imv"));
+        assert_eq!(opts.launch_targets, ["shot.png", "--not-a-flag"]);
+    }
+
+    #[test]
+    fn launch_targets_are_rejected_in_dmenu_mode() {
+        let error = parse_with_config(
+            &args(&["fsel", "--dmenu", "--", "file.txt"]),
+            FselConfig::default(),
+        )
+        .unwrap_err();
+
+        assert!(
+            matches!(error, CliError::Message(message) if message.contains("app-launcher mode"))
+        );
+    }
+
     #[test]
     fn hidden_entry_management_flags_parse() {
         let command =
@@ -2,7 +2,7 @@ use super::helpers::{parse_column_list, value_as_string};
 use crate::cli::error::CliError;
 use crate::cli::help::unknown_argument_help;
 use crate::cli::launch::{parse_launch_prefix, set_launch_prefix, set_systemd_run, set_uwsm};
-use crate::cli::{CliCommand, MatchMode, Opts};
+use crate::cli::{CliCommand, FilterFormat, MatchMode, Opts};
 use lexopt::prelude::*;
 
 pub(super) enum OverridesResult {
@@ -57,6 +57,19 @@ pub(super) fn parse_cli_overrides(
             Long("stdout") => {
                 default.stdout = true;
             }
+            Long("filter") => {
+                default.filter_query =
+                    Some(value_as_string(parser, "Filter query must be valid UTF-8")?);
+            }
+            Long("format") => {
+                let format = value_as_string(parser, "Filter format must be valid UTF-8")?;
+                default.filter_format = Some(format.parse::<FilterFormat>().map_err(|_| {
+                    CliError::message("Invalid filter format. Use 'plain', 'tsv', or 'json'")
+                })?);
+            }
+            Long("explain") => {
+                default.filter_explain = true;
+            }
             Long("launch-prefix") => {
                 cli_launch_methods += 1;
                 let prefix = value_as_string(parser, "Launch prefix must be valid UTF-8")?;
@@ -132,6 +145,23 @@ pub(super) fn parse_cli_overrides(
             Long("auto-select") => {
                 default.dmenu_auto_select = true;
             }
+            Short('m') | Long("multi") => {
+                default.multi_select = true;
+                if let Some(value) = parser.optional_value() {
+                    let limit = value
+                        .into_string()
+                        .map_err(|_| CliError::message("Multi-select limit must be valid UTF-8"))?;
+                    default.multi_select_limit = Some(
+                        limit
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|&limit| limit > 0)
+                            .ok_or_else(|| {
+                                CliError::message("Multi-select limit must be a positive integer")
+                            })?,
+                    );
+                }
+            }
             Long("prompt-only") => {
                 default.dmenu_prompt_only = true;
             }
@@ -173,9 +203,9 @@ pub(super) fn parse_cli_overrides(
             }
             Long("match-mode") => {
                 let mode = value_as_string(parser, "Match mode must be valid UTF-8")?;
-                default.match_mode = mode
-                    .parse::<MatchMode>()
-                    .map_err(|_| CliError::message("Invalid match mode. Use 'exact' or 'fuzzy'"))?;
+                default.match_mode = mode.parse::<MatchMode>().map_err(|_| {
+                    CliError::message("Invalid match mode. Use 'exact', 'fuzzy', or 'extended'")
+                })?;
             }
             Long("prefix-depth") => {
                 let depth = value_as_string(parser, "Prefix depth must be valid UTF-8")?;
@@ -200,6 +230,15 @@ pub(super) fn parse_cli_overrides(
                 default.program =
                     Some(value_as_string(parser, "Program name must be valid UTF-8")?);
             }
+            Long("open-with") => {
+                default.open_with = Some(value_as_string(
+                    parser,
+                    "Open-with target must be valid UTF-8",
+                )?);
+            }
+            Long("category") => {
+                default.category = Some(value_as_string(parser, "Category must be valid UTF-8")?);
+            }
             Short('v') | Long("verbose") => {
                 default.verbose = Some(default.verbose.unwrap_or(0) + 1);
             }
@@ -216,6 +255,15 @@ pub(super) fn parse_cli_overrides(
             Short('V') | Long("version") => {
                 return Ok(OverridesResult::Command(CliCommand::PrintVersion));
             }
+            Long("validate-desktop") => {
+                let paths = match parser.values() {
+                    Ok(values) => values.map(std::path::PathBuf::from).collect(),
+                    Err(_) => Vec::new(),
+                };
+                return Ok(OverridesResult::Command(CliCommand::ValidateDesktop {
+                    paths,
+                }));
+            }
             Value(_) => return Err(arg.unexpected().into()),
             _ => return Err(report_unknown_argument(arg)),
         }
@@ -7,6 +7,7 @@ pub enum MatchMode {
     Exact,
     #[default]
     Fuzzy,
+    Extended,
 }
 
 impl FromStr for MatchMode {
@@ -16,6 +17,7 @@ impl FromStr for MatchMode {
         match value.trim().to_lowercase().as_str() {
             "exact" => Ok(Self::Exact),
             "fuzzy" => Ok(Self::Fuzzy),
+            "extended" => Ok(Self::Extended),
             _ => Err(format!("Invalid match mode: '{value}'")),
         }
     }
@@ -28,6 +30,7 @@ pub enum RankingMode {
     Frecency,
     Recency,
     Frequency,
+    Contextual,
 }
 
 impl RankingMode {
@@ -36,6 +39,7 @@ impl RankingMode {
             Self::Frecency => "frecency",
             Self::Recency => "recency",
             Self::Frequency => "frequency",
+            Self::Contextual => "contextual",
         }
     }
 }
@@ -48,11 +52,41 @@ impl FromStr for RankingMode {
             "frecency" => Ok(Self::Frecency),
             "recency" => Ok(Self::Recency),
             "frequency" => Ok(Self::Frequency),
+            "contextual" => Ok(Self::Contextual),
             _ => Err(format!("Invalid ranking mode: '{value}'")),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FrecencyModel {
+    #[default]
+    Stepped,
+    Exponential,
+}
+
+impl FrecencyModel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stepped => "stepped",
+            Self::Exponential => "exponential",
+        }
+    }
+}
+
+impl FromStr for FrecencyModel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "stepped" => Ok(Self::Stepped),
+            "exponential" => Ok(Self::Exponential),
+            _ => Err(format!("Invalid frecency model: '{value}'")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum PinnedOrderMode {
@@ -63,6 +97,7 @@ pub enum PinnedOrderMode {
     OldestPinned,
     #[serde(alias = "newest", alias = "last_pinned")]
     NewestPinned,
+    Manual,
 }
 
 impl PinnedOrderMode {
@@ -72,6 +107,7 @@ impl PinnedOrderMode {
             Self::Alphabetical => "alphabetical",
             Self::OldestPinned => "oldest_pinned",
             Self::NewestPinned => "newest_pinned",
+            Self::Manual => "manual",
         }
     }
 }
@@ -85,7 +121,29 @@ impl FromStr for PinnedOrderMode {
             "alphabetical" => Ok(Self::Alphabetical),
             "oldest_pinned" | "oldest" => Ok(Self::OldestPinned),
             "newest_pinned" | "newest" | "last_pinned" => Ok(Self::NewestPinned),
+            "manual" => Ok(Self::Manual),
             _ => Err(format!("Invalid pinned order mode: '{value}'")),
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterFormat {
+    #[default]
+    Plain,
+    Tsv,
+    Json,
+}
+
+impl FromStr for FilterFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "tsv" => Ok(Self::Tsv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Invalid filter format: '{value}'")),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 mod enums;
 mod opts;
 
-pub use enums::{MatchMode, PinnedOrderMode, RankingMode};
+pub use enums::{FilterFormat, FrecencyModel, MatchMode, PinnedOrderMode, RankingMode};
 pub use opts::Opts;
@@ -1,4 +1,4 @@
-use super::{MatchMode, PinnedOrderMode, RankingMode};
+use super::{FilterFormat, FrecencyModel, MatchMode, PinnedOrderMode, RankingMode};
 use crate::ui::PanelPosition;
 
 /// Command line interface.
@@ -19,6 +19,9 @@ pub struct Opts {
     pub disable_mouse: bool,
     pub no_exec: bool,
     pub stdout: bool,
+    pub filter_query: Option<String>,
+    pub filter_format: Option<FilterFormat>,
+    pub filter_explain: bool,
     pub launch_prefix: Vec<String>,
     pub launch_prefix_set: bool,
     pub systemd_run: bool,
@@ -41,6 +44,9 @@ pub struct Opts {
     pub title_panel_position: Option<PanelPosition>,
     pub program: Option<String>,
     pub search_string: Option<String>,
+    pub launch_targets: Vec<String>,
+    pub open_with: Option<String>,
+    pub category: Option<String>,
     pub confirm_first_launch: bool,
     pub dmenu_mode: bool,
     pub dmenu_with_nth: Option<Vec<usize>>,
@@ -60,6 +66,8 @@ pub struct Opts {
     pub dmenu_auto_select: bool,
     pub dmenu_prompt_only: bool,
     pub dmenu_hide_before_typing: bool,
+    pub multi_select: bool,
+    pub multi_select_limit: Option<usize>,
     pub cclip_mode: bool,
     pub cclip_tag: Option<String>,
     pub cclip_tag_list: bool,
@@ -72,7 +80,17 @@ pub struct Opts {
     pub hide_before_typing: bool,
     pub match_mode: MatchMode,
     pub ranking_mode: RankingMode,
+    pub frecency_model: FrecencyModel,
+    pub frecency_half_life_secs: u64,
+    pub query_learning_weight: u32,
+    pub text_folding: crate::core::ranking::TextFolding,
     pub pinned_order_mode: PinnedOrderMode,
+    pub exec_check: crate::desktop::ExecCheck,
+    pub show_icons: bool,
+    pub icon_theme: Option<String>,
+    pub link_opener: String,
+    pub activation_token_command: Option<String>,
+    pub config_entries: Vec<crate::desktop::App>,
     pub dmenu_highlight_color: Option<ratatui::style::Color>,
     pub dmenu_cursor: Option<String>,
     pub dmenu_hard_stop: Option<bool>,
@@ -131,6 +149,9 @@ impl Default for Opts {
             disable_mouse: false,
             no_exec: false,
             stdout: false,
+            filter_query: None,
+            filter_format: None,
+            filter_explain: false,
             launch_prefix: Vec::new(),
             launch_prefix_set: false,
             systemd_run: false,
@@ -153,6 +174,9 @@ impl Default for Opts {
             title_panel_position: None,
             program: None,
             search_string: None,
+            launch_targets: Vec::new(),
+            open_with: None,
+            category: None,
             confirm_first_launch: false,
             dmenu_mode: false,
             dmenu_with_nth: None,
@@ -172,6 +196,8 @@ impl Default for Opts {
             dmenu_auto_select: false,
             dmenu_prompt_only: false,
             dmenu_hide_before_typing: false,
+            multi_select: false,
+            multi_select_limit: None,
             cclip_mode: false,
             cclip_tag: None,
             cclip_tag_list: false,
@@ -184,7 +210,17 @@ impl Default for Opts {
             hide_before_typing: false,
             match_mode: MatchMode::Fuzzy,
             ranking_mode: RankingMode::Frecency,
+            frecency_model: FrecencyModel::Stepped,
+            frecency_half_life_secs: crate::core::ranking::DEFAULT_HALF_LIFE_SECS,
+            query_learning_weight: crate::core::ranking::DEFAULT_LEARNING_WEIGHT,
+            text_folding: crate::core::ranking::TextFolding::default(),
             pinned_order_mode: PinnedOrderMode::Ranking,
+            exec_check: crate::desktop::ExecCheck::TryExec,
+            show_icons: true,
+            icon_theme: None,
+            link_opener: "xdg-open".to_string(),
+            activation_token_command: None,
+            config_entries: Vec::new(),
             dmenu_highlight_color: None,
             dmenu_cursor: None,
             dmenu_hard_stop: None,
@@ -24,6 +24,68 @@ pub(super) fn validate(default: &mut Opts, cli_launch_methods: usize) -> Result<
         ));
     }
 
+    if default.filter_query.is_none() {
+        if default.filter_format.is_some() || default.filter_explain {
+            return Err(CliError::message(
+                "Error: --format and --explain require --filter <QUERY>\n",
+            ));
+        }
+    } else {
+        if hidden_commands > 0
+            || default.program.is_some()
+            || default.search_string.is_some()
+            || default.stdout
+        {
+            return Err(CliError::message(
+                "Error: --filter cannot be combined with -p/--program, -ss, --stdout, \
+or hidden-entry commands\n",
+            ));
+        }
+        if default.dmenu_prompt_only
+            || default.cclip_tag_list
+            || default.cclip_clear_tags
+            || default.cclip_wipe_tags
+        {
+            return Err(CliError::message(
+                "Error: --filter cannot be combined with --prompt-only or --tag list/clear/wipe\n",
+            ));
+        }
+    }
+
+    if let Some(target) = default.open_with.clone() {
+        if default.dmenu_mode || default.cclip_mode || hidden_commands > 0 {
+            return Err(CliError::message(
+                "Error: --open-with is only available in app-launcher mode\n",
+            ));
+        }
+        if default.program.is_some() || !default.launch_targets.is_empty() {
+            return Err(CliError::message(
+                "Error: --open-with cannot be combined with -p/--program or arguments after --\n",
+            ));
+        }
+        default.launch_targets = vec![target];
+    }
+
+    if default.category.is_some() && (default.dmenu_mode || default.cclip_mode) {
+        return Err(CliError::message(
+            "Error: --category is only available in app-launcher mode\n",
+        ));
+    }
+
+    if default.multi_select && !(default.dmenu_mode || default.cclip_mode) {
+        return Err(CliError::message(
+            "Error: -m/--multi is only available in dmenu and cclip modes\n",
+        ));
+    }
+
+    if !default.launch_targets.is_empty()
+        && (default.dmenu_mode || default.cclip_mode || hidden_commands > 0)
+    {
+        return Err(CliError::message(
+            "Error: files and URLs after -- are only available in app-launcher mode\n",
+        ));
+    }
+
     if default.program.is_some() && default.search_string.is_some() {
         return Err(CliError::message(
             "Error: Cannot use -p/--program and -ss together\n\
@@ -0,0 +1,179 @@
+//! Output of the non-interactive `--filter` mode shared by every mode.
+
+use super::Item;
+use crate::cli::{FilterFormat, MatchMode, Opts};
+use serde_json::{Map, Value, json};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// One ranked match printed by `--filter`.
+#[derive(Debug, Clone)]
+pub(crate) struct FilterRow {
+    /// Score the row was ranked by.
+    pub score: i64,
+    /// What plain output prints for the row.
+    pub text: String,
+    /// Named values printed as TSV columns and JSON keys, in column order.
+    pub fields: Vec<(&'static str, Value)>,
+    /// Why the row ranked where it did, printed with `--explain`.
+    pub breakdown: Value,
+}
+
+/// Breakdown of a dmenu or cclip match: the mode it was scored in and, for
+/// extended queries, the matched char positions of the display text.
+pub(crate) fn item_breakdown(item: &Item, match_mode: MatchMode) -> Value {
+    json!({
+        "match_mode": match_mode,
+        "positions": item.match_indices,
+    })
+}
+
+/// With `-v`, reports on stderr how many of `total` entries matched and how
+/// long ranking them took, leaving stdout to the rows.
+pub(crate) fn report_filter_timing(cli: &Opts, matched: usize, total: usize, elapsed: Duration) {
+    if cli.verbose.unwrap_or(0) > 0 {
+        eprintln!("Ranked {matched} of {total} entries in {elapsed:.2?}");
+    }
+}
+
+/// Prints `rows` to stdout, best match first.
+///
+/// A closed pipe, as in `fsel --filter fire | head -1`, ends the output
+/// without an error.
+pub(crate) fn print_filter_rows(
+    rows: &[FilterRow],
+    format: FilterFormat,
+    explain: bool,
+) -> eyre::Result<()> {
+    let mut stdout = io::BufWriter::new(io::stdout().lock());
+    match write_filter_rows(&mut stdout, rows, format, explain).and_then(|()| stdout.flush()) {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Writes `rows` in `format`.
+///
+/// Plain output is one `text` per line. TSV rows start with the 1-based rank
+/// and the score, followed by the fields. JSON is a single array of objects.
+/// With `explain`, plain and TSV rows end with the breakdown as compact JSON
+/// and JSON objects gain a `breakdown` key.
+pub(crate) fn write_filter_rows(
+    out: &mut impl Write,
+    rows: &[FilterRow],
+    format: FilterFormat,
+    explain: bool,
+) -> io::Result<()> {
+    match format {
+        FilterFormat::Plain => {
+            for row in rows {
+                if explain {
+                    writeln!(out, "{}\t{}", row.text, row.breakdown)?;
+                } else {
+                    writeln!(out, "{}", row.text)?;
+                }
+            }
+        }
+        FilterFormat::Tsv => {
+            for (rank, row) in rows.iter().enumerate() {
+                let mut columns = vec![(rank + 1).to_string(), row.score.to_string()];
+                columns.extend(row.fields.iter().map(|(_, value)| tsv_cell(value)));
+                if explain {
+                    columns.push(row.breakdown.to_string());
+                }
+                writeln!(out, "{}", columns.join("\t"))?;
+            }
+        }
+        FilterFormat::Json => {
+            let objects: Vec<Value> = rows
+                .iter()
+                .enumerate()
+                .map(|(rank, row)| {
+                    let mut object = Map::new();
+                    object.insert("rank".to_string(), Value::from(rank + 1));
+                    object.insert("score".to_string(), Value::from(row.score));
+                    for (name, value) in &row.fields {
+                        object.insert((*name).to_string(), value.clone());
+                    }
+                    if explain {
+                        object.insert("breakdown".to_string(), row.breakdown.clone());
+                    }
+                    Value::Object(object)
+                })
+                .collect();
+            serde_json::to_writer(&mut *out, &objects)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders a field for a TSV column; lists are joined with commas and tabs or
+/// newlines inside values become spaces so every row stays on one line.
+fn tsv_cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(values) => values.iter().map(tsv_cell).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    };
+    text.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterRow, write_filter_rows};
+    use crate::cli::FilterFormat;
+    use serde_json::{Value, json};
+
+    fn rows() -> Vec<FilterRow> {
+        vec![
+            FilterRow {
+                score: 90,
+                text: "Firefox".to_string(),
+                fields: vec![
+                    ("name", json!("Firefox")),
+                    ("categories", json!(["Network", "WebBrowser"])),
+                ],
+                breakdown: json!({ "tier": "prefix" }),
+            },
+            FilterRow {
+                score: 12,
+                text: "Fire\tTool".to_string(),
+                fields: vec![("name", json!("Fire\tTool")), ("categories", json!([]))],
+                breakdown: Value::Null,
+            },
+        ]
+    }
+
+    fn render(format: FilterFormat, explain: bool) -> String {
+        let mut out = Vec::new();
+        write_filter_rows(&mut out, &rows(), format, explain).expect("writing to a Vec");
+        String::from_utf8(out).expect("output should be UTF-8")
+    }
+
+    #[test]
+    fn plain_and_tsv_rows_follow_rank_order() {
+        assert_eq!(render(FilterFormat::Plain, false), "Firefox\nFire\tTool\n");
+        assert_eq!(
+            render(FilterFormat::Tsv, false),
+            "1\t90\tFirefox\tNetwork,WebBrowser\n2\t12\tFire Tool\t\n"
+        );
+        assert_eq!(
+            render(FilterFormat::Tsv, true).lines().next(),
+            Some("1\t90\tFirefox\tNetwork,WebBrowser\t{\"tier\":\"prefix\"}")
+        );
+    }
+
+    #[test]
+    fn json_rows_carry_rank_score_and_optional_breakdown() {
+        let plain: Value = serde_json::from_str(&render(FilterFormat::Json, false)).unwrap();
+        assert_eq!(plain[0]["rank"], 1);
+        assert_eq!(plain[1]["score"], 12);
+        assert_eq!(plain[0]["categories"], json!(["Network", "WebBrowser"]));
+        assert!(plain[0].get("breakdown").is_none());
+
+        let explained: Value = serde_json::from_str(&render(FilterFormat::Json, true)).unwrap();
+        assert_eq!(explained[0]["breakdown"]["tier"], "prefix");
+    }
+}
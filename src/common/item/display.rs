@@ -1,6 +1,6 @@
 use super::Item;
 use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::ListItem;
 
 impl Item {
@@ -48,25 +48,42 @@ impl Item {
         &'a self,
         tag_metadata: Option<&'a crate::modes::cclip::TagMetadataFormatter>,
     ) -> ListItem<'a> {
+        ListItem::new(self.to_text(tag_metadata))
+    }
+
+    /// Styled display text: tags colored with `tag_metadata` if given,
+    /// otherwise matched characters highlighted.
+    pub fn to_text<'a>(
+        &'a self,
+        tag_metadata: Option<&'a crate::modes::cclip::TagMetadataFormatter>,
+    ) -> Text<'a> {
         if let Some(actual_tags) = &self.tags
             && !actual_tags.is_empty()
             && let Some(formatter) = tag_metadata
             && let Some((tag_start, tag_end)) = tag_bounds(&self.display_text)
         {
-            return build_tagged_list_item(self, actual_tags, formatter, tag_start, tag_end);
+            return build_tagged_line(self, actual_tags, formatter, tag_start, tag_end).into();
         }
 
-        ListItem::new(self.display_text.clone())
+        if self.match_indices.is_empty() {
+            return Text::from(self.display_text.as_str());
+        }
+        Line::from(crate::ui::match_spans(
+            &self.display_text,
+            &self.match_indices,
+            Style::default(),
+        ))
+        .into()
     }
 }
 
-fn build_tagged_list_item<'a>(
+fn build_tagged_line<'a>(
     item: &'a Item,
     actual_tags: &'a [String],
     formatter: &'a crate::modes::cclip::TagMetadataFormatter,
     tag_start: usize,
     tag_end: usize,
-) -> ListItem<'a> {
+) -> Line<'a> {
     let mut spans = Vec::new();
 
     if tag_start > 0 {
@@ -104,7 +121,7 @@ fn build_tagged_list_item<'a>(
         }
     }
 
-    ListItem::new(Line::from(spans))
+    Line::from(spans)
 }
 
 fn tag_bounds(display_text: &str) -> Option<(usize, usize)> {
@@ -1,15 +1,21 @@
 use super::Item;
+use crate::core::ranking::{ExtendedQuery, TextFolding};
 use nucleo_matcher::{Matcher, Utf32Str};
 
 impl Item {
     /// Calculate fuzzy match score against query.
     #[inline]
-    pub fn calculate_score(&self, query: &str, matcher: &mut Matcher) -> Option<i64> {
+    pub fn calculate_score(
+        &self,
+        query: &str,
+        matcher: &mut Matcher,
+        folding: &TextFolding,
+    ) -> Option<i64> {
         if query.is_empty() {
             return Some(0);
         }
 
-        let query_lower = query.to_lowercase();
+        let query_lower = folding.fold(query).to_lowercase();
         let mut query_chars = Vec::new();
         let query_utf32 = Utf32Str::new(&query_lower, &mut query_chars);
 
@@ -17,7 +23,7 @@ impl Item {
             crate::modes::cclip::CclipItem::from_line(self.original_line.clone())
         {
             for tag in &cclip_item.tags {
-                let tag_lower = tag.to_lowercase();
+                let tag_lower = folding.fold(tag).to_lowercase();
                 if tag_lower == query_lower {
                     return Some(1_000_000);
                 }
@@ -33,14 +39,14 @@ impl Item {
             }
         }
 
-        let display_lower = self.display_text.to_lowercase();
+        let display_lower = folding.fold(&self.display_text).to_lowercase();
         let mut display_chars = Vec::new();
         let display_utf32 = Utf32Str::new(&display_lower, &mut display_chars);
         if let Some(score) = matcher.fuzzy_match(display_utf32, query_utf32) {
             return Some((score as i64) * 2);
         }
 
-        let original_lower = self.original_line.to_lowercase();
+        let original_lower = folding.fold(&self.original_line).to_lowercase();
         let mut original_chars = Vec::new();
         let original_utf32 = Utf32Str::new(&original_lower, &mut original_chars);
         matcher
@@ -50,7 +56,7 @@ impl Item {
 
     /// Calculate exact match score against query.
     #[inline]
-    pub fn calculate_exact_score(&self, query: &str) -> Option<i64> {
+    pub fn calculate_exact_score(&self, query: &str, folding: &TextFolding) -> Option<i64> {
         if query.is_empty() {
             return Some(0);
         }
@@ -64,8 +70,8 @@ impl Item {
             (false, query)
         };
 
-        let query_lower = search_query.to_lowercase();
-        let display_lower = self.display_text.to_lowercase();
+        let query_lower = folding.fold(search_query).to_lowercase();
+        let display_lower = folding.fold(&self.display_text).to_lowercase();
 
         if is_quoted {
             return (display_lower == query_lower).then_some(1000);
@@ -83,18 +89,49 @@ impl Item {
         None
     }
 
+    /// Calculate extended-syntax match score against `query`, recording the
+    /// matched positions of the display text for highlighting.
+    ///
+    /// `query` must have been parsed from text folded with `folding`.
+    pub fn calculate_extended_score(
+        &mut self,
+        query: &ExtendedQuery,
+        matcher: &mut Matcher,
+        folding: &TextFolding,
+    ) -> Option<i64> {
+        let mut fields = vec![
+            folding.fold(&self.display_text),
+            folding.fold(&self.original_line),
+        ];
+        if let Some(tags) = &self.tags {
+            fields.extend(tags.iter().map(|tag| folding.fold(tag)));
+        }
+        let fields: Vec<&str> = fields.iter().map(|field| field.as_ref()).collect();
+
+        let matched = query.match_fields(&fields, matcher)?;
+        self.match_indices = if folding.is_enabled() {
+            folding
+                .fold_mapped(&self.display_text)
+                .original_indices(&matched.indices)
+        } else {
+            matched.indices
+        };
+        Some(i64::from(matched.score))
+    }
+
     /// Calculate match score based on `match_nth` columns.
     pub fn calculate_score_with_match_nth(
         &self,
         query: &str,
         matcher: &mut Matcher,
         match_nth: &[usize],
+        folding: &TextFolding,
     ) -> Option<i64> {
         if query.is_empty() {
             return Some(0);
         }
 
-        let query_lower = query.to_lowercase();
+        let query_lower = folding.fold(query).to_lowercase();
         let mut query_chars = Vec::new();
         let query_utf32 = Utf32Str::new(&query_lower, &mut query_chars);
         let mut best_score = None;
@@ -102,7 +139,7 @@ impl Item {
         for &column_index in match_nth {
             if column_index > 0 && column_index <= self.columns.len() {
                 let col_text = &self.columns[column_index - 1];
-                let col_lower = col_text.to_lowercase();
+                let col_lower = folding.fold(col_text).to_lowercase();
                 let mut col_chars = Vec::new();
                 let col_utf32 = Utf32Str::new(&col_lower, &mut col_chars);
                 if let Some(score) = matcher.fuzzy_match(col_utf32, query_utf32) {
@@ -18,6 +18,8 @@ pub struct Item {
     pub line_number: usize,
     /// Tags for cclip items (`None` for dmenu items).
     pub tags: Option<Vec<String>>,
+    /// Char positions of `display_text` matched by an extended query.
+    pub match_indices: Vec<u32>,
 }
 
 impl Item {
@@ -38,6 +40,7 @@ impl Item {
             score: 0,
             line_number,
             tags: None,
+            match_indices: Vec::new(),
         }
     }
 
@@ -51,6 +54,7 @@ impl Item {
             score: 0,
             line_number,
             tags: None,
+            match_indices: Vec::new(),
         }
     }
 
@@ -68,7 +72,7 @@ impl<'a> From<Item> for ListItem<'a> {
 
 impl<'a> From<&'a Item> for ListItem<'a> {
     fn from(item: &'a Item) -> ListItem<'a> {
-        ListItem::new(item.display_text.clone())
+        ListItem::new(item.to_text(None))
     }
 }
 
@@ -1,3 +1,7 @@
+mod filter_output;
 mod item;
 
+pub(crate) use filter_output::{
+    FilterRow, item_breakdown, print_filter_rows, report_filter_timing,
+};
 pub use item::Item;
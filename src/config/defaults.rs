@@ -74,6 +74,8 @@ impl Default for GeneralConfig {
             no_exec: false,
             confirm_first_launch: false,
             prefix_depth: default_prefix_depth(),
+            normalize_unicode: false,
+            transliterate: false,
         }
     }
 }
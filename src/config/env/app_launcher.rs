@@ -1,6 +1,7 @@
 use super::helpers::{
-    BOOLEAN_EXPECTED, INTEGER_EXPECTED, MATCH_MODE_EXPECTED, OverrideSource, PINNED_ORDER_EXPECTED,
-    RANKING_MODE_EXPECTED, set_optional_launch_prefix, set_optional_parsed,
+    BOOLEAN_EXPECTED, FRECENCY_MODEL_EXPECTED, INTEGER_EXPECTED, MATCH_MODE_EXPECTED,
+    OverrideSource, PINNED_ORDER_EXPECTED, RANKING_MODE_EXPECTED, set_optional_launch_prefix,
+    set_optional_parsed, set_optional_string,
 };
 use crate::config::{ConfigError, FselConfig};
 
@@ -52,6 +53,24 @@ pub(super) fn apply(cfg: &mut FselConfig, source: &impl OverrideSource) -> Resul
         &mut cfg.app_launcher.ranking_mode,
         RANKING_MODE_EXPECTED,
     )?;
+    set_optional_parsed(
+        source,
+        "FSEL_APP_LAUNCHER_FRECENCY_MODEL",
+        &mut cfg.app_launcher.frecency_model,
+        FRECENCY_MODEL_EXPECTED,
+    )?;
+    set_optional_parsed(
+        source,
+        "FSEL_APP_LAUNCHER_FRECENCY_HALF_LIFE_HOURS",
+        &mut cfg.app_launcher.frecency_half_life_hours,
+        INTEGER_EXPECTED,
+    )?;
+    set_optional_parsed(
+        source,
+        "FSEL_APP_LAUNCHER_QUERY_LEARNING_WEIGHT",
+        &mut cfg.app_launcher.query_learning_weight,
+        INTEGER_EXPECTED,
+    )?;
     set_optional_parsed(
         source,
         "FSEL_APP_LAUNCHER_PINNED_ORDER",
@@ -70,5 +89,32 @@ pub(super) fn apply(cfg: &mut FselConfig, source: &impl OverrideSource) -> Resul
         &mut cfg.app_launcher.prefix_depth,
         INTEGER_EXPECTED,
     )?;
+    set_optional_parsed(
+        source,
+        "FSEL_APP_LAUNCHER_CHECK_EXEC",
+        &mut cfg.app_launcher.check_exec,
+        BOOLEAN_EXPECTED,
+    )?;
+    set_optional_parsed(
+        source,
+        "FSEL_APP_LAUNCHER_SHOW_ICONS",
+        &mut cfg.app_launcher.show_icons,
+        BOOLEAN_EXPECTED,
+    )?;
+    set_optional_string(
+        source,
+        "FSEL_APP_LAUNCHER_ICON_THEME",
+        &mut cfg.app_launcher.icon_theme,
+    );
+    set_optional_string(
+        source,
+        "FSEL_APP_LAUNCHER_LINK_OPENER",
+        &mut cfg.app_launcher.link_opener,
+    );
+    set_optional_string(
+        source,
+        "FSEL_APP_LAUNCHER_ACTIVATION_TOKEN_COMMAND",
+        &mut cfg.app_launcher.activation_token_command,
+    );
     Ok(())
 }
@@ -77,5 +77,17 @@ pub(super) fn apply(cfg: &mut FselConfig, source: &impl OverrideSource) -> Resul
         &mut cfg.general.prefix_depth,
         INTEGER_EXPECTED,
     )?;
+    set_parsed(
+        source,
+        "FSEL_NORMALIZE_UNICODE",
+        &mut cfg.general.normalize_unicode,
+        BOOLEAN_EXPECTED,
+    )?;
+    set_parsed(
+        source,
+        "FSEL_TRANSLITERATE",
+        &mut cfg.general.transliterate,
+        BOOLEAN_EXPECTED,
+    )?;
     Ok(())
 }
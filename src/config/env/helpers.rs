@@ -4,10 +4,12 @@ use std::str::FromStr;
 
 pub(super) const BOOLEAN_EXPECTED: &str = "true or false";
 pub(super) const INTEGER_EXPECTED: &str = "an unsigned integer";
-pub(super) const MATCH_MODE_EXPECTED: &str = "'fuzzy' or 'exact'";
-pub(super) const RANKING_MODE_EXPECTED: &str = "'frecency', 'recency', or 'frequency'";
+pub(super) const MATCH_MODE_EXPECTED: &str = "'fuzzy', 'exact', or 'extended'";
+pub(super) const RANKING_MODE_EXPECTED: &str =
+    "'frecency', 'recency', 'frequency', or 'contextual'";
+pub(super) const FRECENCY_MODEL_EXPECTED: &str = "'stepped' or 'exponential'";
 pub(super) const PINNED_ORDER_EXPECTED: &str =
-    "'ranking', 'alphabetical', 'oldest', 'oldest_pinned', 'newest', or 'newest_pinned'";
+    "'ranking', 'alphabetical', 'oldest', 'oldest_pinned', 'newest', 'newest_pinned', or 'manual'";
 pub(super) const PANEL_POSITION_EXPECTED: &str = "'top', 'middle', or 'bottom'";
 pub(super) const LAUNCH_PREFIX_EXPECTED: &str = "a shell-words command prefix";
 
@@ -14,6 +14,8 @@ pub enum ConfigError {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfigValidationError {
     MultipleLaunchMethods,
+    InvalidEntry { name: String, reason: String },
+    InvalidTransliteration { key: String },
 }
 
 impl std::fmt::Display for ConfigError {
@@ -43,6 +45,13 @@ impl std::fmt::Display for ConfigValidationError {
                 f,
                 "Only one launch method can be specified at a time in configuration"
             ),
+            Self::InvalidEntry { name, reason } => {
+                write!(f, "Invalid [[entries]] item '{name}': {reason}")
+            }
+            Self::InvalidTransliteration { key } => write!(
+                f,
+                "Invalid [transliteration] key '{key}': expected a single character"
+            ),
         }
     }
 }
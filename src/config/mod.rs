@@ -7,7 +7,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 pub use error::{ConfigError, ConfigValidationError};
-pub use schema::FselConfig;
+pub use schema::{EntryConfig, FselConfig};
 
 #[derive(Debug)]
 struct LoadedConfig {
@@ -36,6 +36,30 @@ impl FselConfig {
             return Err(ConfigValidationError::MultipleLaunchMethods);
         }
 
+        let mut names = std::collections::HashSet::new();
+        for entry in &self.entries {
+            let invalid = |reason: String| ConfigValidationError::InvalidEntry {
+                name: entry.name.clone(),
+                reason,
+            };
+            if entry.name.trim().is_empty() {
+                return Err(invalid("name must not be empty".to_string()));
+            }
+            if !names.insert(entry.name.as_str()) {
+                return Err(invalid("another entry has the same name".to_string()));
+            }
+            crate::desktop::App::from_config_entry(entry)
+                .map_err(|error| invalid(error.to_string()))?;
+        }
+
+        if let Some(key) = self
+            .transliteration
+            .keys()
+            .find(|key| key.chars().count() != 1)
+        {
+            return Err(ConfigValidationError::InvalidTransliteration { key: key.clone() });
+        }
+
         Ok(())
     }
 }
@@ -95,7 +119,7 @@ mod tests {
     use super::{
         ConfigError, ConfigValidationError, FselConfig, load_config_file, load_standalone_keybinds,
     };
-    use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+    use crate::cli::{FrecencyModel, MatchMode, PinnedOrderMode, RankingMode};
     use crate::ui::PanelPosition;
     use crossterm::event::{KeyCode, KeyModifiers};
     use std::fs;
@@ -259,6 +283,9 @@ title_panel_position = "Top"
 [app_launcher]
 match_mode = "FuZzY"
 ranking_mode = "RECENCY"
+frecency_model = "Exponential"
+frecency_half_life_hours = 24
+query_learning_weight = 0
 pinned_order = "OLDEST_PINNED"
 
 [dmenu]
@@ -277,6 +304,12 @@ title_panel_position = "Middle"
         assert_eq!(config.layout.title_panel_position, PanelPosition::Top);
         assert_eq!(config.app_launcher.match_mode, Some(MatchMode::Fuzzy));
         assert_eq!(config.app_launcher.ranking_mode, Some(RankingMode::Recency));
+        assert_eq!(
+            config.app_launcher.frecency_model,
+            Some(FrecencyModel::Exponential)
+        );
+        assert_eq!(config.app_launcher.frecency_half_life_hours, Some(24));
+        assert_eq!(config.app_launcher.query_learning_weight, Some(0));
         assert_eq!(
             config.app_launcher.pinned_order,
             Some(PinnedOrderMode::OldestPinned)
@@ -350,6 +383,35 @@ title_panel_position = "upside_down"
         assert_eq!(layout.title_panel_position, PanelPosition::Top);
     }
 
+    #[test]
+    fn transliteration_table_deserializes_and_rejects_multi_char_keys() {
+        let mut config: FselConfig = toml::from_str(
+            r#"
+normalize_unicode = true
+transliterate = true
+
+[transliteration]
+"ö" = "oe"
+"#,
+        )
+        .expect("config should deserialize");
+
+        assert!(config.general.normalize_unicode);
+        assert!(config.general.transliterate);
+        assert_eq!(config.transliteration["ö"], "oe");
+        assert!(config.validate().is_ok());
+
+        config
+            .transliteration
+            .insert("ae".to_string(), "a".to_string());
+        assert_eq!(
+            config.validate(),
+            Err(ConfigValidationError::InvalidTransliteration {
+                key: "ae".to_string()
+            })
+        );
+    }
+
     #[test]
     fn missing_app_launcher_filter_actions_key_stays_unset() {
         let config: FselConfig = toml::from_str(
@@ -371,4 +433,34 @@ terminal_launcher = "kitty -e"
         let error = config.validate().unwrap_err();
         assert_eq!(error, ConfigValidationError::MultipleLaunchMethods);
     }
+
+    #[test]
+    fn entries_deserialize_and_duplicate_names_are_rejected() {
+        let mut config: FselConfig = toml::from_str(
+            r#"
+terminal_launcher = "kitty -e"
+
+[[entries]]
+name = "VPN up"
+exec = "nmcli con up work"
+keywords = ["network", "vpn"]
+
+[app_launcher]
+filter_actions = true
+"#,
+        )
+        .expect("config should deserialize");
+
+        assert_eq!(config.entries.len(), 1);
+        assert_eq!(config.entries[0].exec, "nmcli con up work");
+        assert!(!config.entries[0].terminal);
+        assert_eq!(config.app_launcher.filter_actions, Some(true));
+        assert!(config.validate().is_ok());
+
+        config.entries.push(config.entries[0].clone());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigValidationError::InvalidEntry { name, .. }) if name == "VPN up"
+        ));
+    }
 }
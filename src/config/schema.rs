@@ -1,7 +1,8 @@
 use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+use crate::cli::{FrecencyModel, MatchMode, PinnedOrderMode, RankingMode};
 use crate::ui::PanelPosition;
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -18,6 +19,29 @@ pub struct FselConfig {
     pub cclip: CclipConfig,
     #[serde(default)]
     pub app_launcher: AppLauncherConfig,
+    /// `[[entries]]`: launcher entries declared in the config instead of a desktop file.
+    #[serde(default)]
+    pub entries: Vec<EntryConfig>,
+    /// `[transliteration]`: replacement text for single characters before matching.
+    #[serde(default)]
+    pub transliteration: HashMap<String, String>,
+}
+
+/// One `[[entries]]` table.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EntryConfig {
+    pub name: String,
+    pub exec: String,
+    #[serde(default)]
+    pub terminal: bool,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    pub comment: Option<String>,
+    pub generic_name: Option<String>,
+    pub icon: Option<String>,
+    pub path: Option<String>,
 }
 
 /// Legacy `[app_launcher]` section for backward compatibility.
@@ -34,9 +58,18 @@ pub struct AppLauncherConfig {
     #[serde(default, deserialize_with = "deserialize_optional_parsed")]
     pub ranking_mode: Option<RankingMode>,
     #[serde(default, deserialize_with = "deserialize_optional_parsed")]
+    pub frecency_model: Option<FrecencyModel>,
+    pub frecency_half_life_hours: Option<u64>,
+    pub query_learning_weight: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_optional_parsed")]
     pub pinned_order: Option<PinnedOrderMode>,
     pub confirm_first_launch: Option<bool>,
     pub prefix_depth: Option<usize>,
+    pub check_exec: Option<bool>,
+    pub show_icons: Option<bool>,
+    pub icon_theme: Option<String>,
+    pub link_opener: Option<String>,
+    pub activation_token_command: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -76,6 +109,10 @@ pub struct GeneralConfig {
     pub confirm_first_launch: bool,
     #[serde(default = "super::defaults::default_prefix_depth")]
     pub prefix_depth: usize,
+    #[serde(default)]
+    pub normalize_unicode: bool,
+    #[serde(default)]
+    pub transliterate: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
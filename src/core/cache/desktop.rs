@@ -1,4 +1,6 @@
-use super::tables::{DESKTOP_CACHE_TABLE, FILE_LIST_TABLE, NAME_INDEX_TABLE};
+use super::tables::{
+    DESKTOP_CACHE_META_TABLE, DESKTOP_CACHE_TABLE, FILE_LIST_TABLE, NAME_INDEX_TABLE,
+};
 use crate::desktop::App;
 use eyre::{Result, eyre};
 use redb::{Database, ReadableDatabase, ReadableTable};
@@ -10,6 +12,14 @@ use std::sync::Arc;
 use std::time::SystemTime;
 
 const FILE_LIST_CACHE_KEY: &str = "paths";
+const CACHE_FORMAT_KEY: &str = "format";
+
+/// Layout version of the rows in [`DESKTOP_CACHE_TABLE`].
+///
+/// postcard rows do not describe their own fields, so bump this whenever
+/// `App` or `CacheEntry` changes shape; rows of any other version are dropped
+/// when the cache is opened.
+const CACHE_FORMAT_VERSION: u64 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CacheEntry {
@@ -40,9 +50,17 @@ impl DesktopCache {
     pub fn new(db: Arc<Database>) -> Result<Self> {
         let write_txn = db.begin_write()?;
         {
-            let _ = write_txn.open_table(DESKTOP_CACHE_TABLE)?;
-            let _ = write_txn.open_table(NAME_INDEX_TABLE)?;
+            let mut cache_table = write_txn.open_table(DESKTOP_CACHE_TABLE)?;
+            let mut index_table = write_txn.open_table(NAME_INDEX_TABLE)?;
             let _ = write_txn.open_table(FILE_LIST_TABLE)?;
+            let mut meta_table = write_txn.open_table(DESKTOP_CACHE_META_TABLE)?;
+
+            let format = meta_table.get(CACHE_FORMAT_KEY)?.map(|value| value.value());
+            if format != Some(CACHE_FORMAT_VERSION) {
+                remove_all_rows(&mut cache_table)?;
+                remove_all_rows(&mut index_table)?;
+                meta_table.insert(CACHE_FORMAT_KEY, CACHE_FORMAT_VERSION)?;
+            }
         }
         write_txn.commit()?;
 
@@ -169,7 +187,7 @@ impl DesktopCache {
     }
 }
 
-fn collect_dir_mtimes(scanned_dirs: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+pub(super) fn collect_dir_mtimes(scanned_dirs: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
     let mut dir_mtimes = HashMap::new();
 
     for dir in scanned_dirs {
@@ -412,6 +430,136 @@ mod tests {
         let _ = fs::remove_dir_all(dir);
     }
 
+    /// `App` as cached before `exec` and `url` were added.
+    #[derive(serde::Serialize)]
+    struct BaselineApp {
+        name: String,
+        command: String,
+        description: String,
+        generic_name: Option<String>,
+        keywords: Vec<String>,
+        categories: Vec<String>,
+        mime_types: Vec<String>,
+        icon: Option<String>,
+        is_terminal: bool,
+        path: Option<String>,
+        only_show_in: Vec<String>,
+        not_show_in: Vec<String>,
+        hidden: bool,
+        startup_notify: bool,
+        startup_wm_class: Option<String>,
+        try_exec: Option<String>,
+        entry_type: String,
+        desktop_id: Option<String>,
+        score: i64,
+        history: u64,
+        pinned: bool,
+        last_access: Option<u64>,
+        breakdown: Option<()>,
+        actions: Option<Vec<String>>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct BaselineCacheEntry {
+        app: BaselineApp,
+        mtime: SystemTime,
+    }
+
+    #[test]
+    fn rows_from_an_older_cache_format_are_dropped_on_open() {
+        let dir = test_temp_dir("baseline-format");
+        let db = Arc::new(
+            redb::Database::create(dir.join("desktop-cache.redb"))
+                .expect("database should be created"),
+        );
+        let desktop_path = dir.join("baseline.desktop");
+        fs::write(
+            &desktop_path,
+            "[Desktop Entry]\nType=Application\nName=Baseline\nExec=/bin/true\n",
+        )
+        .expect("desktop entry should be written");
+        let mtime = fs::metadata(&desktop_path)
+            .and_then(|metadata| metadata.modified())
+            .expect("desktop mtime should be readable");
+
+        let entry = BaselineCacheEntry {
+            app: BaselineApp {
+                name: "Baseline".to_string(),
+                command: "/bin/true".to_string(),
+                description: String::new(),
+                generic_name: None,
+                keywords: Vec::new(),
+                categories: vec!["Utility".to_string()],
+                mime_types: Vec::new(),
+                icon: Some("baseline".to_string()),
+                is_terminal: false,
+                path: None,
+                only_show_in: Vec::new(),
+                not_show_in: Vec::new(),
+                hidden: false,
+                startup_notify: false,
+                startup_wm_class: None,
+                try_exec: None,
+                entry_type: "Application".to_string(),
+                desktop_id: Some("baseline.desktop".to_string()),
+                score: 0,
+                history: 0,
+                pinned: false,
+                last_access: None,
+                breakdown: None,
+                actions: None,
+            },
+            mtime,
+        };
+        let data = postcard::to_allocvec(&entry).expect("baseline entry should encode");
+        let path_key = crate::core::path_key::encode(&desktop_path);
+
+        // A database written by a release without the format key.
+        let write_txn = db.begin_write().expect("write transaction should open");
+        {
+            let mut cache_table = write_txn
+                .open_table(super::DESKTOP_CACHE_TABLE)
+                .expect("cache table should open");
+            let mut index_table = write_txn
+                .open_table(super::NAME_INDEX_TABLE)
+                .expect("name index should open");
+            cache_table
+                .insert(path_key.as_str(), data.as_slice())
+                .expect("baseline row should insert");
+            index_table
+                .insert("Baseline", path_key.as_bytes())
+                .expect("baseline name index should insert");
+        }
+        write_txn.commit().expect("baseline write should commit");
+
+        let cache = DesktopCache::new(Arc::clone(&db)).expect("desktop cache should initialize");
+        assert!(
+            cache
+                .get(&desktop_path)
+                .expect("lookup should succeed")
+                .is_none()
+        );
+        assert!(
+            cache
+                .get_by_name("Baseline")
+                .expect("name lookup should succeed")
+                .is_none()
+        );
+
+        let app = sample_app("Baseline");
+        cache
+            .set(&desktop_path, app.clone())
+            .expect("cache set should succeed");
+        let reopened = DesktopCache::new(Arc::clone(&db)).expect("desktop cache should reopen");
+        let loaded = reopened
+            .get(&desktop_path)
+            .expect("lookup should succeed")
+            .expect("rows in the current format should survive reopening");
+        assert_eq!(loaded.command, app.command);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
     #[test]
     fn legacy_cache_keys_remain_readable_after_upgrade() {
         let dir = test_temp_dir("legacy-cache");
@@ -0,0 +1,121 @@
+use super::desktop::collect_dir_mtimes;
+use super::tables::EXEC_CACHE_TABLE;
+use eyre::Result;
+use redb::{Database, ReadableDatabase};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+const EXEC_CACHE_KEY: &str = "path";
+
+/// Whether each program name was found on `PATH`, valid while the `PATH`
+/// directories keep the modification times recorded here.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ExecIndex {
+    path_dirs: Vec<PathBuf>,
+    dir_mtimes: HashMap<PathBuf, SystemTime>,
+    programs: HashMap<String, bool>,
+}
+
+pub struct ExecCache {
+    db: Arc<Database>,
+}
+
+impl ExecCache {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        let write_txn = db.begin_write()?;
+        {
+            let _ = write_txn.open_table(EXEC_CACHE_TABLE)?;
+        }
+        write_txn.commit()?;
+
+        Ok(Self { db })
+    }
+
+    /// Cached lookups for `path_dirs`, or an empty map when `PATH` or any of its
+    /// directories changed since they were stored.
+    pub fn load(&self, path_dirs: &[PathBuf]) -> Result<HashMap<String, bool>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(EXEC_CACHE_TABLE)?;
+
+        if let Some(data) = table.get(EXEC_CACHE_KEY)?
+            && let Ok(index) = postcard::from_bytes::<ExecIndex>(data.value())
+            && index.path_dirs == path_dirs
+            && index.dir_mtimes == collect_dir_mtimes(path_dirs)
+        {
+            return Ok(index.programs);
+        }
+
+        Ok(HashMap::new())
+    }
+
+    pub fn store(&self, path_dirs: &[PathBuf], programs: &HashMap<String, bool>) -> Result<()> {
+        let index = ExecIndex {
+            path_dirs: path_dirs.to_vec(),
+            dir_mtimes: collect_dir_mtimes(path_dirs),
+            programs: programs.clone(),
+        };
+        let data = postcard::to_allocvec(&index)?;
+
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(EXEC_CACHE_TABLE)?;
+            table.insert(EXEC_CACHE_KEY, data.as_slice())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(EXEC_CACHE_TABLE)?;
+            table.remove(EXEC_CACHE_KEY)?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExecCache;
+    use crate::testing::temp_dir;
+    use std::collections::HashMap;
+    use std::fs;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn cached_programs_are_dropped_when_path_changes() {
+        let dir = temp_dir("exec-cache-path-change");
+        let bin = dir.join("bin");
+        fs::create_dir_all(&bin).expect("bin dir should be created");
+        let db = Arc::new(
+            redb::Database::create(dir.join("cache.redb")).expect("database should be created"),
+        );
+        let cache = ExecCache::new(db).expect("exec cache should initialize");
+        let programs = HashMap::from([("editor".to_string(), false)]);
+
+        cache
+            .store(std::slice::from_ref(&bin), &programs)
+            .expect("programs should be stored");
+
+        assert_eq!(
+            cache
+                .load(std::slice::from_ref(&bin))
+                .expect("programs should load"),
+            programs
+        );
+        assert!(
+            cache
+                .load(&[bin.clone(), dir.join("other")])
+                .expect("programs should load")
+                .is_empty()
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
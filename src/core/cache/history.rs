@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 const PINNED_APPS_KEY: &str = "pinned_apps";
 
+/// Launch counts and pins, keyed by [`App::store_key`].
 pub struct HistoryCache {
     pub history: HashMap<String, u64>,
     pub pinned: HashSet<String>,
@@ -36,27 +37,18 @@ impl HistoryCache {
         Ok(Self { history, pinned })
     }
 
-    pub fn get_history(&self, app_name: &str) -> u64 {
-        self.history.get(app_name).copied().unwrap_or(0)
+    pub fn get_history(&self, key: &str) -> u64 {
+        self.history.get(key).copied().unwrap_or(0)
     }
 
-    pub fn is_pinned(&self, app_name: &str) -> bool {
-        self.pinned.contains(app_name)
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.pinned.contains(key)
     }
 
     pub fn apply_to_app(&self, mut app: App) -> App {
-        app.history = self.get_history(&app.name);
-        app.pinned = self.is_pinned(&app.name);
+        let key = app.store_key();
+        app.history = self.get_history(&key);
+        app.pinned = self.is_pinned(&key);
         app
     }
-
-    pub fn get_best_match(&self, prefix: &str) -> Option<(&String, u64)> {
-        let prefix_lower = prefix.to_lowercase();
-
-        self.history
-            .iter()
-            .filter(|(name, _)| name.to_lowercase().starts_with(&prefix_lower))
-            .max_by_key(|(_, count)| *count)
-            .map(|(name, count)| (name, *count))
-    }
 }
@@ -0,0 +1,145 @@
+use super::desktop::collect_dir_mtimes;
+use super::tables::ICON_CACHE_TABLE;
+use eyre::Result;
+use redb::{Database, ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Resolved icon paths for one theme/size/scale, stored as a single row.
+///
+/// Misses are cached too (`None`), so the row is only trusted while the theme
+/// directories keep the modification times they had when it was written.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IconIndex {
+    dir_mtimes: HashMap<PathBuf, SystemTime>,
+    icons: HashMap<String, Option<PathBuf>>,
+}
+
+pub struct IconCache {
+    db: Arc<Database>,
+}
+
+impl IconCache {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        let write_txn = db.begin_write()?;
+        {
+            let _ = write_txn.open_table(ICON_CACHE_TABLE)?;
+        }
+        write_txn.commit()?;
+
+        Ok(Self { db })
+    }
+
+    /// Cached icon names for `key`, or an empty map when the row is stale.
+    pub fn load(
+        &self,
+        key: &str,
+        watched_dirs: &[PathBuf],
+    ) -> Result<HashMap<String, Option<PathBuf>>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(ICON_CACHE_TABLE)?;
+
+        if let Some(data) = table.get(key)?
+            && let Ok(index) = postcard::from_bytes::<IconIndex>(data.value())
+            && index.dir_mtimes == collect_dir_mtimes(watched_dirs)
+        {
+            let icons = index
+                .icons
+                .into_iter()
+                .filter(|(_, path)| path.as_ref().is_none_or(|path| path.is_file()))
+                .collect();
+            return Ok(icons);
+        }
+
+        Ok(HashMap::new())
+    }
+
+    pub fn store(
+        &self,
+        key: &str,
+        watched_dirs: &[PathBuf],
+        icons: &HashMap<String, Option<PathBuf>>,
+    ) -> Result<()> {
+        let index = IconIndex {
+            dir_mtimes: collect_dir_mtimes(watched_dirs),
+            icons: icons.clone(),
+        };
+        let data = postcard::to_allocvec(&index)?;
+
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(ICON_CACHE_TABLE)?;
+            table.insert(key, data.as_slice())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(ICON_CACHE_TABLE)?;
+            let keys: Vec<String> = table
+                .iter()?
+                .map(|result| result.map(|(key, _)| key.value().to_string()))
+                .collect::<std::result::Result<_, _>>()?;
+            for key in keys {
+                table.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IconCache;
+    use crate::testing::temp_dir;
+    use std::collections::HashMap;
+    use std::fs;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn stored_icons_round_trip_until_the_theme_changes() {
+        let dir = temp_dir("icon-cache-round-trip");
+        let theme_dir = dir.join("hicolor");
+        fs::create_dir_all(&theme_dir).expect("theme dir should be created");
+        let icon_path = theme_dir.join("editor.png");
+        fs::write(&icon_path, "png").expect("icon should be written");
+        let db = Arc::new(
+            redb::Database::create(dir.join("cache.redb")).expect("database should be created"),
+        );
+        let cache = IconCache::new(db).expect("icon cache should initialize");
+        let icons = HashMap::from([
+            ("editor".to_string(), Some(icon_path.clone())),
+            ("missing".to_string(), None),
+        ]);
+
+        cache
+            .store("hicolor:24@1", std::slice::from_ref(&theme_dir), &icons)
+            .expect("icons should be stored");
+        let loaded = cache
+            .load("hicolor:24@1", std::slice::from_ref(&theme_dir))
+            .expect("icons should load");
+        assert_eq!(loaded, icons);
+        assert!(
+            cache
+                .load("hicolor:32@1", std::slice::from_ref(&theme_dir))
+                .expect("other sizes should load")
+                .is_empty()
+        );
+
+        fs::remove_file(&icon_path).expect("icon should be removed");
+        let loaded = cache
+            .load("hicolor:24@1", std::slice::from_ref(&theme_dir))
+            .expect("stale row should still load");
+        assert!(!loaded.contains_key("editor"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
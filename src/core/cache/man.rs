@@ -0,0 +1,201 @@
+use super::tables::MAN_CACHE_TABLE;
+use eyre::Result;
+use redb::{Database, ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// The `whatis` summary of one executable, valid while it keeps this mtime.
+///
+/// Executables without a manual page are stored with `summary: None` so they
+/// are not looked up again, but only while the whatis index keeps the stamp it
+/// had then: pages often arrive later, in another package or the next `mandb`.
+#[derive(Debug, Serialize, Deserialize)]
+struct ManEntry {
+    mtime: SystemTime,
+    index_stamp: Option<SystemTime>,
+    summary: Option<String>,
+}
+
+pub struct ManCache {
+    db: Arc<Database>,
+}
+
+impl ManCache {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        let write_txn = db.begin_write()?;
+        {
+            let _ = write_txn.open_table(MAN_CACHE_TABLE)?;
+        }
+        write_txn.commit()?;
+
+        Ok(Self { db })
+    }
+
+    /// Cached summaries for the executables in `files` whose mtime still matches.
+    ///
+    /// Executables missing from the result have never been looked up, changed
+    /// since, or had no page when the whatis index was at another `index_stamp`;
+    /// a `None` value means they have no manual page.
+    pub fn get_many(
+        &self,
+        files: &[(PathBuf, SystemTime)],
+        index_stamp: Option<SystemTime>,
+    ) -> Result<HashMap<PathBuf, Option<String>>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(MAN_CACHE_TABLE)?;
+
+        let mut found = HashMap::new();
+        for (path, mtime) in files {
+            if let Some(data) = table.get(cache_key(path).as_str())?
+                && let Ok(entry) = postcard::from_bytes::<ManEntry>(data.value())
+                && entry.mtime == *mtime
+                && (entry.summary.is_some() || entry.index_stamp == index_stamp)
+            {
+                found.insert(path.clone(), entry.summary);
+            }
+        }
+        Ok(found)
+    }
+
+    pub fn store_many(
+        &self,
+        entries: Vec<(PathBuf, SystemTime, Option<String>)>,
+        index_stamp: Option<SystemTime>,
+    ) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(MAN_CACHE_TABLE)?;
+            for (path, mtime, summary) in entries {
+                let data = postcard::to_allocvec(&ManEntry {
+                    mtime,
+                    index_stamp,
+                    summary,
+                })?;
+                table.insert(cache_key(&path).as_str(), data.as_slice())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(MAN_CACHE_TABLE)?;
+            let keys: Vec<String> = table
+                .iter()?
+                .map(|result| result.map(|(key, _)| key.value().to_string()))
+                .collect::<std::result::Result<_, _>>()?;
+            for key in keys {
+                table.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
+
+fn cache_key(path: &Path) -> String {
+    crate::core::path_key::encode(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ManCache;
+    use crate::testing::temp_dir;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn summaries_are_dropped_when_the_executable_changes() {
+        let dir = temp_dir("man-cache-mtime");
+        let db = Arc::new(
+            redb::Database::create(dir.join("cache.redb")).expect("database should be created"),
+        );
+        let cache = ManCache::new(db).expect("man cache should initialize");
+        let ls = PathBuf::from("/usr/bin/ls");
+        let tool = PathBuf::from("/usr/bin/tool");
+        let mtime = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let stamp = Some(mtime);
+
+        cache
+            .store_many(
+                vec![
+                    (
+                        ls.clone(),
+                        mtime,
+                        Some("list directory contents".to_string()),
+                    ),
+                    (tool.clone(), mtime, None),
+                ],
+                stamp,
+            )
+            .expect("summaries should be stored");
+
+        let found = cache
+            .get_many(&[(ls.clone(), mtime), (tool.clone(), mtime)], stamp)
+            .expect("summaries should load");
+        assert_eq!(
+            found.get(&ls),
+            Some(&Some("list directory contents".to_string()))
+        );
+        assert_eq!(found.get(&tool), Some(&None));
+
+        let newer = mtime + Duration::from_secs(1);
+        let found = cache
+            .get_many(&[(ls, newer)], stamp)
+            .expect("summaries should load");
+        assert!(found.is_empty());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn missing_pages_are_looked_up_again_after_the_index_changes() {
+        let dir = temp_dir("man-cache-index");
+        let db = Arc::new(
+            redb::Database::create(dir.join("cache.redb")).expect("database should be created"),
+        );
+        let cache = ManCache::new(db).expect("man cache should initialize");
+        let ls = PathBuf::from("/usr/bin/ls");
+        let tool = PathBuf::from("/usr/bin/tool");
+        let mtime = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        // No index yet, as on a fresh install before `mandb` first runs.
+        cache
+            .store_many(
+                vec![
+                    (
+                        ls.clone(),
+                        mtime,
+                        Some("list directory contents".to_string()),
+                    ),
+                    (tool.clone(), mtime, None),
+                ],
+                None,
+            )
+            .expect("summaries should be stored");
+        let files = [(ls.clone(), mtime), (tool.clone(), mtime)];
+        assert_eq!(
+            cache
+                .get_many(&files, None)
+                .expect("summaries should load")
+                .len(),
+            2
+        );
+
+        let rebuilt = Some(mtime + Duration::from_secs(60));
+        let found = cache
+            .get_many(&files, rebuilt)
+            .expect("summaries should load");
+        assert!(found.contains_key(&ls));
+        assert!(!found.contains_key(&tool));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -1,13 +1,21 @@
 mod desktop;
+mod exec;
 mod history;
+mod icon;
+mod man;
 mod tables;
 
 #[allow(unused_imports)]
 pub use desktop::DesktopCache;
+pub use exec::ExecCache;
 #[allow(unused_imports)]
 pub use history::HistoryCache;
+pub use icon::IconCache;
+pub use man::ManCache;
 #[allow(unused_imports)]
 pub use tables::{
-    DESKTOP_CACHE_TABLE, FILE_LIST_TABLE, FRECENCY_TABLE, HIDDEN_ENTRIES_TABLE,
-    HIDDEN_ENTRY_META_TABLE, HISTORY_TABLE, NAME_INDEX_TABLE, PINNED_TABLE,
+    DESKTOP_CACHE_META_TABLE, DESKTOP_CACHE_TABLE, EXEC_CACHE_TABLE, FILE_LIST_TABLE,
+    FRECENCY_TABLE, HIDDEN_ENTRIES_TABLE, HIDDEN_ENTRY_META_TABLE, HISTORY_TABLE, ICON_CACHE_TABLE,
+    LAUNCH_HOURS_TABLE, NAME_INDEX_TABLE, PINNED_TABLE, QUERY_SELECTIONS_TABLE,
+    UNRESOLVED_NAMES_TABLE,
 };
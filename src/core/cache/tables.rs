@@ -2,10 +2,21 @@ use redb::TableDefinition;
 
 pub const DESKTOP_CACHE_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("desktop_cache");
 pub const NAME_INDEX_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("app_name_index");
+pub const ICON_CACHE_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("icon_cache");
+pub const EXEC_CACHE_TABLE: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("exec_availability");
+pub const MAN_CACHE_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("man_summaries");
+pub const DESKTOP_CACHE_META_TABLE: TableDefinition<&str, u64> =
+    TableDefinition::new("desktop_cache_meta");
 pub const FILE_LIST_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("file_list_cache");
 pub const HISTORY_TABLE: TableDefinition<&str, u64> = TableDefinition::new("history");
 pub const PINNED_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("pinned_apps");
 pub const FRECENCY_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("frecency");
+pub const LAUNCH_HOURS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("launch_hours");
+pub const UNRESOLVED_NAMES_TABLE: TableDefinition<&str, u64> =
+    TableDefinition::new("unresolved_history_names");
+pub const QUERY_SELECTIONS_TABLE: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("query_selections");
 pub const HIDDEN_ENTRIES_TABLE: TableDefinition<u64, &[u8]> =
     TableDefinition::new("hidden_entries");
 pub const HIDDEN_ENTRY_META_TABLE: TableDefinition<&str, u64> =
@@ -20,9 +20,10 @@ pub fn open_history_db() -> Result<(std::sync::Arc<redb::Database>, PathBuf)> {
 }
 
 /// load pinned apps from database
-/// returns a set of app names that are pinned
+/// returns the store keys (see `App::store_key`) of pinned apps
 const PINNED_APPS_KEY: &str = "pinned_apps";
 const PINNED_TIMESTAMPS_KEY: &str = "pin_timestamps";
+const PINNED_ORDER_KEY: &str = "pin_order";
 
 fn now_unix_seconds() -> u64 {
     SystemTime::now()
@@ -35,18 +36,21 @@ fn save_pinned_state(
     db: &std::sync::Arc<redb::Database>,
     pinned: &HashSet<String>,
     pin_timestamps: &HashMap<String, u64>,
+    pin_order: &[String],
 ) -> Result<()> {
     let mut apps: Vec<String> = pinned.iter().cloned().collect();
     apps.sort_unstable_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()).then(a.cmp(b)));
 
     let apps_data = postcard::to_allocvec(&apps)?;
     let timestamps_data = postcard::to_allocvec(pin_timestamps)?;
+    let order_data = postcard::to_allocvec(pin_order)?;
 
     let write_txn = db.begin_write()?;
     {
         let mut table = write_txn.open_table(crate::core::cache::PINNED_TABLE)?;
         table.insert(PINNED_APPS_KEY, apps_data.as_slice())?;
         table.insert(PINNED_TIMESTAMPS_KEY, timestamps_data.as_slice())?;
+        table.insert(PINNED_ORDER_KEY, order_data.as_slice())?;
     }
     write_txn.commit()?;
     Ok(())
@@ -127,7 +131,7 @@ pub fn load_pinned_apps(db: &std::sync::Arc<redb::Database>) -> HashSet<String>
 }
 
 /// load pin timestamps from database
-/// returns app_name -> first pinned unix timestamp
+/// returns store key -> first pinned unix timestamp
 pub fn load_pin_timestamps(db: &std::sync::Arc<redb::Database>) -> HashMap<String, u64> {
     let (pinned, pinned_loaded_ok) = load_pinned_apps_internal(db);
     let mut pin_timestamps = HashMap::new();
@@ -208,30 +212,139 @@ pub fn load_pin_timestamps(db: &std::sync::Arc<redb::Database>) -> HashMap<Strin
         }
     }
 
-    if changed && let Err(e) = save_pinned_state(db, &pinned, &pin_timestamps) {
+    // The stored manual order is written back untouched, so skip the write if
+    // it could not be read.
+    let (pin_order, order_loaded_ok) = load_pin_order_internal(db);
+    if changed
+        && order_loaded_ok
+        && let Err(e) = save_pinned_state(db, &pinned, &pin_timestamps, &pin_order)
+    {
         eprintln!("Warning: Failed to persist pin timestamps: {}", e);
     }
 
     pin_timestamps
 }
 
-/// toggle pin status for an app
-pub fn toggle_pin(db: &std::sync::Arc<redb::Database>, app_name: &str) -> Result<bool> {
+fn load_pin_order_internal(db: &std::sync::Arc<redb::Database>) -> (Vec<String>, bool) {
+    let mut pin_order = Vec::new();
+    let mut loaded_ok = true;
+
+    match db.begin_read() {
+        Ok(read_txn) => match read_txn.open_table(crate::core::cache::PINNED_TABLE) {
+            Ok(table) => match table.get(PINNED_ORDER_KEY) {
+                Ok(Some(data)) => match postcard::from_bytes::<Vec<String>>(data.value()) {
+                    Ok(order) => pin_order = order,
+                    Err(e) => {
+                        eprintln!("Warning: Failed to deserialize pin order: {}", e);
+                        loaded_ok = false;
+                    }
+                },
+                Ok(None) => {} // No manual order yet
+                Err(e) => {
+                    eprintln!("Warning: Failed to read pin order: {}", e);
+                    loaded_ok = false;
+                }
+            },
+            Err(open_err) => {
+                if !ensure_pinned_table(db, &open_err) {
+                    loaded_ok = false;
+                }
+            }
+        },
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to begin read transaction for pin order: {}",
+                e
+            );
+            loaded_ok = false;
+        }
+    }
+
+    (pin_order, loaded_ok)
+}
+
+/// Drops unpinned keys from `pin_order` and appends pinned apps it is missing,
+/// oldest pin first, so the order always lists exactly the pinned apps.
+fn reconcile_pin_order(
+    mut pin_order: Vec<String>,
+    pinned: &HashSet<String>,
+    pin_timestamps: &HashMap<String, u64>,
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    pin_order.retain(|key| pinned.contains(key) && seen.insert(key.clone()));
+
+    let mut missing: Vec<&String> = pinned.iter().filter(|key| !seen.contains(*key)).collect();
+    missing.sort_unstable_by(|a, b| {
+        pin_timestamps
+            .get(*a)
+            .cmp(&pin_timestamps.get(*b))
+            .then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+            .then(a.cmp(b))
+    });
+    pin_order.extend(missing.into_iter().cloned());
+    pin_order
+}
+
+/// the manual pinned order, reconciled with the pinned set and persisted when
+/// reconciling changed it
+fn load_pin_order_list(db: &std::sync::Arc<redb::Database>) -> Vec<String> {
+    let (pinned, pinned_loaded_ok) = load_pinned_apps_internal(db);
+    let pin_timestamps = load_pin_timestamps(db);
+    let (stored, order_loaded_ok) = load_pin_order_internal(db);
+
+    let pin_order = reconcile_pin_order(stored.clone(), &pinned, &pin_timestamps);
+    if pin_order != stored
+        && pinned_loaded_ok
+        && order_loaded_ok
+        && let Err(e) = save_pinned_state(db, &pinned, &pin_timestamps, &pin_order)
+    {
+        eprintln!("Warning: Failed to persist pin order: {}", e);
+    }
+    pin_order
+}
+
+/// load the manual pinned order from database
+/// returns store key -> position, first pinned app at 0
+pub fn load_pin_order(db: &std::sync::Arc<redb::Database>) -> HashMap<String, u64> {
+    load_pin_order_list(db).into_iter().zip(0..).collect()
+}
+
+/// toggle pin status for the app stored under `key`
+///
+/// newly pinned apps go to the end of the manual order
+pub fn toggle_pin(db: &std::sync::Arc<redb::Database>, key: &str) -> Result<bool> {
     let mut pinned = load_pinned_apps(db);
     let mut pin_timestamps = load_pin_timestamps(db);
-    let is_pinned = if pinned.contains(app_name) {
-        pinned.remove(app_name);
-        pin_timestamps.remove(app_name);
+    let mut pin_order = load_pin_order_list(db);
+    let is_pinned = if pinned.contains(key) {
+        pinned.remove(key);
+        pin_timestamps.remove(key);
+        pin_order.retain(|pinned_key| pinned_key != key);
         false
     } else {
-        pinned.insert(app_name.to_string());
-        pin_timestamps.insert(app_name.to_string(), now_unix_seconds());
+        pinned.insert(key.to_string());
+        pin_timestamps.insert(key.to_string(), now_unix_seconds());
+        pin_order.push(key.to_string());
         true
     };
-    save_pinned_state(db, &pinned, &pin_timestamps)?;
+    save_pinned_state(db, &pinned, &pin_timestamps, &pin_order)?;
     Ok(is_pinned)
 }
 
+/// swap two pinned apps in the manual order
+/// does nothing unless both keys are pinned
+pub fn swap_pins(db: &std::sync::Arc<redb::Database>, a: &str, b: &str) -> Result<()> {
+    let pinned = load_pinned_apps(db);
+    let pin_timestamps = load_pin_timestamps(db);
+    let mut pin_order = load_pin_order_list(db);
+    let position = |key: &str| pin_order.iter().position(|pinned_key| pinned_key == key);
+    if let (Some(a_index), Some(b_index)) = (position(a), position(b)) {
+        pin_order.swap(a_index, b_index);
+        save_pinned_state(db, &pinned, &pin_timestamps, &pin_order)?;
+    }
+    Ok(())
+}
+
 // =============================================================================
 // FRECENCY STORAGE
 // =============================================================================
@@ -239,8 +352,11 @@ pub fn toggle_pin(db: &std::sync::Arc<redb::Database>, app_name: &str) -> Result
 use crate::core::ranking::FrecencyEntry;
 
 /// Load frecency data from database
+///
+/// Rows written before entries kept decay state are rewritten in the current layout.
 pub fn load_frecency(db: &std::sync::Arc<redb::Database>) -> HashMap<String, FrecencyEntry> {
     let mut frecency = HashMap::new();
+    let mut legacy = HashMap::new();
 
     match db.begin_read() {
         Ok(read_txn) => {
@@ -248,7 +364,10 @@ pub fn load_frecency(db: &std::sync::Arc<redb::Database>) -> HashMap<String, Fre
                 // Iterate over all entries
                 if let Ok(iter) = table.iter() {
                     for (key, value) in iter.flatten() {
-                        if let Ok(entry) = postcard::from_bytes::<FrecencyEntry>(value.value()) {
+                        if let Some((entry, was_legacy)) = FrecencyEntry::decode(value.value()) {
+                            if was_legacy {
+                                legacy.insert(key.value().to_string(), entry.clone());
+                            }
                             frecency.insert(key.value().to_string(), entry);
                         }
                     }
@@ -261,6 +380,12 @@ pub fn load_frecency(db: &std::sync::Arc<redb::Database>) -> HashMap<String, Fre
         ),
     }
 
+    if !legacy.is_empty()
+        && let Err(e) = save_frecency(db, &legacy)
+    {
+        eprintln!("Warning: Failed to migrate frecency data: {}", e);
+    }
+
     frecency
 }
 
@@ -284,14 +409,20 @@ pub fn save_frecency(
     Ok(())
 }
 
-/// Record an app access (updates frecency)
-pub fn record_access(db: &std::sync::Arc<redb::Database>, app_name: &str) -> Result<()> {
+/// Record an access of the app stored under `key` (updates frecency)
+///
+/// `half_life_secs` decays the entry's earlier accesses for the exponential model.
+pub fn record_access(
+    db: &std::sync::Arc<redb::Database>,
+    key: &str,
+    half_life_secs: u64,
+) -> Result<()> {
     let mut frecency = load_frecency(db);
 
     // Update or create entry
     frecency
-        .entry(app_name.to_string())
-        .and_modify(|e| e.access())
+        .entry(key.to_string())
+        .and_modify(|e| e.access(half_life_secs))
         .or_default();
 
     // Age entries if total exceeds max (10000 by default, like zoxide)
@@ -303,9 +434,490 @@ pub fn record_access(db: &std::sync::Arc<redb::Database>, app_name: &str) -> Res
 
 /// Get frecency score for an app
 #[allow(dead_code)]
-pub fn get_frecency_score(db: &std::sync::Arc<redb::Database>, app_name: &str) -> f64 {
+pub fn get_frecency_score(db: &std::sync::Arc<redb::Database>, key: &str) -> f64 {
     load_frecency(db)
-        .get(app_name)
+        .get(key)
         .map(|e| e.frecency())
         .unwrap_or(0.0)
 }
+
+// =============================================================================
+// LAUNCH HOURS
+// =============================================================================
+
+use crate::core::ranking::LaunchHours;
+
+/// Load launch counts by hour of the week, keyed by app store key
+pub fn load_launch_hours(db: &std::sync::Arc<redb::Database>) -> HashMap<String, LaunchHours> {
+    let mut launch_hours = HashMap::new();
+
+    match db.begin_read() {
+        Ok(read_txn) => {
+            if let Ok(table) = read_txn.open_table(crate::core::cache::LAUNCH_HOURS_TABLE)
+                && let Ok(iter) = table.iter()
+            {
+                for (key, value) in iter.flatten() {
+                    if let Ok(hours) = postcard::from_bytes::<LaunchHours>(value.value()) {
+                        launch_hours.insert(key.value().to_string(), hours);
+                    }
+                }
+            }
+        }
+        Err(e) => eprintln!(
+            "Warning: Failed to begin read transaction for launch hours: {}",
+            e
+        ),
+    }
+
+    launch_hours
+}
+
+/// Count a launch of `key` in the hour of the week `unix_secs` falls in
+///
+/// Call once per launch; opening a directory or re-recording an access does not count.
+pub fn record_launch_hour(
+    db: &std::sync::Arc<redb::Database>,
+    key: &str,
+    unix_secs: u64,
+) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(crate::core::cache::LAUNCH_HOURS_TABLE)?;
+        let mut hours = table
+            .get(key)?
+            .and_then(|value| postcard::from_bytes::<LaunchHours>(value.value()).ok())
+            .unwrap_or_default();
+        hours.record(unix_secs);
+        table.insert(key, postcard::to_allocvec(&hours)?.as_slice())?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+// =============================================================================
+// QUERY SELECTIONS
+// =============================================================================
+
+use crate::core::ranking::QuerySelections;
+
+/// Load the entries launched for earlier queries
+pub fn load_query_selections(db: &std::sync::Arc<redb::Database>) -> QuerySelections {
+    let mut selections = QuerySelections::default();
+
+    match db.begin_read() {
+        Ok(read_txn) => {
+            if let Ok(table) = read_txn.open_table(crate::core::cache::QUERY_SELECTIONS_TABLE)
+                && let Ok(iter) = table.iter()
+            {
+                for (query, value) in iter.flatten() {
+                    if let Ok(picks) = postcard::from_bytes::<HashMap<String, u32>>(value.value()) {
+                        selections.insert(query.value().to_string(), picks);
+                    }
+                }
+            }
+        }
+        Err(e) => eprintln!(
+            "Warning: Failed to begin read transaction for query selections: {}",
+            e
+        ),
+    }
+
+    selections
+}
+
+/// Remember that the app stored under `key` was launched after typing `query`
+pub fn record_selection(db: &std::sync::Arc<redb::Database>, query: &str, key: &str) -> Result<()> {
+    let Some(query) = crate::core::ranking::normalize_query(query) else {
+        return Ok(());
+    };
+
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(crate::core::cache::QUERY_SELECTIONS_TABLE)?;
+        let mut picks = table
+            .get(query.as_str())?
+            .and_then(|value| postcard::from_bytes::<HashMap<String, u32>>(value.value()).ok())
+            .unwrap_or_default();
+        crate::core::ranking::record_pick(&mut picks, key);
+        table.insert(query.as_str(), postcard::to_allocvec(&picks)?.as_slice())?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+// =============================================================================
+// NAME KEY MIGRATION
+// =============================================================================
+
+use crate::core::hidden_entries::EntryKey;
+use crate::desktop::App;
+
+/// Moves history, frecency, and pin rows still keyed by app name onto the
+/// store keys of `apps`.
+///
+/// Older versions keyed these rows by visible name. Each name goes to the entry
+/// that wins a duplicate check: the one from the most preferred application
+/// directory, then config entries, then `$PATH` executables. Rows whose name
+/// matches no entry are kept, so they still move once that entry is discovered
+/// again. When rows move, the history and pin flags of `apps` are refreshed.
+pub fn migrate_name_keys(
+    db: &std::sync::Arc<redb::Database>,
+    apps: &mut [App],
+    application_dirs: &[PathBuf],
+) -> Result<()> {
+    let pinned = load_pinned_apps(db);
+    let pin_timestamps = load_pin_timestamps(db);
+    let history = crate::core::cache::HistoryCache::load(db)?.history;
+    let frecency = load_frecency(db);
+
+    let is_legacy = |key: &String| EntryKey::parse(key).is_none();
+    if !(history.keys().any(is_legacy)
+        || frecency.keys().any(is_legacy)
+        || pinned.iter().any(is_legacy))
+    {
+        return Ok(());
+    }
+
+    let keys_by_name = preferred_keys_by_name(apps, application_dirs);
+    let target = |name: &String| -> Option<&String> {
+        is_legacy(name).then(|| keys_by_name.get(name)).flatten()
+    };
+
+    let mut moved = false;
+    let write_txn = db.begin_write()?;
+    {
+        let mut unresolved = write_txn.open_table(crate::core::cache::UNRESOLVED_NAMES_TABLE)?;
+        let mut table = write_txn.open_table(crate::core::cache::HISTORY_TABLE)?;
+        for (name, count) in &history {
+            let Some(key) = target(name) else {
+                continue;
+            };
+            let existing = table.get(key.as_str())?.map(|value| value.value());
+            table.insert(key.as_str(), existing.unwrap_or(0).saturating_add(*count))?;
+            table.remove(name.as_str())?;
+            unresolved.remove(name.as_str())?;
+            moved = true;
+        }
+
+        let mut table = write_txn.open_table(crate::core::cache::FRECENCY_TABLE)?;
+        for (name, entry) in &frecency {
+            let Some(key) = target(name) else {
+                continue;
+            };
+            let merged = match frecency.get(key) {
+                Some(existing) => FrecencyEntry {
+                    score: existing.score.saturating_add(entry.score),
+                    last_access: existing.last_access.max(entry.last_access),
+                    decayed: existing.decayed + entry.decayed,
+                },
+                None => entry.clone(),
+            };
+            table.insert(key.as_str(), postcard::to_allocvec(&merged)?.as_slice())?;
+            table.remove(name.as_str())?;
+            moved = true;
+        }
+    }
+    write_txn.commit()?;
+
+    let mut migrated_pins = HashSet::new();
+    let mut migrated_timestamps = HashMap::new();
+    for name in &pinned {
+        let key = target(name).unwrap_or(name);
+        moved |= key != name;
+        migrated_pins.insert(key.clone());
+        if let Some(pinned_at) = pin_timestamps.get(name) {
+            migrated_timestamps
+                .entry(key.clone())
+                .and_modify(|earliest: &mut u64| *earliest = (*earliest).min(*pinned_at))
+                .or_insert(*pinned_at);
+        }
+    }
+    if migrated_pins != pinned {
+        let mut seen = HashSet::new();
+        let migrated_order: Vec<String> = load_pin_order_internal(db)
+            .0
+            .iter()
+            .map(|name| target(name).unwrap_or(name).clone())
+            .filter(|key| seen.insert(key.clone()))
+            .collect();
+        save_pinned_state(db, &migrated_pins, &migrated_timestamps, &migrated_order)?;
+    }
+
+    if moved {
+        let history_cache = crate::core::cache::HistoryCache::load(db)?;
+        for app in apps.iter_mut() {
+            let key = app.store_key();
+            app.history = history_cache.get_history(&key);
+            app.pinned = history_cache.is_pinned(&key);
+        }
+    }
+    Ok(())
+}
+
+/// Names of history rows still keyed by name that a direct launch could not
+/// find an entry for.
+pub fn load_unresolved_names(db: &std::sync::Arc<redb::Database>) -> HashSet<String> {
+    let mut names = HashSet::new();
+
+    match db.begin_read() {
+        Ok(read_txn) => {
+            if let Ok(table) = read_txn.open_table(crate::core::cache::UNRESOLVED_NAMES_TABLE)
+                && let Ok(iter) = table.iter()
+            {
+                names.extend(iter.flatten().map(|(name, _)| name.value().to_string()));
+            }
+        }
+        Err(e) => eprintln!(
+            "Warning: Failed to begin read transaction for unresolved names: {}",
+            e
+        ),
+    }
+
+    names
+}
+
+/// Remember that no entry is named `name`, so direct launches stop scanning
+/// application directories for it. [`migrate_name_keys`] forgets the name
+/// once its rows move to an entry.
+pub fn mark_unresolved_name(db: &std::sync::Arc<redb::Database>, name: &str) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(crate::core::cache::UNRESOLVED_NAMES_TABLE)?;
+        table.insert(name, now_unix_seconds())?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// Each entry name mapped to the store key of the entry that should inherit
+/// rows kept under that name.
+fn preferred_keys_by_name(apps: &[App], application_dirs: &[PathBuf]) -> HashMap<String, String> {
+    let mut by_priority: Vec<&App> = apps
+        .iter()
+        .filter(|app| app.entry_key().is_some())
+        .collect();
+    by_priority
+        .sort_by_cached_key(|app| crate::core::hidden_entries::priority_key(app, application_dirs));
+
+    let mut keys = HashMap::new();
+    for app in by_priority {
+        if !keys.contains_key(&app.name) {
+            keys.insert(app.name.clone(), app.store_key());
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        load_frecency, load_launch_hours, load_pin_order, load_pin_timestamps, load_pinned_apps,
+        load_query_selections, migrate_name_keys, record_access, record_launch_hour,
+        record_selection, swap_pins, toggle_pin,
+    };
+    use crate::core::cache::HistoryCache;
+    use crate::core::ranking::FrecencyEntry;
+    use crate::desktop::App;
+    use crate::testing::temp_dir;
+    use redb::ReadableDatabase;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    fn desktop_app(name: &str, path: &str, desktop_id: &str) -> App {
+        let mut app = App::parse(
+            format!("[Desktop Entry]\nType=Application\nName={name}\nExec=true"),
+            false,
+        )
+        .expect("test desktop entry should parse");
+        app.desktop_id = Some(desktop_id.to_string());
+        app.set_source_path(Path::new(path));
+        app
+    }
+
+    #[test]
+    fn name_keyed_rows_move_to_the_preferred_entry() {
+        let dir = temp_dir("database-migrate");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+        let write_txn = db.begin_write().expect("write should begin");
+        {
+            let mut history = write_txn
+                .open_table(crate::core::cache::HISTORY_TABLE)
+                .expect("history table should open");
+            history.insert("Editor", 4).expect("history should insert");
+            history.insert("Gone", 2).expect("history should insert");
+            let mut frecency = write_txn
+                .open_table(crate::core::cache::FRECENCY_TABLE)
+                .expect("frecency table should open");
+            let entry = FrecencyEntry {
+                score: 4,
+                last_access: 1_000,
+                decayed: 4.0,
+            };
+            let data = postcard::to_allocvec(&entry).expect("entry should encode");
+            frecency
+                .insert("Editor", data.as_slice())
+                .expect("frecency should insert");
+        }
+        write_txn.commit().expect("write should commit");
+        super::save_pinned_state(
+            &db,
+            &["Editor".to_string()].into(),
+            &HashMap::from([("Editor".to_string(), 50)]),
+            &["Editor".to_string()],
+        )
+        .expect("pins should save");
+
+        let dirs = [
+            PathBuf::from("/home/user/.local/share/applications"),
+            PathBuf::from("/usr/share/applications"),
+        ];
+        let mut apps = vec![
+            desktop_app(
+                "Editor",
+                "/usr/share/applications/editor.desktop",
+                "editor.desktop",
+            ),
+            desktop_app(
+                "Editor",
+                "/home/user/.local/share/applications/editor.desktop",
+                "editor.desktop",
+            ),
+        ];
+        migrate_name_keys(&db, &mut apps, &dirs).expect("migration should succeed");
+
+        let user_key = apps[1].store_key();
+        let history = HistoryCache::load(&db)
+            .expect("history should load")
+            .history;
+        assert_eq!(history.get(&user_key), Some(&4));
+        assert_eq!(history.get("Gone"), Some(&2));
+        assert!(!history.contains_key("Editor"));
+        assert_eq!(
+            load_frecency(&db).get(&user_key).map(|entry| entry.score),
+            Some(4)
+        );
+        assert!(load_pinned_apps(&db).contains(&user_key));
+        assert_eq!(load_pin_timestamps(&db).get(&user_key), Some(&50));
+        assert_eq!(load_pin_order(&db), HashMap::from([(user_key.clone(), 0)]));
+        assert!(apps[1].pinned && apps[1].history == 4);
+        assert!(!apps[0].pinned && apps[0].history == 0);
+
+        drop(db);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn manual_pin_order_appends_new_pins_and_swaps_in_place() {
+        let dir = temp_dir("database-pin-order");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+        for key in ["a", "b", "c"] {
+            assert!(toggle_pin(&db, key).expect("pin should toggle"));
+        }
+        swap_pins(&db, "a", "c").expect("pins should swap");
+        assert!(!toggle_pin(&db, "b").expect("pin should toggle"));
+        assert!(toggle_pin(&db, "d").expect("pin should toggle"));
+
+        let order = load_pin_order(&db);
+        let mut keys: Vec<&String> = order.keys().collect();
+        keys.sort_by_key(|key| order[*key]);
+        assert_eq!(keys, ["c", "a", "d"]);
+
+        drop(db);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn legacy_frecency_rows_are_rewritten_with_decay_state() {
+        #[derive(serde::Serialize)]
+        struct Legacy {
+            score: u64,
+            last_access: u64,
+        }
+
+        let dir = temp_dir("database-frecency-layout");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+        let write_txn = db.begin_write().expect("write should begin");
+        {
+            let mut frecency = write_txn
+                .open_table(crate::core::cache::FRECENCY_TABLE)
+                .expect("frecency table should open");
+            let data = postcard::to_allocvec(&Legacy {
+                score: 3,
+                last_access: 2_000,
+            })
+            .expect("legacy row should encode");
+            frecency
+                .insert("v1:config:Editor", data.as_slice())
+                .expect("frecency should insert");
+        }
+        write_txn.commit().expect("write should commit");
+
+        let loaded = load_frecency(&db);
+        assert_eq!(loaded["v1:config:Editor"].decayed, 3.0);
+
+        let read_txn = db.begin_read().expect("read should begin");
+        let table = read_txn
+            .open_table(crate::core::cache::FRECENCY_TABLE)
+            .expect("frecency table should open");
+        let stored = table
+            .get("v1:config:Editor")
+            .expect("row should read")
+            .expect("row should exist");
+        let (entry, was_legacy) = FrecencyEntry::decode(stored.value()).expect("row should decode");
+        assert!(!was_legacy);
+        assert_eq!((entry.score, entry.last_access), (3, 2_000));
+
+        drop(table);
+        drop(read_txn);
+        drop(db);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn recorded_launches_count_towards_their_hour() {
+        let dir = temp_dir("database-launch-hours");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+        let now = crate::core::ranking::current_unix_seconds();
+
+        record_access(&db, "v1:config:Mail", 3600).expect("access should record");
+        assert!(load_launch_hours(&db).is_empty());
+        record_launch_hour(&db, "v1:config:Mail", now).expect("launch should record");
+        record_launch_hour(&db, "v1:config:Mail", now).expect("launch should record");
+
+        let launch_hours = load_launch_hours(&db);
+        assert_eq!(launch_hours.len(), 1);
+        assert!(launch_hours["v1:config:Mail"].share_at(now) >= 0.5);
+
+        drop(db);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn selections_are_stored_under_the_normalized_query() {
+        let dir = temp_dir("database-query-selections");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+
+        record_selection(&db, " TEL ", "v1:config:Telegram").expect("selection should record");
+        record_selection(&db, "tel", "v1:config:Telegram").expect("selection should record");
+        record_selection(&db, "  ", "v1:config:Terminal").expect("empty query should be skipped");
+
+        let shares = load_query_selections(&db).shares_for("te");
+        assert_eq!(shares.len(), 1);
+        assert!((shares["v1:config:Telegram"] - 2.0 / 3.0).abs() < 1e-9);
+
+        drop(db);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -84,6 +84,17 @@ pub fn log_startup_info(
         let _ = writeln!(file, "  Prefix depth: {}", cli.prefix_depth);
         let _ = writeln!(file, "  Match mode: {:?}", cli.match_mode);
         let _ = writeln!(file, "  Ranking mode: {}", cli.ranking_mode.as_str());
+        let _ = writeln!(
+            file,
+            "  Frecency model: {} (half-life {}h)",
+            cli.frecency_model.as_str(),
+            cli.frecency_half_life_secs / 3600
+        );
+        let _ = writeln!(
+            file,
+            "  Query learning weight: {} tiers",
+            cli.query_learning_weight
+        );
         let _ = writeln!(file, "  Pinned order: {}", cli.pinned_order_mode.as_str());
         let _ = writeln!(file, "  Filter desktop: {}", cli.filter_desktop);
         let _ = writeln!(file, "  Filter actions: {}", cli.filter_actions);
@@ -187,6 +198,9 @@ pub fn log_search_snapshot(query: &str, matches: &[App], prefix_depth: usize, fi
             if let Some(ref b) = app.breakdown {
                 let _ = writeln!(file, "       ├── Tier: {}", b.tier);
                 let _ = writeln!(file, "       ├── Bucket Score: {}", b.bucket_score);
+                if !b.matched_terms.is_empty() {
+                    let _ = writeln!(file, "       ├── Terms: {}", b.matched_terms.join(", "));
+                }
                 let base_score = if b.matcher_score > 0 {
                     b.matcher_score / 100
                 } else {
@@ -205,10 +219,28 @@ pub fn log_search_snapshot(query: &str, matches: &[App], prefix_depth: usize, fi
                     b.raw_frecency_milli as f64 / 1000.0,
                     b.frecency_boost
                 );
+                if b.context_boost != 0 {
+                    let _ = writeln!(
+                        file,
+                        "       ├── Time of week: +{} (usually launched around now)",
+                        b.context_boost
+                    );
+                }
+                if b.learned_boost != 0 {
+                    let _ = writeln!(
+                        file,
+                        "       ├── Learned: +{} (launched for this query before)",
+                        b.learned_boost
+                    );
+                }
                 let _ = writeln!(
                     file,
                     "       └── Final Score: {}",
-                    b.bucket_score + b.matcher_score + b.frecency_boost
+                    b.bucket_score
+                        + b.matcher_score
+                        + b.frecency_boost
+                        + b.context_boost
+                        + b.learned_boost
                 );
             } else {
                 let _ = writeln!(file, "       └── (No breakdown available)");
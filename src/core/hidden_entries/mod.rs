@@ -4,4 +4,4 @@ mod visibility;
 
 pub(crate) use model::{EntryKey, HiddenEntry, HiddenEntryId, NewHiddenEntry};
 pub(crate) use store::HiddenEntryStore;
-pub(crate) use visibility::{HiddenSummary, VisibilityOptions, eligible_apps};
+pub(crate) use visibility::{HiddenSummary, VisibilityOptions, eligible_apps, priority_key};
@@ -19,6 +19,33 @@ impl EntryKey {
         ))
     }
 
+    pub(crate) fn config(name: &str) -> Self {
+        Self(format!("v1:config:{name}"))
+    }
+
+    /// Reads back a key stored with [`EntryKey::as_str`], or `None` for
+    /// anything else, such as a name-keyed row from before entry keys.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        const KINDS: [&str; 3] = ["v1:desktop:", "v1:executable:", "v1:config:"];
+        KINDS
+            .iter()
+            .any(|kind| value.starts_with(kind))
+            .then(|| Self(value.to_string()))
+    }
+
+    /// The entry name of a config entry key.
+    pub(crate) fn config_name(&self) -> Option<&str> {
+        self.0.strip_prefix("v1:config:")
+    }
+
+    /// The desktop ID of a desktop entry key, with `#action` for actions.
+    pub(crate) fn desktop_id(&self) -> Option<&str> {
+        self.0
+            .strip_prefix("v1:desktop:")
+            .and_then(|value| value.split_once(':'))
+            .map(|(_, desktop_id)| desktop_id)
+    }
+
     pub(crate) fn source_path(&self) -> Option<std::path::PathBuf> {
         let encoded_path = self
             .0
@@ -152,6 +179,28 @@ mod tests {
         assert_ne!(first, executable);
     }
 
+    #[test]
+    fn config_entry_keys_have_no_source_path() {
+        let key = EntryKey::config("VPN up");
+
+        assert_eq!(key, EntryKey::config("VPN up"));
+        assert_ne!(key, EntryKey::config("VPN down"));
+        assert_eq!(key.source_path(), None);
+        assert_eq!(key.config_name(), Some("VPN up"));
+    }
+
+    #[test]
+    fn stored_keys_parse_back_and_names_do_not() {
+        let path = Path::new("/usr/share/applications/editor.desktop");
+        let desktop = EntryKey::desktop(path, "editor.desktop#NewWindow");
+
+        assert_eq!(EntryKey::parse(desktop.as_str()), Some(desktop.clone()));
+        assert_eq!(desktop.desktop_id(), Some("editor.desktop#NewWindow"));
+        assert_eq!(desktop.source_path().as_deref(), Some(path));
+        assert_eq!(EntryKey::parse("Firefox"), None);
+        assert_eq!(EntryKey::parse("v1: not a key"), None);
+    }
+
     #[test]
     fn entry_key_recovers_non_utf8_source_path() {
         #[cfg(unix)]
@@ -100,7 +100,9 @@ pub(crate) fn eligible_apps(
     )
 }
 
-fn priority_key(app: &App, application_dirs: &[PathBuf]) -> (usize, String, String) {
+/// Sort key putting `app` in XDG precedence order: entries from earlier
+/// application directories first, entries outside them last.
+pub(crate) fn priority_key(app: &App, application_dirs: &[PathBuf]) -> (usize, String, String) {
     let source_path = app.source_path();
     let root_rank = source_path
         .and_then(|path| {
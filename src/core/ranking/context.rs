@@ -0,0 +1,163 @@
+use std::sync::OnceLock;
+
+/// Number of hour-of-week buckets, Monday 00:00 first.
+pub const HOURS_PER_WEEK: usize = 7 * 24;
+
+/// Total launches kept per entry before every bucket is halved.
+const MAX_LAUNCHES: u32 = 500;
+
+/// Extra weight an app gets when all of its launches fall in the current window.
+const CONTEXT_WEIGHT: f64 = 2.0;
+
+/// Returns the local UTC offset in seconds, or 0 when it cannot be determined.
+///
+/// The offset is read once and cached. Call this before starting other threads:
+/// the `time` crate refuses to read the offset from a multi-threaded process.
+pub fn local_utc_offset_secs() -> i64 {
+    static OFFSET: OnceLock<i64> = OnceLock::new();
+    *OFFSET.get_or_init(|| {
+        time::UtcOffset::current_local_offset()
+            .map(|offset| i64::from(offset.whole_seconds()))
+            .unwrap_or(0)
+    })
+}
+
+/// Returns the hour-of-week bucket of `unix_secs` at `utc_offset_secs`.
+pub fn hour_of_week_at(unix_secs: u64, utc_offset_secs: i64) -> usize {
+    let local_secs = i64::try_from(unix_secs)
+        .unwrap_or(i64::MAX)
+        .saturating_add(utc_offset_secs);
+    let days = local_secs.div_euclid(86400);
+    // 1970-01-01 was a Thursday, three days after a Monday.
+    let weekday = (days + 3).rem_euclid(7) as usize;
+    let hour = (local_secs.rem_euclid(86400) / 3600) as usize;
+    weekday * 24 + hour
+}
+
+/// Returns the hour-of-week bucket of `unix_secs` in local time.
+pub fn hour_of_week(unix_secs: u64) -> usize {
+    hour_of_week_at(unix_secs, local_utc_offset_secs())
+}
+
+/// Launch counts of one entry by local hour of the week.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LaunchHours {
+    counts: Vec<u32>,
+}
+
+impl Default for LaunchHours {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; HOURS_PER_WEEK],
+        }
+    }
+}
+
+impl LaunchHours {
+    /// Records a launch in the bucket of `unix_secs`.
+    pub fn record(&mut self, unix_secs: u64) {
+        self.record_hour(hour_of_week(unix_secs));
+    }
+
+    /// Records a launch in hour-of-week bucket `hour`.
+    pub fn record_hour(&mut self, hour: usize) {
+        // Rows from a future layout with a different bucket count start over.
+        if self.counts.len() != HOURS_PER_WEEK {
+            *self = Self::default();
+        }
+        self.counts[hour % HOURS_PER_WEEK] += 1;
+        if self.total() > MAX_LAUNCHES {
+            self.counts.iter_mut().for_each(|count| *count /= 2);
+        }
+    }
+
+    fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    fn count(&self, hour: isize) -> f64 {
+        let index = hour.rem_euclid(HOURS_PER_WEEK as isize) as usize;
+        self.counts.get(index).copied().unwrap_or(0) as f64
+    }
+
+    /// Share of launches that fall in the window around `unix_secs`.
+    pub fn share_at(&self, unix_secs: u64) -> f64 {
+        self.share_in_hour(hour_of_week(unix_secs))
+    }
+
+    /// Share of launches that fall in the window around bucket `hour`.
+    ///
+    /// The window is the hour itself and half of each neighbouring hour on the
+    /// same weekday, plus a quarter of the same hours on every other day, so a
+    /// daily habit counts even on a weekday it has not been seen on yet.
+    pub fn share_in_hour(&self, hour: usize) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+
+        let window = |hour: isize| {
+            self.count(hour - 1) * 0.5 + self.count(hour) + self.count(hour + 1) * 0.5
+        };
+        let hour = hour as isize;
+        let same_day = window(hour);
+        let other_days: f64 = (1..7).map(|day| window(hour + day * 24)).sum();
+        ((same_day + other_days * 0.25) / f64::from(total)).min(1.0)
+    }
+
+    /// Multiplier applied to an entry's frecency at `unix_secs`.
+    pub fn multiplier_at(&self, unix_secs: u64) -> f64 {
+        1.0 + CONTEXT_WEIGHT * self.share_at(unix_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HOURS_PER_WEEK, LaunchHours, hour_of_week_at};
+
+    const HOUR: u64 = 3600;
+    const DAY: u64 = 86400;
+    // Monday 2024-01-01 00:00 UTC.
+    const MONDAY: u64 = 1_704_067_200;
+
+    #[test]
+    fn hour_of_week_starts_on_monday_in_local_time() {
+        assert_eq!(hour_of_week_at(MONDAY, 0), 0);
+        assert_eq!(hour_of_week_at(MONDAY + 9 * HOUR, 0), 9);
+        assert_eq!(
+            hour_of_week_at(MONDAY + 6 * DAY + 23 * HOUR, 0),
+            HOURS_PER_WEEK - 1
+        );
+        assert_eq!(hour_of_week_at(MONDAY + 7 * DAY, 0), 0);
+        // Sunday 23:00 in UTC is already Monday 01:00 two hours east.
+        assert_eq!(hour_of_week_at(MONDAY - HOUR, 2 * 3600), 1);
+        assert_eq!(hour_of_week_at(MONDAY, -3600), HOURS_PER_WEEK - 1);
+    }
+
+    #[test]
+    fn share_favours_the_hours_an_app_is_used_in() {
+        let mut mail = LaunchHours::default();
+        for day in 0..5 {
+            mail.record_hour(day * 24 + 9);
+        }
+
+        let monday_morning = mail.share_in_hour(9);
+        let saturday_morning = mail.share_in_hour(5 * 24 + 9);
+        let monday_night = mail.share_in_hour(22);
+        assert!(monday_morning > saturday_morning);
+        assert!(saturday_morning > monday_night);
+        assert_eq!(monday_night, 0.0);
+        assert!(mail.share_in_hour(10) > 0.0 && mail.share_in_hour(10) < monday_morning);
+    }
+
+    #[test]
+    fn buckets_are_halved_past_the_launch_cap() {
+        let mut hours = LaunchHours::default();
+        for _ in 0..=500 {
+            hours.record_hour(3);
+        }
+
+        assert_eq!(hours.total(), 250);
+        assert_eq!(hours.share_in_hour(3), 1.0);
+    }
+}
@@ -0,0 +1,221 @@
+use super::fold::char_haystack;
+use nucleo_matcher::pattern::{Atom, CaseMatching, Normalization};
+use nucleo_matcher::{Matcher, Utf32Str};
+
+/// One search term and the text it was parsed from.
+#[derive(Debug, Clone)]
+struct Term {
+    raw: String,
+    atom: Atom,
+}
+
+/// A parsed fzf-style extended query.
+///
+/// Space-separated terms must all match. A term matches fuzzily unless it is
+/// written as `'exact`, `^prefix`, `suffix$` or `^whole$`; a leading `!` negates
+/// it. Terms joined by a standalone `|` are alternatives, only one of which has
+/// to match. `\ ` is a literal space.
+#[derive(Debug, Clone, Default)]
+pub struct ExtendedQuery {
+    groups: Vec<Vec<Term>>,
+}
+
+/// The result of matching an [`ExtendedQuery`] against an entry's fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtendedMatch {
+    /// Sum of the best score of every term group.
+    pub score: u32,
+    /// Each matching term with the index of the field it matched, or `None`
+    /// for negated terms.
+    pub terms: Vec<(String, Option<usize>)>,
+    /// Sorted char positions in the first field matched by positive terms.
+    pub indices: Vec<u32>,
+}
+
+impl ExtendedQuery {
+    /// Parses `query` into term groups.
+    pub fn parse(query: &str) -> Self {
+        let mut groups: Vec<Vec<Term>> = Vec::new();
+        let mut join_next = false;
+        for raw in split_terms(query) {
+            if raw == "|" {
+                join_next = !groups.is_empty();
+                continue;
+            }
+            let term = Term {
+                atom: Atom::parse(&raw, CaseMatching::Ignore, Normalization::Smart),
+                raw,
+            };
+            // A bare `!`, `^` or `'` leaves nothing to match.
+            if term.atom.needle_text().is_empty() {
+                continue;
+            }
+            match groups.last_mut() {
+                Some(group) if join_next => group.push(term),
+                _ => groups.push(vec![term]),
+            }
+            join_next = false;
+        }
+        Self { groups }
+    }
+
+    /// Whether the query has no terms, so every entry matches.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Matches every term group against `fields`.
+    ///
+    /// A positive term matches when any field matches it; a negated term
+    /// matches when no field does. Returns `None` if any group has no matching
+    /// alternative.
+    pub fn match_fields(&self, fields: &[&str], matcher: &mut Matcher) -> Option<ExtendedMatch> {
+        let mut buffers: Vec<Vec<char>> = vec![Vec::new(); fields.len()];
+        let haystacks: Vec<Utf32Str<'_>> = fields
+            .iter()
+            .zip(buffers.iter_mut())
+            .map(|(field, buffer)| char_haystack(field, buffer))
+            .collect();
+
+        let mut result = ExtendedMatch::default();
+        for group in &self.groups {
+            let (score, term, field) = group
+                .iter()
+                .filter_map(|term| {
+                    best_field(&term.atom, &haystacks, matcher)
+                        .map(|(score, field)| (score, term, field))
+                })
+                .max_by_key(|(score, _, _)| *score)?;
+            result.score += u32::from(score);
+            result.terms.push((term.raw.clone(), field));
+            if field.is_some()
+                && let Some(first) = haystacks.first()
+            {
+                let mut indices = Vec::new();
+                if term.atom.indices(*first, matcher, &mut indices).is_some() {
+                    result.indices.extend(indices);
+                }
+            }
+        }
+
+        result.indices.sort_unstable();
+        result.indices.dedup();
+        Some(result)
+    }
+}
+
+/// Best score of `atom` over `haystacks` and the field that produced it.
+///
+/// Negated atoms report no field, since nothing in the entry matched them.
+fn best_field(
+    atom: &Atom,
+    haystacks: &[Utf32Str<'_>],
+    matcher: &mut Matcher,
+) -> Option<(u16, Option<usize>)> {
+    if atom.negative {
+        return haystacks
+            .iter()
+            .all(|haystack| atom.score(*haystack, matcher).is_some())
+            .then_some((0, None));
+    }
+    haystacks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, haystack)| atom.score(*haystack, matcher).map(|score| (score, index)))
+        // Earlier fields win ties, so the name is preferred over metadata.
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(score, index)| (score, Some(index)))
+}
+
+/// Splits on unescaped whitespace, keeping `\ ` inside a term.
+fn split_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut chars = query.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' && chars.peek() == Some(&' ') {
+            // The escape is kept; `Atom::parse` turns it into a space.
+            current.push('\\');
+            current.push(' ');
+            chars.next();
+        } else if ch.is_whitespace() {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(ch);
+        }
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExtendedQuery;
+    use nucleo_matcher::{Config, Matcher};
+
+    fn matches(query: &str, fields: &[&str]) -> bool {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        ExtendedQuery::parse(query)
+            .match_fields(fields, &mut matcher)
+            .is_some()
+    }
+
+    #[test]
+    fn anchors_and_exact_terms_restrict_matches() {
+        assert!(matches("^fire", &["Firefox"]));
+        assert!(!matches("^fox", &["Firefox"]));
+        assert!(matches("fox$", &["Firefox"]));
+        assert!(!matches("fire$", &["Firefox"]));
+        assert!(matches("^firefox$", &["Firefox"]));
+        assert!(!matches("^fire$", &["Firefox"]));
+        assert!(matches("ffx", &["Firefox"]));
+        assert!(!matches("'ffx", &["Firefox"]));
+        assert!(matches("'efo", &["Firefox"]));
+    }
+
+    #[test]
+    fn terms_are_anded_and_pipes_make_alternatives() {
+        assert!(matches("fire fox", &["Firefox"]));
+        assert!(!matches("fire chrome", &["Firefox"]));
+        assert!(matches("^chrome | ^fire", &["Firefox"]));
+        assert!(!matches("^chrome | ^brave", &["Firefox"]));
+        assert!(matches("web ^chrome | ^fire", &["Firefox", "Web Browser"]));
+    }
+
+    #[test]
+    fn negated_terms_exclude_entries_matching_any_field() {
+        assert!(matches("fire !nightly", &["Firefox", "firefox"]));
+        assert!(!matches("fire !nightly", &["Firefox Nightly", "firefox"]));
+        assert!(!matches("fire !^fire", &["Firefox"]));
+        assert!(matches("!nightly", &["Firefox"]));
+    }
+
+    #[test]
+    fn match_reports_terms_fields_and_first_field_positions() {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let result = ExtendedQuery::parse("^fi browser !nightly")
+            .match_fields(&["Firefox", "Web Browser"], &mut matcher)
+            .expect("query should match");
+
+        assert_eq!(
+            result.terms,
+            [
+                ("^fi".to_string(), Some(0)),
+                ("browser".to_string(), Some(1)),
+                ("!nightly".to_string(), None),
+            ]
+        );
+        assert_eq!(result.indices, [0, 1]);
+    }
+
+    #[test]
+    fn escaped_spaces_and_stray_operators_are_handled() {
+        assert!(matches("'web\\ browser", &["Web Browser"]));
+        assert!(!matches("'web\\ browser", &["Web Mail Browser"]));
+        assert!(ExtendedQuery::parse("| ! ^").is_empty());
+    }
+}
@@ -0,0 +1,304 @@
+use nucleo_matcher::Utf32Str;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+/// Built-in Cyrillic and Greek romanization used when transliteration is on.
+///
+/// Only lower-case letters are listed; upper-case input is looked up by its
+/// lower-case form, since matching ignores case anyway.
+const BUILTIN_TRANSLITERATION: &[(char, &str)] = &[
+    // Cyrillic
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('ґ', "g"),
+    ('д', "d"),
+    ('ђ', "dj"),
+    ('е', "e"),
+    ('ё', "e"),
+    ('є', "ye"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('і', "i"),
+    ('ї', "yi"),
+    ('й', "y"),
+    ('ј', "j"),
+    ('к', "k"),
+    ('л', "l"),
+    ('љ', "lj"),
+    ('м', "m"),
+    ('н', "n"),
+    ('њ', "nj"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('ћ', "c"),
+    ('у', "u"),
+    ('ў', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('џ', "dz"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+    // Greek
+    ('α', "a"),
+    ('ά', "a"),
+    ('β', "v"),
+    ('γ', "g"),
+    ('δ', "d"),
+    ('ε', "e"),
+    ('έ', "e"),
+    ('ζ', "z"),
+    ('η', "i"),
+    ('ή', "i"),
+    ('θ', "th"),
+    ('ι', "i"),
+    ('ί', "i"),
+    ('ϊ', "i"),
+    ('ΐ', "i"),
+    ('κ', "k"),
+    ('λ', "l"),
+    ('μ', "m"),
+    ('ν', "n"),
+    ('ξ', "x"),
+    ('ο', "o"),
+    ('ό', "o"),
+    ('π', "p"),
+    ('ρ', "r"),
+    ('σ', "s"),
+    ('ς', "s"),
+    ('τ', "t"),
+    ('υ', "y"),
+    ('ύ', "y"),
+    ('ϋ', "y"),
+    ('ΰ', "y"),
+    ('φ', "f"),
+    ('χ', "ch"),
+    ('ψ', "ps"),
+    ('ω', "o"),
+    ('ώ', "o"),
+];
+
+/// Returns the built-in transliteration table.
+pub fn builtin_transliteration() -> HashMap<char, String> {
+    BUILTIN_TRANSLITERATION
+        .iter()
+        .map(|(from, to)| (*from, (*to).to_string()))
+        .collect()
+}
+
+/// How query and candidate text are folded before they are compared.
+///
+/// Both sides are folded the same way, so with normalization on "cafe" finds
+/// "Café" and "ﬁle" finds "file"; with a transliteration table "moskva" also
+/// finds "Москва".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextFolding {
+    /// Apply NFKD and strip the combining marks it splits off.
+    pub normalize: bool,
+    /// Replacement for each character, applied before normalization.
+    pub transliteration: HashMap<char, String>,
+}
+
+/// Text folded by [`TextFolding::fold_mapped`], with the original position of
+/// every folded character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldedText {
+    /// The folded text.
+    pub text: String,
+    origins: Vec<u32>,
+}
+
+impl TextFolding {
+    /// Whether folding changes anything at all.
+    pub fn is_enabled(&self) -> bool {
+        self.normalize || !self.transliteration.is_empty()
+    }
+
+    /// Folds `text`, borrowing it unchanged when folding is off.
+    pub fn fold<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if !self.is_enabled() || (text.is_ascii() && !self.maps_ascii()) {
+            return Cow::Borrowed(text);
+        }
+        let mut folded = String::with_capacity(text.len());
+        for ch in text.chars() {
+            self.fold_char(ch, &mut |folded_ch| folded.push(folded_ch));
+        }
+        Cow::Owned(folded)
+    }
+
+    /// Folds `text` and remembers which original char each folded char came from.
+    pub fn fold_mapped(&self, text: &str) -> FoldedText {
+        let mut folded = FoldedText {
+            text: String::with_capacity(text.len()),
+            origins: Vec::with_capacity(text.len()),
+        };
+        for (position, ch) in text.chars().enumerate() {
+            if !self.is_enabled() {
+                folded.text.push(ch);
+                folded.origins.push(position as u32);
+                continue;
+            }
+            self.fold_char(ch, &mut |folded_ch| {
+                folded.text.push(folded_ch);
+                folded.origins.push(position as u32);
+            });
+        }
+        folded
+    }
+
+    fn maps_ascii(&self) -> bool {
+        self.transliteration.keys().any(char::is_ascii)
+    }
+
+    fn fold_char(&self, ch: char, push: &mut impl FnMut(char)) {
+        let replacement = self.transliteration.get(&ch).or_else(|| {
+            let mut lower = ch.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(lower), None) if lower != ch => self.transliteration.get(&lower),
+                _ => None,
+            }
+        });
+        match replacement {
+            Some(replacement) => replacement
+                .chars()
+                .for_each(|replaced| self.normalize_char(replaced, push)),
+            None => self.normalize_char(ch, push),
+        }
+    }
+
+    fn normalize_char(&self, ch: char, push: &mut impl FnMut(char)) {
+        if !self.normalize || ch.is_ascii() {
+            push(ch);
+            return;
+        }
+        // NFKD, minus the combining marks it splits off the base letters.
+        decompose_compatible(ch, |part| {
+            if is_combining_mark(part) {
+                return;
+            }
+            match letter_expansion(part) {
+                Some(expansion) => expansion.chars().for_each(&mut *push),
+                None => push(nucleo_matcher::chars::normalize(part)),
+            }
+        });
+    }
+}
+
+impl FoldedText {
+    /// Maps sorted char positions in the folded text back to the original text.
+    pub fn original_indices(&self, indices: &[u32]) -> Vec<u32> {
+        let mut mapped: Vec<u32> = indices
+            .iter()
+            .filter_map(|index| self.origins.get(*index as usize).copied())
+            .collect();
+        mapped.dedup();
+        mapped
+    }
+}
+
+/// Converts `text` into a matcher haystack with one slot per char.
+///
+/// `Utf32Str::new` keeps only the first char of each grapheme, which would
+/// shift match positions past any combining mark; one slot per char keeps them
+/// aligned with `str::chars`, which highlighting relies on.
+pub(crate) fn char_haystack<'a>(text: &'a str, buffer: &'a mut Vec<char>) -> Utf32Str<'a> {
+    if text.is_ascii() {
+        return Utf32Str::Ascii(text.as_bytes());
+    }
+    buffer.clear();
+    buffer.extend(text.chars());
+    Utf32Str::Unicode(buffer)
+}
+
+/// Letters NFKD leaves whole that are usually typed as two ASCII letters.
+///
+/// Other letters it leaves whole, such as `ø` or `ł`, go through nucleo's
+/// Latin normalization afterwards.
+fn letter_expansion(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'ß' => "ss",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextFolding, builtin_transliteration, char_haystack};
+    use std::collections::HashMap;
+
+    fn normalizing() -> TextFolding {
+        TextFolding {
+            normalize: true,
+            transliteration: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn normalization_strips_marks_and_expands_compatibility_forms() {
+        let folding = normalizing();
+        assert_eq!(folding.fold("Café"), "Cafe");
+        assert_eq!(folding.fold("Cafe\u{301}"), "Cafe");
+        assert_eq!(folding.fold("Ærøskøbing"), "AEroskobing");
+        assert_eq!(folding.fold("ﬁle Ｍａｎａｇｅｒ"), "file Manager");
+        assert_eq!(folding.fold("Straße"), "Strasse");
+        assert_eq!(folding.fold("① ǆ ㎏ x⁴ ™ …"), "1 dz kg x4 TM ...");
+        assert_eq!(TextFolding::default().fold("Café"), "Café");
+    }
+
+    #[test]
+    fn transliteration_romanizes_before_normalizing() {
+        let folding = TextFolding {
+            normalize: true,
+            transliteration: builtin_transliteration(),
+        };
+        assert_eq!(folding.fold("Москва"), "moskva");
+        assert_eq!(folding.fold("Αθήνα"), "athina");
+
+        let custom = TextFolding {
+            normalize: true,
+            transliteration: HashMap::from([('ö', "oe".to_string())]),
+        };
+        assert_eq!(custom.fold("Köln Böhm"), "Koeln Boehm");
+    }
+
+    #[test]
+    fn folded_positions_map_back_to_original_chars() {
+        let folding = TextFolding {
+            normalize: true,
+            transliteration: builtin_transliteration(),
+        };
+
+        let decomposed = folding.fold_mapped("Cafe\u{301} Bar");
+        assert_eq!(decomposed.text, "Cafe Bar");
+        assert_eq!(decomposed.original_indices(&[3, 5]), [3, 6]);
+
+        let cyrillic = folding.fold_mapped("Жук");
+        assert_eq!(cyrillic.text, "zhuk");
+        assert_eq!(cyrillic.original_indices(&[0, 1, 2]), [0, 1]);
+    }
+
+    #[test]
+    fn char_haystacks_keep_one_slot_per_char() {
+        let mut buffer = Vec::new();
+        assert_eq!(char_haystack("Cafe\u{301}", &mut buffer).len(), 5);
+        assert_eq!(char_haystack("Cafe", &mut buffer).len(), 4);
+    }
+}
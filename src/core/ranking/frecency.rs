@@ -9,6 +9,9 @@ pub fn current_unix_seconds() -> u64 {
         .unwrap_or(0)
 }
 
+/// Half-life used by the exponential frecency model unless configured: three days.
+pub const DEFAULT_HALF_LIFE_SECS: u64 = 3 * 86400;
+
 /// Frecency data for a single item.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FrecencyEntry {
@@ -16,6 +19,15 @@ pub struct FrecencyEntry {
     pub score: u64,
     /// Last access time (Unix timestamp).
     pub last_access: u64,
+    /// Exponentially decayed access count as of `last_access`.
+    pub decayed: f64,
+}
+
+/// Row layout written before `decayed` existed.
+#[derive(serde::Deserialize)]
+struct LegacyFrecencyEntry {
+    score: u64,
+    last_access: u64,
 }
 
 impl Default for FrecencyEntry {
@@ -23,20 +35,50 @@ impl Default for FrecencyEntry {
         Self {
             score: 1,
             last_access: current_unix_seconds(),
+            decayed: 1.0,
         }
     }
 }
 
 impl FrecencyEntry {
+    /// Decodes a stored row, returning whether it used the legacy layout.
+    ///
+    /// Legacy rows have no decay state, so every past access is treated as if it
+    /// happened at `last_access`.
+    pub fn decode(bytes: &[u8]) -> Option<(Self, bool)> {
+        // A current row also decodes as legacy, so try the current layout first.
+        if let Ok(entry) = postcard::from_bytes::<Self>(bytes) {
+            return Some((entry, false));
+        }
+        let legacy = postcard::from_bytes::<LegacyFrecencyEntry>(bytes).ok()?;
+        let entry = Self {
+            score: legacy.score,
+            last_access: legacy.last_access,
+            decayed: legacy.score as f64,
+        };
+        Some((entry, true))
+    }
+
     /// Records an access and updates the timestamp to now.
-    pub fn access(&mut self) {
-        self.access_at(current_unix_seconds());
+    pub fn access(&mut self, half_life_secs: u64) {
+        self.access_at(current_unix_seconds(), half_life_secs);
     }
 
     /// Records an access at a specific timestamp.
-    pub fn access_at(&mut self, now_secs: u64) {
+    pub fn access_at(&mut self, now_secs: u64, half_life_secs: u64) {
+        self.decayed = self.decayed_at(now_secs, half_life_secs) + 1.0;
         self.score += 1;
-        self.last_access = now_secs;
+        self.last_access = now_secs.max(self.last_access);
+    }
+
+    /// Calculates the exponentially decayed access count at a specific timestamp.
+    ///
+    /// Each access is worth 1 when it happens and half as much every
+    /// `half_life_secs` after that.
+    pub fn decayed_at(&self, now_secs: u64, half_life_secs: u64) -> f64 {
+        let age_secs = now_secs.saturating_sub(self.last_access) as f64;
+        let half_lives = age_secs / half_life_secs.max(1) as f64;
+        self.decayed * 0.5_f64.powf(half_lives)
     }
 
     /// Calculates the frecency score using the current time.
@@ -76,6 +118,8 @@ impl FrecencyEntry {
 }
 
 /// Ages all entries when the total score exceeds `max_age`.
+///
+/// Only access counts are divided; exponential decay state fades on its own.
 pub fn age_entries(entries: &mut HashMap<String, FrecencyEntry>, max_age: u64) {
     let total: u64 = entries.values().map(|entry| entry.score).sum();
 
@@ -93,11 +137,27 @@ mod tests {
     use super::{FrecencyEntry, age_entries};
     use std::collections::HashMap;
 
+    const HOUR: u64 = 3600;
+    const DAY: u64 = 86400;
+
+    fn accessed_at(times: &[u64], half_life_secs: u64) -> FrecencyEntry {
+        let mut entry = FrecencyEntry {
+            score: 0,
+            last_access: 0,
+            decayed: 0.0,
+        };
+        for &time in times {
+            entry.access_at(time, half_life_secs);
+        }
+        entry
+    }
+
     #[test]
     fn frecency_at_uses_expected_time_buckets() {
         let entry = FrecencyEntry {
             score: 4,
             last_access: 1_000,
+            decayed: 4.0,
         };
 
         assert_eq!(entry.frecency_at(1_100), 16.0);
@@ -114,6 +174,7 @@ mod tests {
                 FrecencyEntry {
                     score: 10,
                     last_access: 100,
+                    decayed: 10.0,
                 },
             ),
             (
@@ -121,6 +182,7 @@ mod tests {
                 FrecencyEntry {
                     score: 1,
                     last_access: 100,
+                    decayed: 1.0,
                 },
             ),
         ]);
@@ -130,4 +192,76 @@ mod tests {
         assert_eq!(entries["alpha"].score, 5);
         assert!(!entries.contains_key("beta"));
     }
+
+    #[test]
+    fn decayed_at_halves_every_half_life() {
+        let entry = accessed_at(&[1_000], DAY);
+
+        assert_eq!(entry.decayed_at(1_000, DAY), 1.0);
+        assert_eq!(entry.decayed_at(1_000 + DAY, DAY), 0.5);
+        assert_eq!(entry.decayed_at(1_000 + 3 * DAY, DAY), 0.125);
+    }
+
+    #[test]
+    fn decayed_at_has_no_jumps_at_stepped_boundaries() {
+        let entry = accessed_at(&[0], 3 * DAY);
+
+        let before = entry.decayed_at(HOUR - 1, 3 * DAY);
+        let after = entry.decayed_at(HOUR + 1, 3 * DAY);
+        assert!(before > after);
+        assert!(before - after < 0.001);
+        assert_eq!(
+            entry.frecency_at(HOUR - 1) / entry.frecency_at(HOUR + 1),
+            2.0
+        );
+    }
+
+    #[test]
+    fn recent_use_overtakes_old_heavy_use_once_it_decays() {
+        let half_life = 3 * DAY;
+        let old_favourite = accessed_at(
+            &[0, HOUR, 2 * HOUR, 3 * HOUR, 4 * HOUR, 5 * HOUR],
+            half_life,
+        );
+        let new_habit = accessed_at(&[10 * DAY, 10 * DAY + HOUR], half_life);
+
+        assert!(
+            old_favourite.decayed_at(5 * HOUR, half_life)
+                > new_habit.decayed_at(10 * DAY, half_life)
+        );
+        assert!(
+            new_habit.decayed_at(10 * DAY + HOUR, half_life)
+                > old_favourite.decayed_at(10 * DAY + HOUR, half_life)
+        );
+        // Both fade at the same rate, so the order holds as time passes.
+        assert!(
+            new_habit.decayed_at(40 * DAY, half_life)
+                > old_favourite.decayed_at(40 * DAY, half_life)
+        );
+    }
+
+    #[test]
+    fn legacy_rows_decode_with_their_count_as_decay_state() {
+        #[derive(serde::Serialize)]
+        struct Legacy {
+            score: u64,
+            last_access: u64,
+        }
+        let legacy = postcard::to_allocvec(&Legacy {
+            score: 7,
+            last_access: 500,
+        })
+        .expect("legacy row should encode");
+        let current = postcard::to_allocvec(&accessed_at(&[500], DAY)).expect("row should encode");
+
+        let (entry, was_legacy) = FrecencyEntry::decode(&legacy).expect("legacy row should decode");
+        assert!(was_legacy);
+        assert_eq!(
+            (entry.score, entry.last_access, entry.decayed),
+            (7, 500, 7.0)
+        );
+        let (entry, was_legacy) = FrecencyEntry::decode(&current).expect("row should decode");
+        assert!(!was_legacy);
+        assert_eq!(entry.decayed, 1.0);
+    }
 }
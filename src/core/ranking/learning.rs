@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+/// Default weight of learned query picks, in match tiers.
+pub const DEFAULT_LEARNING_WEIGHT: u32 = 3;
+
+/// Longest query prefix that is remembered, in characters.
+const MAX_QUERY_CHARS: usize = 32;
+
+/// Picks kept per query before every count is halved.
+const MAX_PICKS_PER_QUERY: u32 = 50;
+
+/// Normalizes a typed query for storage and lookup.
+///
+/// Case and surrounding or repeated whitespace are ignored. Returns `None` for
+/// queries with nothing to learn from.
+pub fn normalize_query(query: &str) -> Option<String> {
+    let normalized: String = query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .take(MAX_QUERY_CHARS)
+        .collect();
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// Adds a pick of `key` to the picks of one query, halving old picks past the cap.
+pub fn record_pick(picks: &mut HashMap<String, u32>, key: &str) {
+    *picks.entry(key.to_string()).or_default() += 1;
+    if picks.values().sum::<u32>() > MAX_PICKS_PER_QUERY {
+        picks.values_mut().for_each(|count| *count /= 2);
+        picks.retain(|_, count| *count > 0);
+    }
+}
+
+/// Entries launched for each typed query, keyed by normalized query and then by
+/// app store key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuerySelections {
+    by_query: HashMap<String, HashMap<String, u32>>,
+}
+
+impl QuerySelections {
+    /// Sets the picks remembered for normalized `query`.
+    pub fn insert(&mut self, query: String, picks: HashMap<String, u32>) {
+        self.by_query.insert(query, picks);
+    }
+
+    /// Confidence in `0.0..1.0` that each entry is the one meant by `query`.
+    ///
+    /// Picks made after typing `query` or a longer query that starts with it all
+    /// count, so launching Telegram from "tele" also teaches "te". One pick is
+    /// worth half; repeated picks of the same entry approach one.
+    pub fn shares_for(&self, query: &str) -> HashMap<String, f64> {
+        let Some(query) = normalize_query(query) else {
+            return HashMap::new();
+        };
+
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        for (stored, picks) in &self.by_query {
+            if stored.starts_with(&query) {
+                for (key, count) in picks {
+                    *counts.entry(key.as_str()).or_default() += count;
+                }
+            }
+        }
+
+        let total: u32 = counts.values().sum();
+        counts
+            .into_iter()
+            .map(|(key, count)| (key.to_string(), f64::from(count) / f64::from(total + 1)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QuerySelections, normalize_query, record_pick};
+    use std::collections::HashMap;
+
+    fn record(selections: &mut QuerySelections, query: &str, key: &str) {
+        let query = normalize_query(query).expect("query should not be empty");
+        record_pick(selections.by_query.entry(query).or_default(), key);
+    }
+
+    #[test]
+    fn queries_are_normalized_before_they_are_remembered() {
+        assert_eq!(normalize_query("  Te  Le "), Some("te le".to_string()));
+        assert_eq!(normalize_query("   "), None);
+        assert_eq!(
+            normalize_query(&"x".repeat(40)).map(|query| query.len()),
+            Some(32)
+        );
+    }
+
+    #[test]
+    fn picks_for_longer_queries_count_towards_their_prefixes() {
+        let mut selections = QuerySelections::default();
+        record(&mut selections, "tele", "telegram");
+        record(&mut selections, "Te", "telegram");
+        record(&mut selections, "te", "terminal");
+        record(&mut selections, "f", "firefox");
+
+        let shares = selections.shares_for("te");
+        assert_eq!(shares["telegram"], 0.5);
+        assert_eq!(shares["terminal"], 0.25);
+        assert!(!shares.contains_key("firefox"));
+        assert_eq!(selections.shares_for("tel")["telegram"], 0.5);
+        assert!(selections.shares_for("term").is_empty());
+    }
+
+    #[test]
+    fn picks_are_halved_past_the_cap() {
+        let mut picks = HashMap::new();
+        record_pick(&mut picks, "rare");
+        for _ in 0..50 {
+            record_pick(&mut picks, "common");
+        }
+
+        assert_eq!(picks.get("common"), Some(&25));
+        assert!(!picks.contains_key("rare"));
+    }
+}
@@ -1,9 +1,17 @@
 //! Pure ranking and frecency policy for launcher search.
 
+mod context;
+mod extended;
+mod fold;
 mod frecency;
+mod learning;
 mod query;
 mod sort;
 
-pub use frecency::{FrecencyEntry, age_entries, current_unix_seconds};
-pub use query::{FilterOptions, ScoreBreakdown, filter_apps};
-pub use sort::sort_by_ranking;
+pub use context::{LaunchHours, local_utc_offset_secs};
+pub use extended::ExtendedQuery;
+pub use fold::{TextFolding, builtin_transliteration};
+pub use frecency::{DEFAULT_HALF_LIFE_SECS, FrecencyEntry, age_entries, current_unix_seconds};
+pub use learning::{DEFAULT_LEARNING_WEIGHT, QuerySelections, normalize_query, record_pick};
+pub use query::{FilterOptions, ScoreBreakdown, field_completions, filter_apps, strip_field_terms};
+pub use sort::{SortOptions, sort_by_ranking};
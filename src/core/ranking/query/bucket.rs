@@ -1,3 +1,4 @@
+use crate::core::ranking::TextFolding;
 use crate::desktop::App;
 
 #[derive(Copy, Clone)]
@@ -18,6 +19,8 @@ pub(super) enum QueryBucket {
     ExecNameWordStart,
     MetadataMatch,
     FuzzyMatch,
+    PinnedExtendedMatch,
+    ExtendedMatch,
 }
 
 impl QueryBucket {
@@ -39,6 +42,8 @@ impl QueryBucket {
             Self::ExecNameWordStart => 65_000_000,
             Self::MetadataMatch => 30_000_000,
             Self::FuzzyMatch => 0,
+            Self::PinnedExtendedMatch => 20_000_000,
+            Self::ExtendedMatch => 0,
         }
     }
 
@@ -60,6 +65,17 @@ impl QueryBucket {
             Self::ExecNameWordStart => "Normal Exec Name Word-Start",
             Self::MetadataMatch => "Normal Metadata Match",
             Self::FuzzyMatch => "Normal Fuzzy Match",
+            Self::PinnedExtendedMatch => "Pinned Extended Match",
+            Self::ExtendedMatch => "Normal Extended Match",
+        }
+    }
+
+    /// Extended queries rank by term scores alone, so only pins get a tier.
+    pub(super) const fn extended(pinned: bool) -> Self {
+        if pinned {
+            Self::PinnedExtendedMatch
+        } else {
+            Self::ExtendedMatch
         }
     }
 }
@@ -70,10 +86,12 @@ pub(super) fn query_bucket(
     query_lower: &str,
     matcher_score: i64,
     prefix_depth: usize,
+    folding: &TextFolding,
 ) -> Option<QueryBucket> {
-    let app_name_lower = app.name.to_lowercase();
-    let exec_name_lower = exec_name.to_lowercase();
-    let generic_name_lower = app.generic_name.as_ref().map(|value| value.to_lowercase());
+    let lower = |text: &str| folding.fold(text).to_lowercase();
+    let app_name_lower = lower(&app.name);
+    let exec_name_lower = lower(exec_name);
+    let generic_name_lower = app.generic_name.as_deref().map(lower);
 
     let name_exact = app_name_lower == query_lower;
     let exec_exact = exec_name_lower == query_lower;
@@ -90,11 +108,15 @@ pub(super) fn query_bucket(
         || app
             .keywords
             .iter()
-            .any(|keyword| matches_word_start(&keyword.to_lowercase(), query_lower))
+            .any(|keyword| matches_word_start(&lower(keyword), query_lower))
         || app
             .categories
             .iter()
-            .any(|category| matches_word_start(&category.to_lowercase(), query_lower));
+            .any(|category| matches_word_start(&lower(category), query_lower))
+        // Desktop comments are free prose, but an executable's description is its
+        // one-line manual summary, which is worth searching.
+        || (app.is_path_executable()
+            && matches_word_start(&lower(&app.description), query_lower));
 
     let bucket = if app.pinned {
         if name_exact {
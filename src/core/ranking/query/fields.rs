@@ -0,0 +1,261 @@
+use crate::desktop::App;
+use std::collections::HashMap;
+
+/// Most values offered for one field by [`field_completions`].
+const MAX_VALUE_COMPLETIONS: usize = 8;
+
+/// App field a `name:value` query term narrows on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Category,
+    Keyword,
+    Exec,
+    Mime,
+    Pinned,
+    Terminal,
+}
+
+impl Field {
+    const ALL: [Self; 6] = [
+        Self::Category,
+        Self::Keyword,
+        Self::Exec,
+        Self::Mime,
+        Self::Pinned,
+        Self::Terminal,
+    ];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Category => "cat",
+            Self::Keyword => "kw",
+            Self::Exec => "exec",
+            Self::Mime => "mime",
+            Self::Pinned => "pinned",
+            Self::Terminal => "term",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Values of this field on `app`, for fields that hold text.
+    fn values(self, app: &App) -> Vec<&str> {
+        match self {
+            Self::Category => app.categories.iter().map(String::as_str).collect(),
+            Self::Keyword => app.keywords.iter().map(String::as_str).collect(),
+            Self::Exec => vec![crate::strings::extract_exec_name(&app.command)],
+            Self::Mime => app.mime_types.iter().map(String::as_str).collect(),
+            Self::Pinned | Self::Terminal => Vec::new(),
+        }
+    }
+}
+
+/// One `name:value` term of a launcher query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldFilter {
+    field: Field,
+    /// Lower-cased value after the colon; may be empty.
+    value: String,
+}
+
+impl FieldFilter {
+    fn matches(&self, app: &App) -> bool {
+        match self.field {
+            Field::Pinned => flag_matches(app.pinned, &self.value),
+            Field::Terminal => flag_matches(app.is_terminal, &self.value),
+            // The whole command line, so `exec:flatpak` finds `flatpak run ...`.
+            Field::Exec => app.command.to_lowercase().contains(&self.value),
+            field => field
+                .values(app)
+                .iter()
+                .any(|value| value.to_lowercase().contains(&self.value)),
+        }
+    }
+}
+
+/// A launcher query split into field filters and the text left to match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct ScopedQuery {
+    filters: Vec<FieldFilter>,
+    /// Query text without the field terms.
+    pub(super) text: String,
+}
+
+impl ScopedQuery {
+    /// Splits `cat:`, `kw:`, `exec:`, `mime:`, `pinned:` and `term:` terms out
+    /// of `query`. Terms with any other name are left in the text.
+    pub(super) fn parse(query: &str) -> Self {
+        let mut scoped = Self::default();
+        let mut text = Vec::new();
+        for term in query.split_whitespace() {
+            match parse_term(term) {
+                Some(filter) => scoped.filters.push(filter),
+                None => text.push(term),
+            }
+        }
+        scoped.text = if scoped.filters.is_empty() {
+            query.to_string()
+        } else {
+            text.join(" ")
+        };
+        scoped
+    }
+
+    /// Whether `app` passes every field filter.
+    pub(super) fn matches(&self, app: &App) -> bool {
+        self.filters.iter().all(|filter| filter.matches(app))
+    }
+}
+
+/// Returns `query` without its field terms, the text the fuzzy matcher sees.
+pub fn strip_field_terms(query: &str) -> String {
+    ScopedQuery::parse(query).text
+}
+
+/// `pinned:` and `term:` match set flags; `no`, `false` or `0` inverts them.
+fn flag_matches(set: bool, value: &str) -> bool {
+    set != matches!(value, "no" | "false" | "0")
+}
+
+fn parse_term(term: &str) -> Option<FieldFilter> {
+    let (name, value) = term.split_once(':')?;
+    Some(FieldFilter {
+        field: Field::from_name(name)?,
+        value: value.to_lowercase(),
+    })
+}
+
+/// Completions for the field term being typed at the end of `query`.
+///
+/// A partial field name, such as `ca` or `m:`, completes to matching field
+/// names; a known field with a colon completes to the values found on `apps`,
+/// most common first.
+pub fn field_completions(query: &str, apps: &[App]) -> Vec<String> {
+    if query.ends_with(char::is_whitespace) {
+        return Vec::new();
+    }
+    let Some(term) = query.split_whitespace().next_back() else {
+        return Vec::new();
+    };
+
+    let Some((name, typed)) = term.split_once(':') else {
+        // Two letters before a bare word is taken as the start of a field name.
+        if term.len() < 2 {
+            return Vec::new();
+        }
+        return field_names_starting_with(term);
+    };
+    let Some(field) = Field::from_name(name) else {
+        return field_names_starting_with(name);
+    };
+
+    match field {
+        Field::Pinned | Field::Terminal => ["", "no"]
+            .into_iter()
+            .filter(|value| value.starts_with(&typed.to_lowercase()) && *value != typed)
+            .map(|value| format!("{}:{value}", field.name()))
+            .collect(),
+        _ => {
+            let typed = typed.to_lowercase();
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for app in apps {
+                for value in field.values(app) {
+                    if !value.is_empty() && value.to_lowercase().starts_with(&typed) {
+                        *counts.entry(value).or_default() += 1;
+                    }
+                }
+            }
+            let mut values: Vec<(&str, usize)> = counts.into_iter().collect();
+            values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            values
+                .into_iter()
+                .take(MAX_VALUE_COMPLETIONS)
+                .map(|(value, _)| format!("{}:{value}", field.name()))
+                .collect()
+        }
+    }
+}
+
+fn field_names_starting_with(prefix: &str) -> Vec<String> {
+    let prefix = prefix.to_lowercase();
+    Field::ALL
+        .into_iter()
+        .filter(|field| field.name().starts_with(&prefix))
+        .map(|field| format!("{}:", field.name()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScopedQuery, field_completions};
+    use crate::desktop::App;
+
+    fn app(name: &str, extra: &str) -> App {
+        App::parse(
+            format!("[Desktop Entry]\nType=Application\nName={name}\n{extra}"),
+            false,
+        )
+        .expect("test desktop entry should parse")
+    }
+
+    #[test]
+    fn field_terms_are_split_from_the_text() {
+        let scoped = ScopedQuery::parse("cat:game  doom KW:Shooter http://x");
+        assert_eq!(scoped.text, "doom http://x");
+        assert_eq!(scoped.filters.len(), 2);
+
+        let plain = ScopedQuery::parse("fire  fox");
+        assert_eq!(plain.text, "fire  fox");
+        assert!(plain.filters.is_empty());
+    }
+
+    #[test]
+    fn filters_match_categories_exec_mime_and_flags() {
+        let mut viewer = app(
+            "Viewer",
+            "Exec=flatpak run org.viewer\nCategories=Graphics;Viewer;\nMimeType=image/png;",
+        );
+        let terminal = app("Htop", "Exec=htop\nTerminal=true\nCategories=System;");
+        viewer.pinned = true;
+
+        let matches = |query: &str| {
+            let scoped = ScopedQuery::parse(query);
+            [&viewer, &terminal]
+                .into_iter()
+                .filter(|app| scoped.matches(app))
+                .map(|app| app.name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matches("cat:graph"), ["Viewer"]);
+        assert_eq!(matches("exec:flatpak"), ["Viewer"]);
+        assert_eq!(matches("mime:image/png"), ["Viewer"]);
+        assert_eq!(matches("pinned:"), ["Viewer"]);
+        assert_eq!(matches("pinned:no"), ["Htop"]);
+        assert_eq!(matches("term:"), ["Htop"]);
+        assert!(matches("term: cat:graphics").is_empty());
+    }
+
+    #[test]
+    fn completions_offer_field_names_then_common_values() {
+        let apps = [
+            app("Doom", "Exec=doom\nCategories=Game;ActionGame;"),
+            app("Chess", "Exec=chess\nCategories=Game;BoardGame;"),
+            app("Gimp", "Exec=gimp\nCategories=Graphics;"),
+        ];
+
+        assert_eq!(field_completions("doom ca", &apps), ["cat:"]);
+        assert_eq!(field_completions("x", &apps), Vec::<String>::new());
+        assert_eq!(field_completions("e:", &apps), ["exec:"]);
+        assert_eq!(
+            field_completions("cat:g", &apps),
+            ["cat:Game", "cat:Graphics"]
+        );
+        assert_eq!(field_completions("term:", &apps), ["term:no"]);
+        assert!(field_completions("cat:g ", &apps).is_empty());
+    }
+}
@@ -1,53 +1,127 @@
 use super::FilterOptions;
+use crate::cli::MatchMode;
+use crate::core::ranking::ExtendedQuery;
 use crate::desktop::App;
 use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config, Matcher, Utf32Str};
+use std::collections::HashMap;
 
 pub(super) struct QueryContext<'a> {
     pub(super) query_lower: String,
     pattern: Pattern,
     matcher: Matcher,
+    /// Learned share of each entry for this query, by store key.
+    pub(super) learned: HashMap<String, f64>,
+    extended: Option<ExtendedQuery>,
     pub(super) options: FilterOptions<'a>,
 }
 
 impl<'a> QueryContext<'a> {
     pub(super) fn new(options: FilterOptions<'a>) -> Self {
+        let query = options.folding.fold(options.query);
         Self {
-            query_lower: options.query.to_lowercase(),
-            pattern: Pattern::parse(options.query, CaseMatching::Ignore, Normalization::Smart),
+            query_lower: query.to_lowercase(),
+            pattern: Pattern::parse(&query, CaseMatching::Ignore, Normalization::Smart),
             matcher: Matcher::new(Config::DEFAULT.match_paths()),
+            learned: if options.learning_weight > 0 {
+                options.query_selections.shares_for(options.query)
+            } else {
+                HashMap::new()
+            },
+            extended: (options.match_mode == MatchMode::Extended)
+                .then(|| ExtendedQuery::parse(&query)),
             options,
         }
     }
 }
 
 pub(super) fn base_fuzzy_score(app: &App, exec_name: &str, context: &mut QueryContext<'_>) -> i64 {
-    let name_score = fuzzy_score(&context.pattern, &mut context.matcher, &app.name);
-    let exec_score = fuzzy_score(&context.pattern, &mut context.matcher, exec_name);
+    let folding = context.options.folding;
+    let mut score = |haystack: &str| {
+        fuzzy_score(
+            &context.pattern,
+            &mut context.matcher,
+            &folding.fold(haystack),
+        )
+    };
+    let name_score = score(&app.name);
+    let exec_score = score(exec_name);
 
     let mut metadata_score = 0;
     for keyword in &app.keywords {
-        metadata_score =
-            metadata_score.max(fuzzy_score(&context.pattern, &mut context.matcher, keyword));
+        metadata_score = metadata_score.max(score(keyword));
     }
     for category in &app.categories {
-        metadata_score = metadata_score.max(fuzzy_score(
-            &context.pattern,
-            &mut context.matcher,
-            category,
-        ));
+        metadata_score = metadata_score.max(score(category));
     }
     if let Some(generic_name) = &app.generic_name {
-        metadata_score = metadata_score.max(fuzzy_score(
-            &context.pattern,
-            &mut context.matcher,
-            generic_name,
-        ));
+        metadata_score = metadata_score.max(score(generic_name));
     }
 
     name_score.max(exec_score * 2).max(metadata_score)
 }
 
+/// Terms of an extended query that matched an app, and the name positions they
+/// matched.
+pub(super) struct ExtendedAppMatch {
+    pub(super) score: i64,
+    pub(super) terms: Vec<String>,
+    pub(super) name_indices: Vec<u32>,
+}
+
+/// Matches the extended query of `context` against the searchable fields of `app`.
+pub(super) fn extended_match(
+    app: &App,
+    exec_name: &str,
+    context: &mut QueryContext<'_>,
+) -> Option<ExtendedAppMatch> {
+    let query = context.extended.as_ref()?;
+
+    let mut fields = vec![(app.name.as_str(), "name"), (exec_name, "exec")];
+    if let Some(generic_name) = &app.generic_name {
+        fields.push((generic_name, "generic name"));
+    }
+    fields.extend(
+        app.keywords
+            .iter()
+            .map(|keyword| (keyword.as_str(), "keyword")),
+    );
+    fields.extend(
+        app.categories
+            .iter()
+            .map(|category| (category.as_str(), "category")),
+    );
+    if app.is_path_executable() {
+        fields.push((app.description.as_str(), "description"));
+    }
+
+    let folding = context.options.folding;
+    let folded: Vec<_> = fields.iter().map(|(text, _)| folding.fold(text)).collect();
+    let haystacks: Vec<&str> = folded.iter().map(|text| text.as_ref()).collect();
+    let matched = query.match_fields(&haystacks, &mut context.matcher)?;
+    let name_indices = if folding.is_enabled() {
+        folding
+            .fold_mapped(&app.name)
+            .original_indices(&matched.indices)
+    } else {
+        matched.indices
+    };
+    let terms = matched
+        .terms
+        .into_iter()
+        .map(|(term, field)| match field {
+            Some(field) => format!("{term} ({})", fields[field].1),
+            None => format!("{term} (absent)"),
+        })
+        .collect();
+
+    Some(ExtendedAppMatch {
+        score: i64::from(matched.score),
+        terms,
+        name_indices,
+    })
+}
+
 fn fuzzy_score(pattern: &Pattern, matcher: &mut Matcher, haystack: &str) -> i64 {
     let mut buffer = Vec::new();
     let haystack = Utf32Str::new(haystack, &mut buffer);
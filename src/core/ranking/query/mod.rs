@@ -1,12 +1,15 @@
 mod bucket;
+mod fields;
 mod matcher;
 
-use super::FrecencyEntry;
 use super::sort::{compare_names, compare_pinned_order, ranking_boost, ranking_score};
-use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+use super::{FrecencyEntry, LaunchHours, QuerySelections, TextFolding};
+use crate::cli::{FrecencyModel, MatchMode, PinnedOrderMode, RankingMode};
 use crate::desktop::App;
-use bucket::query_bucket;
-use matcher::{QueryContext, base_fuzzy_score};
+use bucket::{QueryBucket, query_bucket};
+use fields::ScopedQuery;
+pub use fields::{field_completions, strip_field_terms};
+use matcher::{QueryContext, base_fuzzy_score, extended_match};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -26,6 +29,18 @@ pub struct ScoreBreakdown {
     /// Name of the ranking mode that produced the frecency boost.
     #[serde(default = "default_ranking_mode_label")]
     pub ranking_mode: String,
+    /// Extra boost from launches at this time of the week (contextual ranking).
+    #[serde(default)]
+    pub context_boost: i64,
+    /// Extra boost from earlier launches of this entry for the same query.
+    #[serde(default)]
+    pub learned_boost: i64,
+    /// Extended-syntax terms that matched, each with the field it matched.
+    #[serde(default)]
+    pub matched_terms: Vec<String>,
+    /// Char positions of the name matched by an extended query, for highlighting.
+    #[serde(default)]
+    pub name_indices: Vec<u32>,
 }
 
 fn default_ranking_mode_label() -> String {
@@ -39,70 +54,139 @@ pub struct FilterOptions<'a> {
     pub query: &'a str,
     /// Matching strategy for the current query.
     pub match_mode: MatchMode,
-    /// Frecency metadata keyed by app name.
+    /// Frecency metadata keyed by app store key.
     pub frecency_data: &'a HashMap<String, FrecencyEntry>,
     /// Prefix depth that still enables word-start tiering.
     pub prefix_depth: usize,
     /// Ranking mode used for frecency boosts.
     pub ranking_mode: RankingMode,
+    /// How the frecency ranking mode weighs past accesses.
+    pub frecency_model: FrecencyModel,
+    /// Half-life of the exponential frecency model, in seconds.
+    pub half_life_secs: u64,
     /// Pinned-app tie-break policy.
     pub pinned_order_mode: PinnedOrderMode,
     /// First-pin timestamps for deterministic pinned ordering.
     pub pin_timestamps: &'a HashMap<String, u64>,
+    /// Positions in the manual pinned order.
+    pub pin_order: &'a HashMap<String, u64>,
+    /// Launch counts by hour of the week for the contextual ranking mode.
+    pub launch_hours: &'a HashMap<String, LaunchHours>,
+    /// Entries launched for earlier queries.
+    pub query_selections: &'a QuerySelections,
+    /// Match tiers an entry always launched for the query can climb; 0 disables.
+    pub learning_weight: u32,
+    /// How query and entry text are folded before matching.
+    pub folding: &'a TextFolding,
     /// Current Unix timestamp in seconds.
     pub now_secs: u64,
 }
 
+/// Score gap between neighbouring match tiers.
+const TIER_SCORE: f64 = 5_000_000.0;
+
 /// Filters applications for `options.query` and returns them in ranked order.
+///
+/// Field terms such as `cat:game` or `pinned:` narrow the apps first; the rest of
+/// the query is matched as usual. A query of field terms alone keeps the order of
+/// `apps`.
 pub fn filter_apps(apps: &[App], options: FilterOptions<'_>) -> Vec<App> {
     if options.query.is_empty() {
         return apps.to_vec();
     }
 
-    let mut context = QueryContext::new(options);
-    let mut scored: Vec<(i64, App)> = apps
-        .iter()
+    let scoped = ScopedQuery::parse(options.query);
+    let candidates = apps.iter().filter(|app| scoped.matches(app));
+    if scoped.text.is_empty() {
+        return candidates.cloned().collect();
+    }
+
+    let mut context = QueryContext::new(FilterOptions {
+        query: &scoped.text,
+        ..options
+    });
+    let mut scored: Vec<(i64, String, App)> = candidates
         .filter_map(|app| score_app_for_query(app, &mut context))
         .collect();
 
-    scored.sort_by(|a, b| {
-        b.0.cmp(&a.0)
+    scored.sort_by(|(a_score, a_key, a), (b_score, b_key, b)| {
+        b_score
+            .cmp(a_score)
             .then_with(|| {
                 compare_pinned_order(
-                    &a.1,
-                    &b.1,
+                    (a, a_key),
+                    (b, b_key),
                     context.options.pinned_order_mode,
                     context.options.pin_timestamps,
+                    context.options.pin_order,
                 )
             })
-            .then_with(|| compare_names(&a.1.name, &b.1.name))
+            .then_with(|| compare_names(&a.name, &b.name))
     });
 
-    scored.into_iter().map(|(_, app)| app).collect()
+    scored.into_iter().map(|(_, _, app)| app).collect()
 }
 
-fn score_app_for_query(app: &App, context: &mut QueryContext<'_>) -> Option<(i64, App)> {
+/// Scores `app` for the query, returning the score, its store key, and a copy
+/// carrying the score breakdown.
+fn score_app_for_query(app: &App, context: &mut QueryContext<'_>) -> Option<(i64, String, App)> {
     let exec_name = crate::strings::extract_exec_name(&app.command);
-    let matcher_score = match context.options.match_mode {
-        MatchMode::Exact => 0,
-        MatchMode::Fuzzy => base_fuzzy_score(app, exec_name, context),
+    let mut matched_terms = Vec::new();
+    let mut name_indices = Vec::new();
+    let (bucket, matcher_score) = if context.options.match_mode == MatchMode::Extended {
+        let matched = extended_match(app, exec_name, context)?;
+        matched_terms = matched.terms;
+        name_indices = matched.name_indices;
+        (QueryBucket::extended(app.pinned), matched.score)
+    } else {
+        let matcher_score = match context.options.match_mode {
+            MatchMode::Fuzzy => base_fuzzy_score(app, exec_name, context),
+            MatchMode::Exact | MatchMode::Extended => 0,
+        };
+        let bucket = query_bucket(
+            app,
+            exec_name,
+            &context.query_lower,
+            matcher_score,
+            context.options.prefix_depth,
+            context.options.folding,
+        )?;
+        (bucket, matcher_score)
     };
-    let bucket = query_bucket(
-        app,
-        exec_name,
-        &context.query_lower,
-        matcher_score,
-        context.options.prefix_depth,
-    )?;
 
+    let options = context.options;
+    let store_key = app.store_key();
+    let entry = options.frecency_data.get(&store_key);
     let frecency_score = ranking_score(
-        context.options.frecency_data.get(&app.name),
-        context.options.ranking_mode,
-        context.options.now_secs,
+        entry,
+        None,
+        options.ranking_mode,
+        options.frecency_model,
+        options.half_life_secs,
+        options.now_secs,
     );
-    let frecency_boost = ranking_boost(frecency_score, context.options.ranking_mode);
+    let frecency_boost = ranking_boost(frecency_score, options.ranking_mode);
+    // The time-of-week share is reported apart from the frecency it scales.
+    let context_boost = match options.ranking_mode {
+        RankingMode::Contextual => {
+            let contextual_score = ranking_score(
+                entry,
+                options.launch_hours.get(&store_key),
+                options.ranking_mode,
+                options.frecency_model,
+                options.half_life_secs,
+                options.now_secs,
+            );
+            ranking_boost(contextual_score, options.ranking_mode) - frecency_boost
+        }
+        _ => 0,
+    };
+    let learned_boost = context.learned.get(&store_key).map_or(0, |share| {
+        (share * f64::from(options.learning_weight) * TIER_SCORE) as i64
+    });
     let matcher_boost = matcher_score * 100;
-    let final_score = bucket.score() + matcher_boost + frecency_boost;
+    let final_score =
+        bucket.score() + matcher_boost + frecency_boost + context_boost + learned_boost;
 
     let mut ranked_app = app.clone();
     ranked_app.score = final_score;
@@ -112,16 +196,24 @@ fn score_app_for_query(app: &App, context: &mut QueryContext<'_>) -> Option<(i64
         matcher_score: matcher_boost,
         frecency_boost,
         raw_frecency_milli: (frecency_score * 1000.0) as i64,
-        ranking_mode: context.options.ranking_mode.as_str().to_string(),
+        ranking_mode: options.ranking_mode.as_str().to_string(),
+        context_boost,
+        learned_boost,
+        matched_terms,
+        name_indices,
     });
 
-    Some((final_score, ranked_app))
+    Some((final_score, store_key, ranked_app))
 }
 
 #[cfg(test)]
 mod tests {
     use super::{FilterOptions, filter_apps};
-    use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+    use crate::cli::{FrecencyModel, MatchMode, PinnedOrderMode, RankingMode};
+    use crate::core::ranking::{
+        DEFAULT_HALF_LIFE_SECS, DEFAULT_LEARNING_WEIGHT, FrecencyEntry, LaunchHours,
+        QuerySelections, TextFolding,
+    };
     use crate::desktop::App;
     use std::collections::HashMap;
 
@@ -168,8 +260,15 @@ mod tests {
                 frecency_data: &HashMap::new(),
                 prefix_depth: 5,
                 ranking_mode: RankingMode::Frecency,
+                frecency_model: FrecencyModel::Stepped,
+                half_life_secs: DEFAULT_HALF_LIFE_SECS,
                 pinned_order_mode: PinnedOrderMode::Ranking,
                 pin_timestamps: &HashMap::new(),
+                pin_order: &HashMap::new(),
+                launch_hours: &HashMap::new(),
+                query_selections: &QuerySelections::default(),
+                learning_weight: DEFAULT_LEARNING_WEIGHT,
+                folding: &TextFolding::default(),
                 now_secs: 10_000,
             },
         );
@@ -203,8 +302,15 @@ mod tests {
                 frecency_data: &HashMap::new(),
                 prefix_depth: 5,
                 ranking_mode: RankingMode::Frecency,
+                frecency_model: FrecencyModel::Stepped,
+                half_life_secs: DEFAULT_HALF_LIFE_SECS,
                 pinned_order_mode: PinnedOrderMode::Ranking,
                 pin_timestamps: &HashMap::new(),
+                pin_order: &HashMap::new(),
+                launch_hours: &HashMap::new(),
+                query_selections: &QuerySelections::default(),
+                learning_weight: DEFAULT_LEARNING_WEIGHT,
+                folding: &TextFolding::default(),
                 now_secs: 10_000,
             },
         );
@@ -218,4 +324,301 @@ mod tests {
             Some("Normal Metadata Match")
         );
     }
+
+    #[test]
+    fn filter_apps_matches_executable_summaries_but_not_desktop_comments() {
+        let mut tool = test_app("rg", "/usr/bin/rg", None, &[], &[]);
+        tool.description = "recursively search the current directory".to_string();
+        tool.set_source_path(std::path::Path::new("/usr/bin/rg"));
+        let mut desktop = test_app("Finder", "/usr/bin/finder", None, &[], &[]);
+        desktop.description = "search your files".to_string();
+        desktop.set_source_path(std::path::Path::new(
+            "/usr/share/applications/finder.desktop",
+        ));
+
+        let ranked = filter_apps(
+            &[tool, desktop],
+            FilterOptions {
+                query: "search",
+                match_mode: MatchMode::Exact,
+                frecency_data: &HashMap::new(),
+                prefix_depth: 6,
+                ranking_mode: RankingMode::Frecency,
+                frecency_model: FrecencyModel::Stepped,
+                half_life_secs: DEFAULT_HALF_LIFE_SECS,
+                pinned_order_mode: PinnedOrderMode::Ranking,
+                pin_timestamps: &HashMap::new(),
+                pin_order: &HashMap::new(),
+                launch_hours: &HashMap::new(),
+                query_selections: &QuerySelections::default(),
+                learning_weight: DEFAULT_LEARNING_WEIGHT,
+                folding: &TextFolding::default(),
+                now_secs: 10_000,
+            },
+        );
+
+        let names: Vec<&str> = ranked.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, ["rg"]);
+    }
+
+    #[test]
+    fn contextual_boost_is_reported_separately_in_the_breakdown() {
+        let apps = vec![
+            test_app("Mail", "/usr/bin/mail", None, &[], &[]),
+            test_app("Maps", "/usr/bin/maps", None, &[], &[]),
+        ];
+        let now = 100 * 86400;
+        let used = FrecencyEntry {
+            score: 2,
+            last_access: now - 3600,
+            decayed: 2.0,
+        };
+        let frecency = HashMap::from([
+            ("Mail".to_string(), used.clone()),
+            ("Maps".to_string(), used),
+        ]);
+        let mut mail_hours = LaunchHours::default();
+        mail_hours.record(now - 7 * 86400);
+        let launch_hours = HashMap::from([("Mail".to_string(), mail_hours)]);
+
+        let ranked = filter_apps(
+            &apps,
+            FilterOptions {
+                query: "ma",
+                match_mode: MatchMode::Fuzzy,
+                frecency_data: &frecency,
+                prefix_depth: 3,
+                ranking_mode: RankingMode::Contextual,
+                frecency_model: FrecencyModel::Stepped,
+                half_life_secs: DEFAULT_HALF_LIFE_SECS,
+                pinned_order_mode: PinnedOrderMode::Ranking,
+                pin_timestamps: &HashMap::new(),
+                pin_order: &HashMap::new(),
+                launch_hours: &launch_hours,
+                query_selections: &QuerySelections::default(),
+                learning_weight: DEFAULT_LEARNING_WEIGHT,
+                folding: &TextFolding::default(),
+                now_secs: now,
+            },
+        );
+
+        let boosts: Vec<(&str, i64, i64)> = ranked
+            .iter()
+            .map(|app| {
+                let breakdown = app.breakdown.as_ref().expect("breakdown should be set");
+                (
+                    app.name.as_str(),
+                    breakdown.frecency_boost,
+                    breakdown.context_boost,
+                )
+            })
+            .collect();
+        assert_eq!(boosts[0].0, "Mail");
+        assert_eq!(boosts[0].1, boosts[1].1);
+        assert!(boosts[0].2 > 0);
+        assert_eq!(boosts[1].2, 0);
+    }
+
+    #[test]
+    fn learned_picks_lift_the_entry_usually_launched_for_a_query() {
+        let apps = vec![
+            test_app("Terminal", "/usr/bin/terminal", None, &[], &[]),
+            test_app("Telegram", "/usr/bin/telegram", None, &[], &[]),
+        ];
+        // Terminal is launched more often overall, but "te" usually means Telegram.
+        let frecency = HashMap::from([(
+            "Terminal".to_string(),
+            FrecencyEntry {
+                score: 10,
+                last_access: 9_000,
+                decayed: 10.0,
+            },
+        )]);
+        let mut query_selections = QuerySelections::default();
+        query_selections.insert(
+            "tel".to_string(),
+            HashMap::from([("Telegram".to_string(), 1)]),
+        );
+        let ranked = |learning_weight| {
+            filter_apps(
+                &apps,
+                FilterOptions {
+                    query: "te",
+                    match_mode: MatchMode::Fuzzy,
+                    frecency_data: &frecency,
+                    prefix_depth: 3,
+                    ranking_mode: RankingMode::Frecency,
+                    frecency_model: FrecencyModel::Stepped,
+                    half_life_secs: DEFAULT_HALF_LIFE_SECS,
+                    pinned_order_mode: PinnedOrderMode::Ranking,
+                    pin_timestamps: &HashMap::new(),
+                    pin_order: &HashMap::new(),
+                    launch_hours: &HashMap::new(),
+                    query_selections: &query_selections,
+                    learning_weight,
+                    folding: &TextFolding::default(),
+                    now_secs: 10_000,
+                },
+            )
+        };
+
+        let learned = ranked(DEFAULT_LEARNING_WEIGHT);
+        assert_eq!(learned[0].name, "Telegram");
+        assert_eq!(
+            learned[0]
+                .breakdown
+                .as_ref()
+                .map(|breakdown| breakdown.learned_boost),
+            Some(7_500_000)
+        );
+        assert_eq!(ranked(0)[0].name, "Terminal");
+    }
+
+    #[test]
+    fn extended_queries_report_matched_terms_and_name_positions() {
+        let apps = vec![
+            test_app("Firefox", "/usr/bin/firefox", Some("Web Browser"), &[], &[]),
+            test_app(
+                "Firefox Nightly",
+                "/usr/bin/firefox-nightly",
+                None,
+                &[],
+                &[],
+            ),
+            test_app("Files", "/usr/bin/nautilus", None, &[], &[]),
+        ];
+
+        let ranked = filter_apps(
+            &apps,
+            FilterOptions {
+                query: "^fi browser | nautilus !nightly",
+                match_mode: MatchMode::Extended,
+                frecency_data: &HashMap::new(),
+                prefix_depth: 3,
+                ranking_mode: RankingMode::Frecency,
+                frecency_model: FrecencyModel::Stepped,
+                half_life_secs: DEFAULT_HALF_LIFE_SECS,
+                pinned_order_mode: PinnedOrderMode::Ranking,
+                pin_timestamps: &HashMap::new(),
+                pin_order: &HashMap::new(),
+                launch_hours: &HashMap::new(),
+                query_selections: &QuerySelections::default(),
+                learning_weight: DEFAULT_LEARNING_WEIGHT,
+                folding: &TextFolding::default(),
+                now_secs: 10_000,
+            },
+        );
+
+        let mut names: Vec<&str> = ranked.iter().map(|app| app.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["Files", "Firefox"]);
+        let firefox = ranked
+            .iter()
+            .find(|app| app.name == "Firefox")
+            .and_then(|app| app.breakdown.as_ref())
+            .expect("Firefox should have a breakdown");
+        assert_eq!(firefox.tier, "Normal Extended Match");
+        assert_eq!(
+            firefox.matched_terms,
+            ["^fi (name)", "browser (generic name)", "!nightly (absent)"]
+        );
+        assert_eq!(firefox.name_indices, [0, 1]);
+    }
+
+    #[test]
+    fn folded_queries_match_accented_and_transliterated_names() {
+        let apps = vec![
+            test_app("Café", "/usr/bin/bistro", None, &[], &[]),
+            test_app("Москва Maps", "/usr/bin/moscow-maps", None, &[], &[]),
+        ];
+        let folding = TextFolding {
+            normalize: true,
+            transliteration: crate::core::ranking::builtin_transliteration(),
+        };
+        let unfolded = TextFolding::default();
+        let ranked = |query, match_mode, folding| {
+            filter_apps(
+                &apps,
+                FilterOptions {
+                    query,
+                    match_mode,
+                    frecency_data: &HashMap::new(),
+                    prefix_depth: 3,
+                    ranking_mode: RankingMode::Frecency,
+                    frecency_model: FrecencyModel::Stepped,
+                    half_life_secs: DEFAULT_HALF_LIFE_SECS,
+                    pinned_order_mode: PinnedOrderMode::Ranking,
+                    pin_timestamps: &HashMap::new(),
+                    pin_order: &HashMap::new(),
+                    launch_hours: &HashMap::new(),
+                    query_selections: &QuerySelections::default(),
+                    learning_weight: DEFAULT_LEARNING_WEIGHT,
+                    folding,
+                    now_secs: 10_000,
+                },
+            )
+        };
+        let tier = |app: &App| {
+            app.breakdown
+                .as_ref()
+                .map(|breakdown| breakdown.tier.clone())
+        };
+
+        let cafe = ranked("cafe", MatchMode::Exact, &folding);
+        assert_eq!(cafe[0].name, "Café");
+        assert_eq!(tier(&cafe[0]).as_deref(), Some("Normal App Name Exact"));
+        assert!(ranked("cafe", MatchMode::Exact, &unfolded).is_empty());
+
+        let moskva = ranked("mosk", MatchMode::Exact, &folding);
+        assert_eq!(moskva[0].name, "Москва Maps");
+        assert_eq!(tier(&moskva[0]).as_deref(), Some("Normal App Name Prefix"));
+
+        let extended = ranked("^mosk", MatchMode::Extended, &folding);
+        assert_eq!(
+            extended[0]
+                .breakdown
+                .as_ref()
+                .map(|breakdown| breakdown.name_indices.clone()),
+            Some(vec![0, 1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn field_terms_narrow_apps_before_the_text_is_matched() {
+        let apps = vec![
+            test_app("Doom", "/usr/bin/doom", None, &[], &["Game"]),
+            test_app("Dolphin", "/usr/bin/dolphin", None, &[], &["System"]),
+            test_app("Chess", "/usr/bin/chess", None, &[], &["Game"]),
+        ];
+        let ranked = |query| {
+            filter_apps(
+                &apps,
+                FilterOptions {
+                    query,
+                    match_mode: MatchMode::Fuzzy,
+                    frecency_data: &HashMap::new(),
+                    prefix_depth: 3,
+                    ranking_mode: RankingMode::Frecency,
+                    frecency_model: FrecencyModel::Stepped,
+                    half_life_secs: DEFAULT_HALF_LIFE_SECS,
+                    pinned_order_mode: PinnedOrderMode::Ranking,
+                    pin_timestamps: &HashMap::new(),
+                    pin_order: &HashMap::new(),
+                    launch_hours: &HashMap::new(),
+                    query_selections: &QuerySelections::default(),
+                    learning_weight: DEFAULT_LEARNING_WEIGHT,
+                    folding: &TextFolding::default(),
+                    now_secs: 10_000,
+                },
+            )
+            .into_iter()
+            .map(|app| app.name)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranked("do"), ["Dolphin", "Doom"]);
+        assert_eq!(ranked("cat:game do"), ["Doom"]);
+        assert_eq!(ranked("do cat:game"), ["Doom"]);
+        assert_eq!(ranked("cat:game"), ["Doom", "Chess"]);
+    }
 }
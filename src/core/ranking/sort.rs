@@ -1,5 +1,5 @@
-use super::FrecencyEntry;
-use crate::cli::{PinnedOrderMode, RankingMode};
+use super::{FrecencyEntry, LaunchHours};
+use crate::cli::{FrecencyModel, PinnedOrderMode, RankingMode};
 use crate::desktop::App;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -8,11 +8,14 @@ pub(super) fn compare_names(a: &str, b: &str) -> Ordering {
     a.to_lowercase().cmp(&b.to_lowercase()).then(a.cmp(b))
 }
 
+/// Orders two pinned apps by `pinned_order_mode`; `a_key` and `b_key` are their
+/// store keys, which callers compute once rather than on every comparison.
 pub(super) fn compare_pinned_order(
-    a: &App,
-    b: &App,
+    (a, a_key): (&App, &str),
+    (b, b_key): (&App, &str),
     pinned_order_mode: PinnedOrderMode,
     pin_timestamps: &HashMap<String, u64>,
+    pin_order: &HashMap<String, u64>,
 ) -> Ordering {
     if !(a.pinned && b.pinned) {
         return Ordering::Equal;
@@ -23,8 +26,8 @@ pub(super) fn compare_pinned_order(
         PinnedOrderMode::Alphabetical => compare_names(&a.name, &b.name),
         PinnedOrderMode::OldestPinned => {
             let by_time = match (
-                pin_timestamps.get(&a.name).copied(),
-                pin_timestamps.get(&b.name).copied(),
+                pin_timestamps.get(a_key).copied(),
+                pin_timestamps.get(b_key).copied(),
             ) {
                 (Some(a_ts), Some(b_ts)) => a_ts.cmp(&b_ts),
                 (Some(_), None) => Ordering::Less,
@@ -35,8 +38,8 @@ pub(super) fn compare_pinned_order(
         }
         PinnedOrderMode::NewestPinned => {
             let by_time = match (
-                pin_timestamps.get(&a.name).copied(),
-                pin_timestamps.get(&b.name).copied(),
+                pin_timestamps.get(a_key).copied(),
+                pin_timestamps.get(b_key).copied(),
             ) {
                 (Some(a_ts), Some(b_ts)) => b_ts.cmp(&a_ts),
                 (Some(_), None) => Ordering::Less,
@@ -45,6 +48,15 @@ pub(super) fn compare_pinned_order(
             };
             by_time.then_with(|| compare_names(&a.name, &b.name))
         }
+        PinnedOrderMode::Manual => {
+            let by_position = match (pin_order.get(a_key).copied(), pin_order.get(b_key).copied()) {
+                (Some(a_pos), Some(b_pos)) => a_pos.cmp(&b_pos),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            by_position.then_with(|| compare_names(&a.name, &b.name))
+        }
     }
 }
 
@@ -56,85 +68,154 @@ fn recency_score(last_access: u64, now_secs: u64) -> f64 {
 
 pub(super) fn ranking_score(
     entry: Option<&FrecencyEntry>,
+    launch_hours: Option<&LaunchHours>,
     ranking_mode: RankingMode,
+    frecency_model: FrecencyModel,
+    half_life_secs: u64,
     now_secs: u64,
 ) -> f64 {
     let Some(entry) = entry else {
         return 0.0;
     };
 
+    let frecency = || match frecency_model {
+        FrecencyModel::Stepped => entry.frecency_at(now_secs),
+        FrecencyModel::Exponential => entry.decayed_at(now_secs, half_life_secs),
+    };
     match ranking_mode {
-        RankingMode::Frecency => entry.frecency_at(now_secs),
+        RankingMode::Frecency => frecency(),
         RankingMode::Frequency => entry.score as f64,
         RankingMode::Recency => recency_score(entry.last_access, now_secs),
+        RankingMode::Contextual => {
+            let multiplier = launch_hours.map_or(1.0, |hours| hours.multiplier_at(now_secs));
+            frecency() * multiplier
+        }
     }
 }
 
 pub(super) fn ranking_boost(score: f64, ranking_mode: RankingMode) -> i64 {
     match ranking_mode {
-        RankingMode::Frecency => (score * 10.0) as i64,
+        RankingMode::Frecency | RankingMode::Contextual => (score * 10.0) as i64,
         RankingMode::Frequency => (score * 10.0) as i64,
         RankingMode::Recency => (score * 10_000.0) as i64,
     }
 }
 
+/// Inputs required to order apps without a search query.
+#[derive(Clone, Copy)]
+pub struct SortOptions<'a> {
+    /// Frecency metadata keyed by app store key.
+    pub frecency_data: &'a HashMap<String, FrecencyEntry>,
+    /// Ranking mode used for ordering.
+    pub ranking_mode: RankingMode,
+    /// How the frecency ranking mode weighs past accesses.
+    pub frecency_model: FrecencyModel,
+    /// Half-life of the exponential frecency model, in seconds.
+    pub half_life_secs: u64,
+    /// Pinned-app tie-break policy.
+    pub pinned_order_mode: PinnedOrderMode,
+    /// First-pin timestamps for deterministic pinned ordering.
+    pub pin_timestamps: &'a HashMap<String, u64>,
+    /// Positions in the manual pinned order.
+    pub pin_order: &'a HashMap<String, u64>,
+    /// Launch counts by hour of the week for the contextual ranking mode.
+    pub launch_hours: &'a HashMap<String, LaunchHours>,
+    /// Current Unix timestamp in seconds.
+    pub now_secs: u64,
+}
+
+/// What ordering an app needs, computed once per sort rather than on every
+/// comparison.
+struct SortFacts<'a> {
+    key: String,
+    entry: Option<&'a FrecencyEntry>,
+    /// Frecency or contextual score, for the modes that rank by one.
+    score: f64,
+}
+
 /// Sorts applications by the configured ranking mode and pinned policy.
-pub fn sort_by_ranking(
-    apps: &mut [App],
-    frecency_data: &HashMap<String, FrecencyEntry>,
-    ranking_mode: RankingMode,
-    pinned_order_mode: PinnedOrderMode,
-    pin_timestamps: &HashMap<String, u64>,
-    now_secs: u64,
-) {
-    for app in apps.iter_mut() {
-        if let Some(entry) = frecency_data.get(&app.name) {
-            app.last_access = Some(entry.last_access);
-        }
-    }
+pub fn sort_by_ranking(apps: &mut Vec<App>, options: SortOptions<'_>) {
+    let SortOptions {
+        frecency_data,
+        ranking_mode,
+        frecency_model,
+        half_life_secs,
+        pinned_order_mode,
+        pin_timestamps,
+        pin_order,
+        launch_hours,
+        now_secs,
+    } = options;
+
+    let mut ranked: Vec<(SortFacts<'_>, App)> = apps
+        .drain(..)
+        .map(|mut app| {
+            let key = app.store_key();
+            let entry = frecency_data.get(&key);
+            if let Some(entry) = entry {
+                app.last_access = Some(entry.last_access);
+            }
+            let score = match ranking_mode {
+                RankingMode::Frecency | RankingMode::Contextual => ranking_score(
+                    entry,
+                    launch_hours.get(&key),
+                    ranking_mode,
+                    frecency_model,
+                    half_life_secs,
+                    now_secs,
+                ),
+                RankingMode::Frequency | RankingMode::Recency => 0.0,
+            };
+            (SortFacts { key, entry, score }, app)
+        })
+        .collect();
 
-    apps.sort_by(|a, b| {
+    ranked.sort_by(|(a_facts, a), (b_facts, b)| {
         match (a.pinned, b.pinned) {
             (true, false) => return Ordering::Less,
             (false, true) => return Ordering::Greater,
             _ => {}
         }
 
-        let pinned_order = compare_pinned_order(a, b, pinned_order_mode, pin_timestamps);
+        let pinned_order = compare_pinned_order(
+            (a, &a_facts.key),
+            (b, &b_facts.key),
+            pinned_order_mode,
+            pin_timestamps,
+            pin_order,
+        );
         if pinned_order != Ordering::Equal {
             return pinned_order;
         }
 
-        let a_entry = frecency_data.get(&a.name);
-        let b_entry = frecency_data.get(&b.name);
-
         let ranking_cmp = match ranking_mode {
-            RankingMode::Frecency => {
-                let a_score = ranking_score(a_entry, ranking_mode, now_secs);
-                let b_score = ranking_score(b_entry, ranking_mode, now_secs);
-                b_score.partial_cmp(&a_score).unwrap_or(Ordering::Equal)
-            }
+            RankingMode::Frecency | RankingMode::Contextual => b_facts
+                .score
+                .partial_cmp(&a_facts.score)
+                .unwrap_or(Ordering::Equal),
             RankingMode::Frequency => {
-                let a_score = a_entry.map(|entry| entry.score).unwrap_or(0);
-                let b_score = b_entry.map(|entry| entry.score).unwrap_or(0);
+                let a_score = a_facts.entry.map(|entry| entry.score).unwrap_or(0);
+                let b_score = b_facts.entry.map(|entry| entry.score).unwrap_or(0);
                 b_score.cmp(&a_score)
             }
             RankingMode::Recency => {
-                let a_score = a_entry.map(|entry| entry.last_access).unwrap_or(0);
-                let b_score = b_entry.map(|entry| entry.last_access).unwrap_or(0);
+                let a_score = a_facts.entry.map(|entry| entry.last_access).unwrap_or(0);
+                let b_score = b_facts.entry.map(|entry| entry.last_access).unwrap_or(0);
                 b_score.cmp(&a_score)
             }
         };
 
         ranking_cmp.then_with(|| compare_names(&a.name, &b.name))
     });
+
+    apps.extend(ranked.into_iter().map(|(_, app)| app));
 }
 
 #[cfg(test)]
 mod tests {
-    use super::sort_by_ranking;
-    use crate::cli::{PinnedOrderMode, RankingMode};
-    use crate::core::ranking::FrecencyEntry;
+    use super::{SortOptions, sort_by_ranking};
+    use crate::cli::{FrecencyModel, PinnedOrderMode, RankingMode};
+    use crate::core::ranking::{DEFAULT_HALF_LIFE_SECS, FrecencyEntry, LaunchHours};
     use crate::desktop::App;
     use std::collections::HashMap;
 
@@ -148,6 +229,37 @@ mod tests {
         .expect("test desktop entry should parse")
     }
 
+    fn entry(score: u64, last_access: u64) -> FrecencyEntry {
+        FrecencyEntry {
+            score,
+            last_access,
+            decayed: score as f64,
+        }
+    }
+
+    fn options<'a>(
+        frecency_data: &'a HashMap<String, FrecencyEntry>,
+        empty: &'a HashMap<String, u64>,
+    ) -> SortOptions<'a> {
+        static NO_LAUNCH_HOURS: std::sync::LazyLock<HashMap<String, LaunchHours>> =
+            std::sync::LazyLock::new(HashMap::new);
+        SortOptions {
+            frecency_data,
+            ranking_mode: RankingMode::Frecency,
+            frecency_model: FrecencyModel::Stepped,
+            half_life_secs: DEFAULT_HALF_LIFE_SECS,
+            pinned_order_mode: PinnedOrderMode::Ranking,
+            pin_timestamps: empty,
+            pin_order: empty,
+            launch_hours: &NO_LAUNCH_HOURS,
+            now_secs: 10_000,
+        }
+    }
+
+    fn names(apps: &[App]) -> Vec<&str> {
+        apps.iter().map(|app| app.name.as_str()).collect()
+    }
+
     #[test]
     fn sort_by_ranking_respects_oldest_pinned_order() {
         let mut alpha = test_app("Alpha", "/usr/bin/alpha");
@@ -157,20 +269,46 @@ mod tests {
 
         let mut apps = vec![beta, alpha];
         let pin_timestamps = HashMap::from([("Alpha".to_string(), 100), ("Beta".to_string(), 200)]);
+        let (frecency, empty) = (HashMap::new(), HashMap::new());
 
         sort_by_ranking(
             &mut apps,
-            &HashMap::new(),
-            RankingMode::Frecency,
-            PinnedOrderMode::OldestPinned,
-            &pin_timestamps,
-            10_000,
+            SortOptions {
+                pinned_order_mode: PinnedOrderMode::OldestPinned,
+                pin_timestamps: &pin_timestamps,
+                ..options(&frecency, &empty)
+            },
         );
 
         assert_eq!(apps[0].name, "Alpha");
         assert_eq!(apps[1].name, "Beta");
     }
 
+    #[test]
+    fn sort_by_ranking_follows_manual_pinned_order() {
+        let mut alpha = test_app("Alpha", "/usr/bin/alpha");
+        let mut beta = test_app("Beta", "/usr/bin/beta");
+        let mut gamma = test_app("Gamma", "/usr/bin/gamma");
+        alpha.pinned = true;
+        beta.pinned = true;
+        gamma.pinned = true;
+
+        let mut apps = vec![alpha, beta, gamma];
+        let pin_order = HashMap::from([("Gamma".to_string(), 0), ("Alpha".to_string(), 1)]);
+        let (frecency, empty) = (HashMap::new(), HashMap::new());
+
+        sort_by_ranking(
+            &mut apps,
+            SortOptions {
+                pinned_order_mode: PinnedOrderMode::Manual,
+                pin_order: &pin_order,
+                ..options(&frecency, &empty)
+            },
+        );
+
+        assert_eq!(names(&apps), ["Gamma", "Alpha", "Beta"]);
+    }
+
     #[test]
     fn sort_by_ranking_uses_frequency_mode_and_sets_last_access() {
         let mut apps = vec![
@@ -178,33 +316,135 @@ mod tests {
             test_app("Beta", "/usr/bin/beta"),
         ];
         let frecency = HashMap::from([
-            (
-                "Alpha".to_string(),
-                FrecencyEntry {
-                    score: 2,
-                    last_access: 100,
-                },
-            ),
-            (
-                "Beta".to_string(),
-                FrecencyEntry {
-                    score: 5,
-                    last_access: 250,
-                },
-            ),
+            ("Alpha".to_string(), entry(2, 100)),
+            ("Beta".to_string(), entry(5, 250)),
         ]);
+        let empty = HashMap::new();
 
         sort_by_ranking(
             &mut apps,
-            &frecency,
-            RankingMode::Frequency,
-            PinnedOrderMode::Ranking,
-            &HashMap::new(),
-            10_000,
+            SortOptions {
+                ranking_mode: RankingMode::Frequency,
+                ..options(&frecency, &empty)
+            },
         );
 
         assert_eq!(apps[0].name, "Beta");
         assert_eq!(apps[0].last_access, Some(250));
         assert_eq!(apps[1].last_access, Some(100));
     }
+
+    #[test]
+    fn exponential_model_ranks_smoothly_across_stepped_boundaries() {
+        const HOUR: u64 = 3600;
+        const DAY: u64 = 86400;
+        // Heavy use a day and a half ago against light use just over an hour ago.
+        let now = 2 * DAY;
+        let frecency = HashMap::from([
+            ("Heavy".to_string(), entry(6, now - DAY - DAY / 2)),
+            ("Light".to_string(), entry(2, now - HOUR - 1)),
+        ]);
+        let empty = HashMap::new();
+        let sorted = |frecency_model, now_secs| {
+            let mut apps = vec![
+                test_app("Heavy", "/usr/bin/heavy"),
+                test_app("Light", "/usr/bin/light"),
+            ];
+            sort_by_ranking(
+                &mut apps,
+                SortOptions {
+                    frecency_model,
+                    half_life_secs: DAY,
+                    now_secs,
+                    ..options(&frecency, &empty)
+                },
+            );
+            names(&apps).join(",")
+        };
+
+        // Stepped: heavy (6 / 2 = 3) loses to light (2 * 2 = 4) until light
+        // crosses the one-day step, then wins again (6 / 2 vs 2 / 2).
+        assert_eq!(sorted(FrecencyModel::Stepped, now), "Light,Heavy");
+        assert_eq!(sorted(FrecencyModel::Stepped, now + DAY), "Heavy,Light");
+        // Exponential: 6 * 2^-1.5 ≈ 2.1 vs ≈ 1.9, and both fade at the same
+        // rate, so the order never flips as time passes.
+        assert_eq!(sorted(FrecencyModel::Exponential, now), "Heavy,Light");
+        assert_eq!(sorted(FrecencyModel::Exponential, now + DAY), "Heavy,Light");
+        assert_eq!(
+            sorted(FrecencyModel::Exponential, now + 30 * DAY),
+            "Heavy,Light"
+        );
+    }
+
+    #[test]
+    fn contextual_mode_boosts_apps_used_at_this_time_of_week() {
+        const DAY: u64 = 86400;
+        let now = 30 * DAY;
+        let frecency = HashMap::from([
+            ("Mail".to_string(), entry(4, now - DAY)),
+            ("Game".to_string(), entry(5, now - DAY)),
+        ]);
+        let mut mail_hours = LaunchHours::default();
+        let mut game_hours = LaunchHours::default();
+        for week in 1..=4 {
+            mail_hours.record(now - week * 7 * DAY);
+            game_hours.record(now - week * 7 * DAY + DAY / 2);
+        }
+        let launch_hours = HashMap::from([
+            ("Mail".to_string(), mail_hours),
+            ("Game".to_string(), game_hours),
+        ]);
+        let empty = HashMap::new();
+        let sorted = |ranking_mode| {
+            let mut apps = vec![
+                test_app("Game", "/usr/bin/game"),
+                test_app("Mail", "/usr/bin/mail"),
+            ];
+            sort_by_ranking(
+                &mut apps,
+                SortOptions {
+                    ranking_mode,
+                    launch_hours: &launch_hours,
+                    now_secs: now,
+                    ..options(&frecency, &empty)
+                },
+            );
+            names(&apps).join(",")
+        };
+
+        assert_eq!(sorted(RankingMode::Frecency), "Game,Mail");
+        assert_eq!(sorted(RankingMode::Contextual), "Mail,Game");
+    }
+
+    #[test]
+    fn entries_sharing_a_name_rank_by_their_own_usage() {
+        let mut native = test_app("Firefox", "/usr/bin/firefox");
+        native.desktop_id = Some("firefox.desktop".to_string());
+        native.set_source_path(std::path::Path::new(
+            "/usr/share/applications/firefox.desktop",
+        ));
+        let mut flatpak = test_app("Firefox", "/usr/bin/flatpak run org.mozilla.firefox");
+        flatpak.desktop_id = Some("org.mozilla.firefox.desktop".to_string());
+        flatpak.set_source_path(std::path::Path::new(
+            "/var/lib/flatpak/exports/share/applications/org.mozilla.firefox.desktop",
+        ));
+        let frecency = HashMap::from([(flatpak.store_key(), entry(3, 9_000))]);
+        let empty = HashMap::new();
+
+        let mut apps = vec![native, flatpak];
+        sort_by_ranking(
+            &mut apps,
+            SortOptions {
+                ranking_mode: RankingMode::Frequency,
+                ..options(&frecency, &empty)
+            },
+        );
+
+        assert_eq!(
+            apps[0].desktop_id.as_deref(),
+            Some("org.mozilla.firefox.desktop")
+        );
+        assert_eq!(apps[0].last_access, Some(9_000));
+        assert_eq!(apps[1].last_access, None);
+    }
 }
@@ -0,0 +1,246 @@
+use super::State;
+use crate::desktop::{App, EntryKind};
+
+/// Desktop actions of one entry, browsed in place of the full app list.
+#[derive(Debug)]
+pub(super) struct ActionView {
+    parent_name: String,
+    actions: Vec<App>,
+    saved_query: String,
+    saved_selected: Option<usize>,
+    saved_scroll_offset: usize,
+}
+
+impl State {
+    /// Replace the list with `actions` of `parent_name`, remembering where the
+    /// user was so [`State::leave_actions`] can restore it.
+    ///
+    /// Returns `false` and leaves the state untouched when there is nothing to show.
+    pub(crate) fn enter_actions(&mut self, parent_name: &str, mut actions: Vec<App>) -> bool {
+        if actions.is_empty() || self.action_view.is_some() {
+            return false;
+        }
+
+        crate::core::ranking::sort_by_ranking(
+            &mut actions,
+            self.sort_options(crate::core::ranking::current_unix_seconds()),
+        );
+        self.action_view = Some(ActionView {
+            parent_name: parent_name.to_string(),
+            actions,
+            saved_query: std::mem::take(&mut self.query),
+            saved_selected: self.selected,
+            saved_scroll_offset: self.scroll_offset,
+        });
+        self.refresh_visibility();
+        true
+    }
+
+    /// Browse the entries a `Type=Directory` entry groups: those sharing one of
+    /// its categories, or filed under its name when it lists none.
+    pub(crate) fn enter_directory(&mut self, directory: &App) -> bool {
+        let categories: Vec<&str> = if directory.categories.is_empty() {
+            vec![directory.name.as_str()]
+        } else {
+            directory.categories.iter().map(String::as_str).collect()
+        };
+        let members = self
+            .apps
+            .iter()
+            .filter(|app| app.kind() != EntryKind::Directory)
+            .filter(|app| {
+                app.categories
+                    .iter()
+                    .any(|category| categories.contains(&category.as_str()))
+            })
+            .cloned()
+            .collect();
+        self.enter_actions(&directory.name, members)
+    }
+
+    /// Return to the full app list with the query and selection from before
+    /// [`State::enter_actions`]. Returns `false` when no actions were open.
+    pub(crate) fn leave_actions(&mut self) -> bool {
+        let Some(view) = self.action_view.take() else {
+            return false;
+        };
+
+        self.query = view.saved_query;
+        self.refresh_visibility();
+        if let Some(selected) = view.saved_selected
+            && selected < self.shown.len()
+        {
+            self.selected = Some(selected);
+            self.scroll_offset = view.saved_scroll_offset.min(selected);
+        }
+        true
+    }
+
+    /// Name of the entry whose actions are being browsed, if any.
+    pub(crate) fn action_parent(&self) -> Option<&str> {
+        self.action_view
+            .as_ref()
+            .map(|view| view.parent_name.as_str())
+    }
+
+    /// Mutable access to every loaded entry, including open actions.
+    pub(crate) fn all_apps_mut(&mut self) -> impl Iterator<Item = &mut App> {
+        let actions = self
+            .action_view
+            .iter_mut()
+            .flat_map(|view| view.actions.iter_mut());
+        self.apps.iter_mut().chain(actions)
+    }
+
+    /// Re-rank the open actions after a pin or history change.
+    pub(crate) fn resort_actions(&mut self, now_secs: u64) {
+        if let Some(mut view) = self.action_view.take() {
+            crate::core::ranking::sort_by_ranking(&mut view.actions, self.sort_options(now_secs));
+            self.action_view = Some(view);
+        }
+    }
+
+    pub(super) fn visible_source(&self) -> &[App] {
+        match &self.action_view {
+            Some(view) => &view.actions,
+            None => &self.apps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+    use crate::core::ranking::FrecencyEntry;
+    use crate::core::state::State;
+    use crate::desktop::App;
+    use std::collections::HashMap;
+
+    fn app(name: &str) -> App {
+        App::parse(
+            format!("[Desktop Entry]\nType=Application\nName={name}\nExec={name}"),
+            false,
+        )
+        .expect("test desktop entry should parse")
+    }
+
+    fn state() -> State {
+        State::new(
+            vec![app("Alpha"), app("Firefox"), app("Zed")],
+            MatchMode::Fuzzy,
+            HashMap::new(),
+            3,
+            RankingMode::Frecency,
+            PinnedOrderMode::Ranking,
+            HashMap::new(),
+        )
+    }
+
+    fn shown_names(state: &State) -> Vec<&str> {
+        state.shown.iter().map(|app| app.name.as_str()).collect()
+    }
+
+    #[test]
+    fn entering_actions_replaces_list_and_clears_query() {
+        let mut state = state();
+        state.query = "fire".to_string();
+        state.filter();
+
+        let entered = state.enter_actions(
+            "Firefox",
+            vec![app("Firefox (New Window)"), app("Firefox (Private Window)")],
+        );
+
+        assert!(entered);
+        assert_eq!(state.action_parent(), Some("Firefox"));
+        assert!(state.query.is_empty());
+        assert_eq!(
+            shown_names(&state),
+            ["Firefox (New Window)", "Firefox (Private Window)"]
+        );
+    }
+
+    #[test]
+    fn frequently_used_actions_float_up() {
+        let mut state = state();
+        state.frecency_data.insert(
+            "Firefox (Private Window)".to_string(),
+            FrecencyEntry {
+                score: 5,
+                last_access: crate::core::ranking::current_unix_seconds(),
+                decayed: 5.0,
+            },
+        );
+
+        state.enter_actions(
+            "Firefox",
+            vec![app("Firefox (New Window)"), app("Firefox (Private Window)")],
+        );
+
+        assert_eq!(
+            shown_names(&state),
+            ["Firefox (Private Window)", "Firefox (New Window)"]
+        );
+    }
+
+    #[test]
+    fn leaving_actions_restores_query_and_selection() {
+        let mut state = state();
+        state.selected = Some(2);
+        state.enter_actions("Zed", vec![app("Zed (New Window)")]);
+
+        assert!(state.leave_actions());
+
+        assert_eq!(state.action_parent(), None);
+        assert_eq!(shown_names(&state), ["Alpha", "Firefox", "Zed"]);
+        assert_eq!(state.selected, Some(2));
+        assert!(!state.leave_actions());
+    }
+
+    #[test]
+    fn directories_group_entries_by_category() {
+        let with_categories = |name: &str, categories: &str| {
+            App::parse(
+                format!(
+                    "[Desktop Entry]\nType=Application\nName={name}\nExec={name}\n\
+                     Categories={categories}"
+                ),
+                false,
+            )
+            .expect("test desktop entry should parse")
+        };
+        let directory = App::parse(
+            "[Desktop Entry]\nType=Directory\nName=Development\nCategories=Development;IDE;",
+            false,
+        )
+        .expect("directory entry should parse");
+        let mut state = State::new(
+            vec![
+                with_categories("Zed", "Development;TextEditor;"),
+                with_categories("Firefox", "Network;WebBrowser;"),
+                with_categories("Builder", "GNOME;IDE;"),
+                directory.clone(),
+            ],
+            MatchMode::Fuzzy,
+            HashMap::new(),
+            3,
+            RankingMode::Frecency,
+            PinnedOrderMode::Ranking,
+            HashMap::new(),
+        );
+
+        assert!(state.enter_directory(&directory));
+
+        assert_eq!(state.action_parent(), Some("Development"));
+        assert_eq!(shown_names(&state), ["Builder", "Zed"]);
+    }
+
+    #[test]
+    fn entries_without_actions_keep_the_main_list() {
+        let mut state = state();
+
+        assert!(!state.enter_actions("Alpha", Vec::new()));
+        assert_eq!(state.action_parent(), None);
+        assert_eq!(shown_names(&state).len(), 3);
+    }
+}
@@ -0,0 +1,166 @@
+use super::State;
+use crate::desktop::App;
+
+/// XDG main categories, in the order the category key steps through them.
+const MAIN_CATEGORIES: &[&str] = &[
+    "AudioVideo",
+    "Audio",
+    "Video",
+    "Development",
+    "Education",
+    "Game",
+    "Graphics",
+    "Network",
+    "Office",
+    "Science",
+    "Settings",
+    "System",
+    "Utility",
+];
+
+/// Whether `app` lists `category`, ignoring ASCII case.
+pub(crate) fn in_category(app: &App, category: &str) -> bool {
+    app.categories
+        .iter()
+        .any(|candidate| candidate.eq_ignore_ascii_case(category))
+}
+
+impl State {
+    /// The category narrowing the list, if any.
+    pub(crate) fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// Show only entries in `category`, or every entry for `None`.
+    pub(crate) fn set_category(&mut self, category: Option<String>) {
+        self.category = category;
+        self.refresh_visibility();
+    }
+
+    /// Main categories that at least one loaded entry belongs to.
+    pub(crate) fn available_categories(&self) -> Vec<&'static str> {
+        MAIN_CATEGORIES
+            .iter()
+            .copied()
+            .filter(|category| self.apps.iter().any(|app| in_category(app, category)))
+            .collect()
+    }
+
+    /// Open the category strip on the active category, or close it.
+    pub(crate) fn toggle_category_picker(&mut self) {
+        self.category_picker = match self.category_picker {
+            Some(_) => None,
+            None => Some(self.category_position()),
+        };
+    }
+
+    /// Close the category strip, keeping the category it narrowed to.
+    pub(crate) fn close_category_picker(&mut self) {
+        self.category_picker = None;
+    }
+
+    /// Entries of the open category strip, `None` standing for every entry,
+    /// and the index of the highlighted one.
+    pub(crate) fn category_picker(&self) -> Option<(Vec<Option<&'static str>>, usize)> {
+        let cursor = self.category_picker?;
+        let entries = std::iter::once(None)
+            .chain(self.available_categories().into_iter().map(Some))
+            .collect();
+        Some((entries, cursor))
+    }
+
+    /// Highlight the next (or previous) strip entry and narrow the list to it.
+    pub(crate) fn move_category_picker(&mut self, forward: bool) {
+        let Some(cursor) = self.category_picker else {
+            return;
+        };
+        let available = self.available_categories();
+        let len = available.len() + 1;
+        let cursor = if forward {
+            (cursor + 1) % len
+        } else {
+            (cursor + len - 1) % len
+        };
+        self.category_picker = Some(cursor);
+        let category = cursor
+            .checked_sub(1)
+            .map(|index| available[index].to_string());
+        self.set_category(category);
+    }
+
+    /// Strip position of the active category, 0 being every entry.
+    fn category_position(&self) -> usize {
+        let Some(current) = &self.category else {
+            return 0;
+        };
+        self.available_categories()
+            .iter()
+            .position(|category| category.eq_ignore_ascii_case(current))
+            .map_or(0, |index| index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+    use crate::core::state::State;
+    use crate::desktop::App;
+    use std::collections::HashMap;
+
+    fn app(name: &str, categories: &str) -> App {
+        App::parse(
+            format!(
+                "[Desktop Entry]\nType=Application\nName={name}\nExec={name}\n\
+                 Categories={categories}"
+            ),
+            false,
+        )
+        .expect("test desktop entry should parse")
+    }
+
+    #[test]
+    fn strip_lists_present_categories_and_narrows_before_filtering() {
+        let mut state = State::new(
+            vec![
+                app("Zed", "Development;TextEditor;"),
+                app("Zenity", "GTK;Utility;"),
+                app("Gimp", "Graphics;"),
+            ],
+            MatchMode::Fuzzy,
+            HashMap::new(),
+            3,
+            RankingMode::Frecency,
+            PinnedOrderMode::Ranking,
+            HashMap::new(),
+        );
+        assert!(state.category_picker().is_none());
+
+        state.toggle_category_picker();
+        let (entries, cursor) = state.category_picker().expect("strip should be open");
+        assert_eq!(
+            entries,
+            [None, Some("Development"), Some("Graphics"), Some("Utility")]
+        );
+        assert_eq!(cursor, 0);
+
+        state.move_category_picker(true);
+        assert_eq!(state.category(), Some("Development"));
+        state.query = "z".to_string();
+        state.filter();
+        let names: Vec<&str> = state.shown.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, ["Zed"]);
+
+        state.move_category_picker(false);
+        state.move_category_picker(false);
+        assert_eq!(state.category(), Some("Utility"));
+        state.close_category_picker();
+        assert!(state.category_picker().is_none());
+        assert_eq!(state.category(), Some("Utility"));
+
+        state.toggle_category_picker();
+        assert_eq!(state.category_picker().map(|(_, cursor)| cursor), Some(3));
+        state.move_category_picker(true);
+        assert_eq!(state.category(), None);
+        assert_eq!(state.shown.len(), 2);
+    }
+}
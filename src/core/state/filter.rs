@@ -18,8 +18,15 @@ impl State {
                     frecency_data: &self.frecency_data,
                     prefix_depth: self.prefix_depth,
                     ranking_mode: self.ranking_mode,
+                    frecency_model: self.frecency_model,
+                    half_life_secs: self.half_life_secs,
                     pinned_order_mode: self.pinned_order_mode,
                     pin_timestamps: &self.pin_timestamps,
+                    pin_order: &self.pin_order,
+                    launch_hours: &self.launch_hours,
+                    query_selections: &self.query_selections,
+                    learning_weight: self.learning_weight,
+                    folding: &self.folding,
                     now_secs,
                 },
             );
@@ -40,6 +40,13 @@ impl State {
                         .push_str(&format!("Keywords: {}\n", app.keywords.join(", ")));
                 }
 
+                if self.action_parent().is_none() && !app.action_names().is_empty() {
+                    self.text.push_str(&format!(
+                        "Actions: {} (Right to browse)\n",
+                        app.action_names().len()
+                    ));
+                }
+
                 if verbose > 2 {
                     if !app.mime_types.is_empty() {
                         self.text
@@ -72,6 +79,14 @@ impl State {
             );
         }
 
+        let completions = crate::core::ranking::field_completions(&self.query, &self.eligible_apps);
+        if !completions.is_empty() {
+            append_section(
+                &mut self.text,
+                &format!("Filters: {}", completions.join("  ")),
+            );
+        }
+
         if let Some(status_message) = &self.status_message {
             append_section(&mut self.text, status_message);
         }
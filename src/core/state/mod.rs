@@ -3,15 +3,19 @@
 //! This module owns launcher state and message handling. Ranking policy lives in
 //! `crate::core::ranking`.
 
+mod actions;
+mod category;
 mod filter;
 mod info;
+mod reload;
 mod update;
 
 use crate::core::hidden_entries::{EntryKey, HiddenSummary, VisibilityOptions};
-use crate::core::ranking::FrecencyEntry;
+use crate::core::ranking::{FrecencyEntry, LaunchHours, QuerySelections, SortOptions, TextFolding};
 use crate::desktop::App;
 use std::collections::{HashMap, HashSet};
 
+pub(crate) use category::in_category;
 pub use update::update;
 
 #[derive(Debug)]
@@ -39,16 +43,34 @@ pub struct State {
     pub prefix_depth: usize,
     /// Ranking mode used for ordering and score boosts.
     pub ranking_mode: crate::cli::RankingMode,
+    /// How the frecency ranking mode weighs past accesses.
+    pub frecency_model: crate::cli::FrecencyModel,
+    /// Half-life of the exponential frecency model, in seconds.
+    pub half_life_secs: u64,
     /// Strategy for ordering pinned apps.
     pub pinned_order_mode: crate::cli::PinnedOrderMode,
-    /// First pinned timestamp by app name.
+    /// First pinned timestamp by app store key.
     pub pin_timestamps: HashMap<String, u64>,
+    /// Position in the manual pinned order by app store key.
+    pub pin_order: HashMap<String, u64>,
+    /// Launch counts by hour of the week by app store key.
+    pub launch_hours: HashMap<String, LaunchHours>,
+    /// Entries launched for earlier queries.
+    pub query_selections: QuerySelections,
+    /// Match tiers a learned query pick can climb; 0 disables learning.
+    pub learning_weight: u32,
+    /// How query and app text are folded before matching.
+    pub folding: TextFolding,
     /// Match mode used for app filtering.
     pub match_mode: crate::cli::MatchMode,
     hidden_entry_keys: HashSet<EntryKey>,
     visibility_options: VisibilityOptions,
     hidden_summary: HiddenSummary,
     status_message: Option<String>,
+    action_view: Option<actions::ActionView>,
+    category: Option<String>,
+    /// Highlighted entry of the category strip while it is open.
+    category_picker: Option<usize>,
 }
 
 impl State {
@@ -74,13 +96,23 @@ impl State {
             frecency_data,
             prefix_depth,
             ranking_mode,
+            frecency_model: crate::cli::FrecencyModel::default(),
+            half_life_secs: crate::core::ranking::DEFAULT_HALF_LIFE_SECS,
             pinned_order_mode,
             pin_timestamps,
+            pin_order: HashMap::new(),
+            launch_hours: HashMap::new(),
+            query_selections: QuerySelections::default(),
+            learning_weight: crate::core::ranking::DEFAULT_LEARNING_WEIGHT,
+            folding: TextFolding::default(),
             match_mode,
             hidden_entry_keys: HashSet::new(),
             visibility_options: VisibilityOptions::default(),
             hidden_summary: HiddenSummary::default(),
             status_message: None,
+            action_view: None,
+            category: None,
+            category_picker: None,
         };
         state.refresh_visibility();
         state
@@ -109,13 +141,67 @@ impl State {
     }
 
     pub(crate) fn unhide_entry(&mut self, entry_key: &EntryKey) {
+        self.hidden_entry_keys.remove(entry_key);
+        self.refresh_keeping_selection();
+    }
+
+    /// Ordering inputs for [`crate::core::ranking::sort_by_ranking`].
+    pub(crate) fn sort_options(&self, now_secs: u64) -> SortOptions<'_> {
+        SortOptions {
+            frecency_data: &self.frecency_data,
+            ranking_mode: self.ranking_mode,
+            frecency_model: self.frecency_model,
+            half_life_secs: self.half_life_secs,
+            pinned_order_mode: self.pinned_order_mode,
+            pin_timestamps: &self.pin_timestamps,
+            pin_order: &self.pin_order,
+            launch_hours: &self.launch_hours,
+            now_secs,
+        }
+    }
+
+    /// Re-rank every loaded entry, including open actions.
+    pub(crate) fn resort(&mut self, now_secs: u64) {
+        let mut apps = std::mem::take(&mut self.apps);
+        crate::core::ranking::sort_by_ranking(&mut apps, self.sort_options(now_secs));
+        self.apps = apps;
+        self.resort_actions(now_secs);
+    }
+
+    /// Re-rank with a new manual pinned order, keeping the selected entry selected.
+    pub(crate) fn set_pin_order(&mut self, pin_order: HashMap<String, u64>) {
+        self.pin_order = pin_order;
+        self.resort(crate::core::ranking::current_unix_seconds());
+        self.refresh_keeping_selection();
+    }
+
+    /// Store key of the pin next to `key` in the manual order, the one above it
+    /// when `up`.
+    ///
+    /// Taken from the whole order rather than `shown`, where a query or category
+    /// can put pins side by side that are not neighbours.
+    pub(crate) fn pin_neighbour(&self, key: &str, up: bool) -> Option<&str> {
+        let position = *self.pin_order.get(key)?;
+        let target = if up {
+            position.checked_sub(1)?
+        } else {
+            position + 1
+        };
+        self.pin_order
+            .iter()
+            .find(|(_, position)| **position == target)
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Like [`State::refresh_visibility`], but keeps the selected entry selected
+    /// when it is still shown, or the nearest row when it is not.
+    fn refresh_keeping_selection(&mut self) {
         let selected_key = self
             .selected
             .and_then(|index| self.shown.get(index))
             .and_then(App::entry_key);
         let selected_index = self.selected.unwrap_or(0);
         let scroll_offset = self.scroll_offset;
-        self.hidden_entry_keys.remove(entry_key);
         self.refresh_visibility();
         if !self.shown.is_empty() {
             let restored_selection = selected_key
@@ -135,11 +221,17 @@ impl State {
     }
 
     pub(crate) fn refresh_visibility(&mut self) {
-        let (eligible_apps, hidden_summary) = crate::core::hidden_entries::eligible_apps(
-            &self.apps,
+        let (mut eligible_apps, hidden_summary) = crate::core::hidden_entries::eligible_apps(
+            self.visible_source(),
             &self.hidden_entry_keys,
             &self.visibility_options,
         );
+        // Actions and directory groups are already a narrowed list.
+        if let Some(category) = &self.category
+            && self.action_parent().is_none()
+        {
+            eligible_apps.retain(|app| in_category(app, category));
+        }
         self.eligible_apps = eligible_apps;
         self.hidden_summary = hidden_summary;
         self.filter();
@@ -178,3 +270,48 @@ pub enum Message {
     /// Tick event (for animations, etc.).
     Tick,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+    use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+    use crate::desktop::App;
+    use std::collections::HashMap;
+
+    fn pinned_app(name: &str) -> App {
+        let mut app = App::parse(
+            format!("[Desktop Entry]\nType=Application\nName={name}\nExec={name}"),
+            false,
+        )
+        .expect("test desktop entry should parse");
+        app.pinned = true;
+        app
+    }
+
+    #[test]
+    fn pin_neighbours_follow_the_manual_order_while_a_query_is_active() {
+        let mut state = State::new(
+            vec![pinned_app("Foot"), pinned_app("Bar"), pinned_app("Fox")],
+            MatchMode::Fuzzy,
+            HashMap::new(),
+            3,
+            RankingMode::Frecency,
+            PinnedOrderMode::Manual,
+            HashMap::new(),
+        );
+        state.set_pin_order(HashMap::from([
+            ("Foot".to_string(), 0),
+            ("Bar".to_string(), 1),
+            ("Fox".to_string(), 2),
+        ]));
+        state.query = "fo".to_string();
+        state.filter();
+        let shown: Vec<&str> = state.shown.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(shown, ["Foot", "Fox"]);
+
+        assert_eq!(state.pin_neighbour("Fox", true), Some("Bar"));
+        assert_eq!(state.pin_neighbour("Foot", false), Some("Bar"));
+        assert_eq!(state.pin_neighbour("Foot", true), None);
+        assert_eq!(state.pin_neighbour("Fox", false), None);
+    }
+}
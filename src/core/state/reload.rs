@@ -0,0 +1,89 @@
+use super::State;
+use crate::desktop::DesktopChange;
+
+impl State {
+    /// Swap in the entries of desktop files that changed on disk.
+    ///
+    /// The query is kept and re-run, and the selected entry stays selected if it
+    /// is still listed.
+    pub(crate) fn apply_desktop_changes(&mut self, changes: Vec<DesktopChange>) {
+        if changes.is_empty() {
+            return;
+        }
+
+        for change in changes {
+            self.apps
+                .retain(|app| app.source_path() != Some(change.path.as_path()));
+            self.apps.extend(change.apps);
+        }
+        self.resort(crate::core::ranking::current_unix_seconds());
+        self.refresh_keeping_selection();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+    use crate::cli::{MatchMode, PinnedOrderMode, RankingMode};
+    use crate::desktop::{App, DesktopChange};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    fn app(name: &str) -> App {
+        let mut app = App::parse(
+            format!("[Desktop Entry]\nType=Application\nName={name}\nExec=/bin/true"),
+            false,
+        )
+        .expect("fixture should parse");
+        let file_name = format!("{}.desktop", name.to_lowercase());
+        app.set_source_path(&Path::new("/apps").join(&file_name));
+        app.desktop_id = Some(file_name);
+        app
+    }
+
+    #[test]
+    fn changed_files_replace_their_entries_and_keep_the_selection() {
+        let mut state = State::new(
+            vec![app("Editor"), app("Editorial"), app("Player")],
+            MatchMode::Fuzzy,
+            HashMap::new(),
+            3,
+            RankingMode::Frecency,
+            PinnedOrderMode::Ranking,
+            HashMap::new(),
+        );
+        state.query = "edit".to_string();
+        state.filter();
+        let selected = state
+            .shown
+            .iter()
+            .position(|app| app.name == "Editorial")
+            .expect("Editorial should match");
+        state.selected = Some(selected);
+
+        let mut renamed = app("Editor");
+        renamed.name = "Editor Nightly".to_string();
+        state.apply_desktop_changes(vec![
+            DesktopChange {
+                path: PathBuf::from("/apps/editor.desktop"),
+                apps: vec![renamed],
+            },
+            DesktopChange {
+                path: PathBuf::from("/apps/player.desktop"),
+                apps: Vec::new(),
+            },
+            DesktopChange {
+                path: PathBuf::from("/apps/editorconfig.desktop"),
+                apps: vec![app("EditorConfig")],
+            },
+        ]);
+
+        assert_eq!(state.query, "edit");
+        assert_eq!(state.apps.len(), 3);
+        assert!(state.apps.iter().all(|app| app.name != "Player"));
+        assert!(state.shown.iter().any(|app| app.name == "Editor Nightly"));
+        assert!(state.shown.iter().any(|app| app.name == "EditorConfig"));
+        let selected = state.selected.expect("an entry should stay selected");
+        assert_eq!(state.shown[selected].name, "Editorial");
+    }
+}
@@ -0,0 +1,194 @@
+//! `TryExec` checks: hiding entries whose program is not installed.
+
+use super::App;
+use crate::core::cache::ExecCache;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Which program an entry must be able to run to be shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecCheck {
+    /// Only entries with a `TryExec` key are checked, as the spec requires.
+    #[default]
+    TryExec,
+    /// Entries without `TryExec` are checked by the first word of `Exec`.
+    TryExecOrExec,
+}
+
+/// Answers whether an entry's program exists, remembering `PATH` lookups.
+///
+/// Shared between discovery workers, hence the interior mutability.
+pub(crate) struct ExecAvailability {
+    check: ExecCheck,
+    path_dirs: Vec<PathBuf>,
+    known: Mutex<HashMap<String, bool>>,
+    unsaved: AtomicBool,
+    cache: Option<ExecCache>,
+}
+
+impl ExecAvailability {
+    /// Start from the cached lookups in `db`, if it has any for the current `PATH`.
+    pub(crate) fn load(db: &std::sync::Arc<redb::Database>, check: ExecCheck) -> Self {
+        let path_dirs = path_dirs();
+        let cache = ExecCache::new(std::sync::Arc::clone(db)).ok();
+        let known = cache
+            .as_ref()
+            .and_then(|cache| cache.load(&path_dirs).ok())
+            .unwrap_or_default();
+        Self {
+            check,
+            path_dirs,
+            known: Mutex::new(known),
+            unsaved: AtomicBool::new(false),
+            cache,
+        }
+    }
+
+    /// Whether `app` may be shown; entries with nothing to check always may.
+    pub(crate) fn is_available(&self, app: &App) -> bool {
+        let program = match (&app.try_exec, self.check) {
+            (Some(try_exec), _) => Some(try_exec.clone()),
+            (None, ExecCheck::TryExecOrExec) => app.exec.argv(None).into_iter().next(),
+            (None, ExecCheck::TryExec) => None,
+        };
+        match program {
+            Some(program) if !program.is_empty() => self.program_exists(&program),
+            _ => true,
+        }
+    }
+
+    fn program_exists(&self, program: &str) -> bool {
+        // Absolute paths live outside the PATH directories whose mtimes guard
+        // the cache, so they are always checked directly.
+        if program.contains('/') {
+            return is_executable(Path::new(program));
+        }
+
+        let mut known = self.known.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some(exists) = known.get(program) {
+            return *exists;
+        }
+        let exists = find_in(&self.path_dirs, program);
+        known.insert(program.to_string(), exists);
+        self.unsaved.store(true, Ordering::Relaxed);
+        exists
+    }
+
+    /// Write new lookups back to the cache.
+    pub(crate) fn save(&self) {
+        if !self.unsaved.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let known = self.known.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some(cache) = &self.cache {
+            let _ = cache.store(&self.path_dirs, &known);
+        }
+    }
+}
+
+/// Whether `program` is an executable path, or names one on `PATH`.
+pub(crate) fn program_on_path(program: &str) -> bool {
+    if program.contains('/') {
+        return is_executable(Path::new(program));
+    }
+    find_in(&path_dirs(), program)
+}
+
+fn find_in(path_dirs: &[PathBuf], program: &str) -> bool {
+    path_dirs
+        .iter()
+        .any(|dir| is_executable(&dir.join(program)))
+}
+
+fn path_dirs() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default()
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        path.metadata()
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecAvailability, ExecCheck};
+    use crate::desktop::App;
+    use crate::testing::temp_dir;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicBool;
+
+    fn availability(check: ExecCheck, path_dirs: Vec<PathBuf>) -> ExecAvailability {
+        ExecAvailability {
+            check,
+            path_dirs,
+            known: Mutex::new(HashMap::new()),
+            unsaved: AtomicBool::new(false),
+            cache: None,
+        }
+    }
+
+    fn app(extra: &str) -> App {
+        App::parse(
+            format!("[Desktop Entry]\nType=Application\nName=Test\n{extra}"),
+            false,
+        )
+        .expect("test desktop entry should parse")
+    }
+
+    #[cfg(unix)]
+    fn install(dir: &std::path::Path, name: &str) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        fs::write(&path, "#!/bin/sh\n").expect("program should be written");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .expect("program should be made executable");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn try_exec_is_resolved_against_path() {
+        let bin = temp_dir("available-try-exec");
+        install(&bin, "present");
+        fs::write(bin.join("not-executable"), "").expect("plain file should be written");
+        let availability = availability(ExecCheck::TryExec, vec![bin.clone()]);
+
+        assert!(availability.is_available(&app("Exec=present\nTryExec=present")));
+        assert!(!availability.is_available(&app("Exec=gone\nTryExec=gone")));
+        assert!(!availability.is_available(&app("Exec=x\nTryExec=not-executable")));
+        assert!(availability.is_available(&app("Exec=gone")));
+
+        let _ = fs::remove_dir_all(bin);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_fallback_checks_the_program_word() {
+        let bin = temp_dir("available-exec-fallback");
+        install(&bin, "present");
+        let availability = availability(ExecCheck::TryExecOrExec, vec![bin.clone()]);
+        let absolute = bin.join("present");
+
+        assert!(availability.is_available(&app("Exec=present --flag %U")));
+        assert!(!availability.is_available(&app("Exec=\"gone\" %U")));
+        assert!(availability.is_available(&app(&format!("Exec={}", absolute.display()))));
+
+        let _ = fs::remove_dir_all(bin);
+    }
+}
@@ -0,0 +1,87 @@
+//! `[[entries]]` from the config, turned into launcher entries.
+
+use super::App;
+use super::exec::{ExecCommand, ExecFields};
+use crate::config::EntryConfig;
+use eyre::eyre;
+
+impl App {
+    /// Build the entry a `[[entries]]` table declares.
+    ///
+    /// `exec` uses desktop-entry quoting, so field codes such as `%c` work and
+    /// file codes receive the targets passed after `--`.
+    pub(crate) fn from_config_entry(entry: &EntryConfig) -> eyre::Result<App> {
+        let fields = ExecFields {
+            name: &entry.name,
+            icon: entry.icon.as_deref(),
+        };
+        let exec = ExecCommand::parse(&entry.exec, fields)
+            .map_err(|error| eyre!("Invalid exec: {error}"))?;
+        if exec.argv(None).is_empty() {
+            return Err(eyre!("exec must not be empty"));
+        }
+
+        Ok(App {
+            name: entry.name.clone(),
+            command: exec.display(None),
+            exec,
+            description: entry.comment.clone().unwrap_or_default(),
+            generic_name: entry.generic_name.clone(),
+            keywords: entry.keywords.clone(),
+            categories: entry.categories.clone(),
+            mime_types: Vec::new(),
+            icon: entry.icon.clone(),
+            is_terminal: entry.terminal,
+            path: entry.path.clone(),
+            only_show_in: Vec::new(),
+            not_show_in: Vec::new(),
+            hidden: false,
+            startup_notify: false,
+            startup_wm_class: None,
+            try_exec: None,
+            entry_type: "Application".to_string(),
+            url: None,
+            desktop_id: None,
+            source_path: None,
+            from_config: true,
+            score: 0,
+            history: 0,
+            pinned: false,
+            last_access: None,
+            breakdown: None,
+            actions: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::EntryConfig;
+    use crate::core::hidden_entries::EntryKey;
+    use crate::desktop::App;
+
+    #[test]
+    fn config_entries_become_launchable_apps_with_their_own_key() {
+        let app = App::from_config_entry(&EntryConfig {
+            name: "VPN up".to_string(),
+            exec: "nmcli con up \"work vpn\"".to_string(),
+            terminal: true,
+            keywords: vec!["network".to_string()],
+            ..EntryConfig::default()
+        })
+        .expect("entry should convert");
+
+        assert_eq!(app.exec.argv(None), ["nmcli", "con", "up", "work vpn"]);
+        assert!(app.is_terminal);
+        assert_eq!(app.keywords, ["network"]);
+        assert_eq!(app.entry_key(), Some(EntryKey::config("VPN up")));
+        assert!(
+            App::from_config_entry(&EntryConfig {
+                name: "Broken".to_string(),
+                exec: "sh -c \"echo".to_string(),
+                ..EntryConfig::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -15,23 +15,88 @@ pub(crate) fn application_dirs() -> Vec<PathBuf> {
     )
 }
 
+/// Returns the XDG icon base directories in lookup order, existing or not.
+///
+/// Follows the Icon Theme spec: `$HOME/.icons`, then `icons` under every data
+/// directory, then `/usr/share/pixmaps`.
+pub(crate) fn icon_base_dirs() -> Vec<PathBuf> {
+    let xdg_data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let home_dir = directories::UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    let xdg_data_dirs = std::env::var("XDG_DATA_DIRS").ok();
+
+    let mut dirs: Vec<PathBuf> = home_dir.iter().map(|home| home.join(".icons")).collect();
+    for data_dir in data_dirs_from_sources(
+        xdg_data_home.as_deref(),
+        home_dir.as_deref(),
+        xdg_data_dirs.as_deref(),
+    ) {
+        let icons_dir = data_dir.join("icons");
+        if !dirs.contains(&icons_dir) {
+            dirs.push(icons_dir);
+        }
+    }
+    dirs.push(PathBuf::from("/usr/share/pixmaps"));
+    dirs
+}
+
+/// Returns the shared-mime-info `mime` directories, most important first,
+/// existing or not.
+pub(crate) fn mime_dirs() -> Vec<PathBuf> {
+    let xdg_data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let home_dir = directories::UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    let xdg_data_dirs = std::env::var("XDG_DATA_DIRS").ok();
+
+    let mut dirs = Vec::new();
+    for data_dir in data_dirs_from_sources(
+        xdg_data_home.as_deref(),
+        home_dir.as_deref(),
+        xdg_data_dirs.as_deref(),
+    ) {
+        let mime_dir = data_dir.join("mime");
+        if !dirs.contains(&mime_dir) {
+            dirs.push(mime_dir);
+        }
+    }
+    dirs
+}
+
 fn application_dirs_from_sources(
     xdg_data_home: Option<&Path>,
     home_dir: Option<&Path>,
     xdg_data_dirs: Option<&str>,
 ) -> Vec<PathBuf> {
     let mut dirs = Vec::new();
+    for data_dir in data_dirs_from_sources(xdg_data_home, home_dir, xdg_data_dirs) {
+        push_applications_dir(&mut dirs, data_dir);
+    }
+    dirs
+}
+
+/// `$XDG_DATA_HOME` followed by `$XDG_DATA_DIRS`, with the spec defaults for both.
+fn data_dirs_from_sources(
+    xdg_data_home: Option<&Path>,
+    home_dir: Option<&Path>,
+    xdg_data_dirs: Option<&str>,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
 
     if let Some(xdg_data_home) = xdg_data_home {
-        push_applications_dir(&mut dirs, xdg_data_home);
+        dirs.push(xdg_data_home.to_path_buf());
     } else if let Some(home_dir) = home_dir {
-        push_applications_dir(&mut dirs, home_dir.join(".local/share"));
+        dirs.push(home_dir.join(".local/share"));
     }
 
     if let Some(xdg_data_dirs) = xdg_data_dirs {
-        for data_dir in xdg_data_dirs.split(':').filter(|entry| !entry.is_empty()) {
-            push_applications_dir(&mut dirs, PathBuf::from(data_dir));
-        }
+        dirs.extend(
+            xdg_data_dirs
+                .split(':')
+                .filter(|entry| !entry.is_empty())
+                .map(PathBuf::from),
+        );
     } else {
         #[cfg(not(target_os = "openbsd"))]
         let default_paths = vec![
@@ -46,9 +111,7 @@ fn application_dirs_from_sources(
             PathBuf::from("/usr/X11R6/share"),
         ];
 
-        for default_path in default_paths {
-            push_applications_dir(&mut dirs, default_path);
-        }
+        dirs.extend(default_paths);
     }
 
     dirs
@@ -1,4 +1,4 @@
-use super::{Action, App};
+use super::{Action, App, ExecAvailability, ExecCheck, ExecCommand};
 use crate::core::cache::HistoryCache;
 use jwalk::WalkDir;
 use rayon::prelude::*;
@@ -20,9 +20,11 @@ pub struct DiscoverOptions {
     pub list_executables: bool,
     /// Include `Hidden=true` tombstones for automatic duplicate resolution.
     pub auto_hide_duplicates: bool,
+    /// Which program must be installed for an entry to be listed.
+    pub exec_check: ExecCheck,
 }
 
-fn current_desktop(filter_desktop: bool) -> Option<Vec<String>> {
+pub(crate) fn current_desktop(filter_desktop: bool) -> Option<Vec<String>> {
     if !filter_desktop {
         return None;
     }
@@ -32,7 +34,7 @@ fn current_desktop(filter_desktop: bool) -> Option<Vec<String>> {
         .map(|desktop| desktop.split(':').map(|part| part.to_string()).collect())
 }
 
-fn walk_desktop_files(dirs: &[PathBuf]) -> Vec<PathBuf> {
+pub(crate) fn walk_desktop_files(dirs: &[PathBuf]) -> Vec<PathBuf> {
     let mut desktop_files = Vec::new();
     for dir in dirs {
         for entry in WalkDir::new(dir)
@@ -156,11 +158,15 @@ fn should_keep_for_desktop(app: &App, current_desktop: Option<&[String]>) -> boo
     })
 }
 
-fn executable_app(path: &Path, file_name: &str) -> App {
+fn executable_app(path: &Path, file_name: &str, summary: Option<&String>) -> App {
     App {
         name: file_name.to_string(),
         command: path.to_string_lossy().to_string(),
-        description: format!("Executable: {file_name}"),
+        exec: ExecCommand::program(path.to_string_lossy()),
+        description: match summary {
+            Some(summary) => summary.clone(),
+            None => format!("Executable: {file_name}"),
+        },
         generic_name: None,
         keywords: vec![],
         categories: vec!["Executable".to_string()],
@@ -175,8 +181,10 @@ fn executable_app(path: &Path, file_name: &str) -> App {
         startup_wm_class: None,
         try_exec: None,
         entry_type: "Application".to_string(),
+        url: None,
         desktop_id: None,
         source_path: Some(path.to_path_buf()),
+        from_config: false,
         history: 0,
         score: 0,
         pinned: false,
@@ -186,9 +194,14 @@ fn executable_app(path: &Path, file_name: &str) -> App {
     }
 }
 
-fn send_path_executables(sender: &mpsc::Sender<App>, history_cache: &HistoryCache) -> Option<()> {
+fn send_path_executables(
+    sender: &mpsc::Sender<App>,
+    history_cache: &HistoryCache,
+    db: &std::sync::Arc<redb::Database>,
+) -> Option<()> {
     let path_var = env::var("PATH").ok()?;
     let mut seen_executables = HashSet::new();
+    let mut executables = Vec::new();
 
     for path_dir in path_var.split(':') {
         let Ok(entries) = fs::read_dir(path_dir) else {
@@ -210,22 +223,130 @@ fn send_path_executables(sender: &mpsc::Sender<App>, history_cache: &HistoryCach
                         && let Some(file_name) = path.file_name().and_then(|name| name.to_str())
                         && seen_executables.insert(file_name.to_string())
                     {
-                        let app = executable_app(&path, file_name);
-                        if sender.send(history_cache.apply_to_app(app)).is_err() {
-                            return None;
-                        }
+                        let file_name = file_name.to_string();
+                        executables.push((path, file_name));
                     }
                 }
             }
         }
     }
 
+    let paths: Vec<PathBuf> = executables.iter().map(|(path, _)| path.clone()).collect();
+    let summaries = super::whatis::summaries(db, &paths);
+    for (path, file_name) in executables {
+        let app = executable_app(&path, &file_name, summaries.get(&path));
+        if sender.send(history_cache.apply_to_app(app)).is_err() {
+            return None;
+        }
+    }
+
     Some(())
 }
 
-/// Finds XDG applications in `dirs` and streams them back over a channel.
+/// Parses every `[Desktop Action …]` section `app` declares from `contents`.
+///
+/// The returned entries carry only the action name in `desktop_id`; callers turn
+/// it into the `{parent}#{action}` form.
+fn action_apps(app: &App, contents: &str, filter_desktop: bool) -> Vec<App> {
+    let Some(actions) = &app.actions else {
+        return Vec::new();
+    };
+
+    actions
+        .iter()
+        .filter_map(|name| {
+            let action = Action::default().name(name).from(app.name.clone());
+            let mut action_app = App::parse_action(contents, &action, filter_desktop).ok()?;
+            action_app.desktop_id = Some(action.name);
+            Some(action_app)
+        })
+        .collect()
+}
+
+/// Loads the desktop actions of an already discovered `app` from its source file.
+pub(crate) fn load_actions(app: &App, filter_desktop: bool) -> Vec<App> {
+    let Some(source_path) = app.source_path() else {
+        return Vec::new();
+    };
+    let Ok(contents) = fs::read_to_string(source_path) else {
+        return Vec::new();
+    };
+
+    let mut actions = action_apps(app, &contents, filter_desktop);
+    for action_app in &mut actions {
+        action_app.set_source_path(source_path);
+        action_app.desktop_id = match (&app.desktop_id, action_app.desktop_id.take()) {
+            (Some(parent_id), Some(name)) => Some(format!("{parent_id}#{name}")),
+            _ => None,
+        };
+    }
+    actions
+}
+
+/// What one desktop file contributes to the launcher.
+pub(crate) struct FileEntries {
+    /// Listed entries: the file's desktop actions followed by the app itself.
+    pub(crate) apps: Vec<App>,
+    /// The freshly parsed app, to be written back to the desktop cache.
+    pub(crate) parsed: Option<App>,
+}
+
+/// Everything needed to turn a desktop file into launcher entries.
+pub(crate) struct FileLoader<'a> {
+    pub(crate) dirs: &'a [PathBuf],
+    pub(crate) desktop_cache: Option<&'a crate::core::cache::DesktopCache>,
+    pub(crate) history_cache: &'a HistoryCache,
+    pub(crate) availability: &'a ExecAvailability,
+    pub(crate) current_desktop: Option<&'a [String]>,
+    pub(crate) options: DiscoverOptions,
+}
+
+impl FileLoader<'_> {
+    /// Entries for `file_path`, or `None` when it is not a usable desktop file.
+    pub(crate) fn load(&self, file_path: &Path) -> Option<FileEntries> {
+        let (mut app, file_contents) = load_app_from_path(
+            file_path,
+            self.desktop_cache,
+            self.options.filter_desktop,
+            self.options.auto_hide_duplicates,
+        )?;
+        attach_desktop_id(&mut app, self.dirs, file_path, None);
+
+        if !should_keep_for_desktop(&app, self.current_desktop) {
+            return None;
+        }
+        let parsed = file_contents.as_ref().map(|_| app.clone());
+        // Keep caching the parsed entry so reinstalling its program only
+        // costs a PATH lookup.
+        if !app.hidden && !self.availability.is_available(&app) {
+            return Some(FileEntries {
+                apps: Vec::new(),
+                parsed,
+            });
+        }
+
+        let mut apps = Vec::new();
+        if !app.hidden && !self.options.filter_actions && app.actions.is_some() {
+            let contents = file_contents.or_else(|| fs::read_to_string(file_path).ok());
+            if let Some(contents) = contents {
+                for mut action_app in action_apps(&app, &contents, self.options.filter_desktop) {
+                    let suffix = action_app.desktop_id.take();
+                    attach_desktop_id(&mut action_app, self.dirs, file_path, suffix.as_deref());
+                    apps.push(self.history_cache.apply_to_app(action_app));
+                }
+            }
+        }
+        apps.push(self.history_cache.apply_to_app(app));
+
+        Some(FileEntries { apps, parsed })
+    }
+}
+
+/// Finds XDG applications in `dirs` and streams them back over a channel,
+/// followed by the `[[entries]]` declared in the config.
 pub fn read_with_options(
     dirs: Vec<impl Into<PathBuf>>,
+    config_entries: Vec<App>,
     db: &std::sync::Arc<redb::Database>,
     options: DiscoverOptions,
 ) -> mpsc::Receiver<App> {
@@ -235,71 +356,29 @@ pub fn read_with_options(
     let current_desktop = current_desktop(options.filter_desktop);
 
     let _worker = thread::spawn(move || {
-        let history_cache = HistoryCache::load(&db_clone).unwrap_or_else(|_| HistoryCache {
-            history: std::collections::HashMap::new(),
-            pinned: std::collections::HashSet::new(),
-        });
+        let history_cache = load_history_cache(&db_clone);
         let desktop_cache = crate::core::cache::DesktopCache::new(db_clone.clone()).ok();
+        let availability = ExecAvailability::load(&db_clone, options.exec_check);
         let desktop_files = load_desktop_files(&dirs, desktop_cache.as_ref());
-        let history_cache_ref = &history_cache;
-        let desktop_cache_ref = desktop_cache.as_ref();
-        let current_desktop_ref = current_desktop.as_deref();
+        let loader = FileLoader {
+            dirs: &dirs,
+            desktop_cache: desktop_cache.as_ref(),
+            history_cache: &history_cache,
+            availability: &availability,
+            current_desktop: current_desktop.as_deref(),
+            options,
+        };
 
         let apps_to_cache: Vec<(PathBuf, App)> = desktop_files
             .into_par_iter()
             .filter_map(|file_path| {
-                let file_path_ref = file_path.as_path();
-                let (mut app, file_contents) = load_app_from_path(
-                    file_path_ref,
-                    desktop_cache_ref,
-                    options.filter_desktop,
-                    options.auto_hide_duplicates,
-                )?;
-                attach_desktop_id(&mut app, &dirs, file_path_ref, None);
-
-                if !should_keep_for_desktop(&app, current_desktop_ref) {
-                    return None;
-                }
-
-                let app_with_history = history_cache_ref.apply_to_app(app.clone());
-
-                if !app.hidden
-                    && !options.filter_actions
-                    && let Some(actions) = &app.actions
-                {
-                    let contents = match &file_contents {
-                        Some(contents) => Some(contents.clone()),
-                        None => fs::read_to_string(file_path_ref).ok(),
-                    };
-
-                    if let Some(contents) = contents {
-                        for action in actions {
-                            let action = Action::default().name(action).from(app.name.clone());
-                            if let Ok(mut action_app) =
-                                App::parse_action(&contents, &action, options.filter_desktop)
-                            {
-                                attach_desktop_id(
-                                    &mut action_app,
-                                    &dirs,
-                                    file_path_ref,
-                                    Some(action.name.as_str()),
-                                );
-                                if sender
-                                    .send(history_cache_ref.apply_to_app(action_app))
-                                    .is_err()
-                                {
-                                    return None;
-                                }
-                            }
-                        }
+                let entries = loader.load(&file_path)?;
+                for app in entries.apps {
+                    if sender.send(app).is_err() {
+                        return None;
                     }
                 }
-
-                if sender.send(app_with_history).is_err() {
-                    return None;
-                }
-
-                file_contents.map(|_| (file_path, app))
+                entries.parsed.map(|app| (file_path, app))
             })
             .collect();
 
@@ -309,17 +388,34 @@ pub fn read_with_options(
             let _ = cache.batch_set(apps_to_cache);
         }
 
+        for app in config_entries {
+            if availability.is_available(&app)
+                && sender.send(history_cache.apply_to_app(app)).is_err()
+            {
+                return;
+            }
+        }
+        availability.save();
+
         if options.list_executables {
-            let _ = send_path_executables(&sender, &history_cache);
+            let _ = send_path_executables(&sender, &history_cache, &db_clone);
         }
     });
 
     receiver
 }
 
+pub(crate) fn load_history_cache(db: &std::sync::Arc<redb::Database>) -> HistoryCache {
+    HistoryCache::load(db).unwrap_or_else(|_| HistoryCache {
+        history: std::collections::HashMap::new(),
+        pinned: std::collections::HashSet::new(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{DiscoverOptions, desktop_file_id, read_with_options};
+    use super::{DiscoverOptions, desktop_file_id, load_actions, read_with_options};
+    use crate::desktop::App;
     use std::fs;
     use std::path::PathBuf;
     use std::sync::Arc;
@@ -351,12 +447,14 @@ mod tests {
         let db = Arc::new(redb::Database::create(&db_path).expect("database should be created"));
         let receiver = read_with_options(
             vec![dir.clone()],
+            Vec::new(),
             &db,
             DiscoverOptions {
                 filter_desktop: false,
                 filter_actions,
                 list_executables: false,
                 auto_hide_duplicates: false,
+                exec_check: Default::default(),
             },
         );
         let mut names = Vec::new();
@@ -386,6 +484,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_actions_reads_sections_from_the_source_file() {
+        let dir = test_temp_dir("load-actions");
+        let desktop_path = dir.join("editor.desktop");
+        let contents = "[Desktop Entry]\nType=Application\nName=Editor\nExec=/usr/bin/editor\nActions=OpenWindow;\n\n[Desktop Action OpenWindow]\nName=Open Window\nExec=/usr/bin/editor --new-window\n";
+        fs::write(&desktop_path, contents).expect("desktop entry should be written");
+        let mut app = App::parse(contents, false).expect("desktop entry should parse");
+        app.set_source_path(&desktop_path);
+        app.desktop_id = Some("editor.desktop".to_string());
+
+        let actions = load_actions(&app, false);
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].name, "Editor (Open Window)");
+        assert_eq!(
+            actions[0].desktop_id.as_deref(),
+            Some("editor.desktop#OpenWindow")
+        );
+        assert_eq!(actions[0].source_path(), Some(desktop_path.as_path()));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
     #[test]
     fn desktop_file_id_uses_the_relative_path() {
         let root = PathBuf::from("/usr/share/applications");
@@ -436,12 +557,14 @@ mod tests {
 
         let receiver = read_with_options(
             vec![dir.clone()],
+            Vec::new(),
             &db,
             DiscoverOptions {
                 filter_desktop: false,
                 filter_actions: false,
                 list_executables: false,
                 auto_hide_duplicates: true,
+                exec_check: Default::default(),
             },
         );
         let apps = receiver.into_iter().collect::<Vec<_>>();
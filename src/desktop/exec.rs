@@ -0,0 +1,507 @@
+//! Parsing for the `Exec` key as described by the Desktop Entry specification.
+//!
+//! Quoting and escaping are resolved once at parse time. `%i` and `%c` are
+//! expanded from the entry itself, while the file, URL and location codes stay
+//! as typed placeholders because their values are only known at launch time.
+
+use super::target::LaunchTarget;
+use std::fmt;
+use std::path::Path;
+
+/// Entry fields that `%i` and `%c` expand to.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ExecFields<'a> {
+    /// Translated `Name` of the entry.
+    pub(crate) name: &'a str,
+    /// `Icon` key of the entry, if any.
+    pub(crate) icon: Option<&'a str>,
+}
+
+/// Errors produced while tokenizing an `Exec` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExecError {
+    /// A quoted argument was never closed.
+    UnterminatedQuote,
+    /// The value ended in the middle of an escape sequence.
+    TrailingBackslash,
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote => write!(f, "unterminated quoted argument"),
+            Self::TrailingBackslash => write!(f, "trailing backslash"),
+        }
+    }
+}
+
+impl std::error::Error for ExecError {}
+
+/// Field-code mistakes that [`ExecCommand::parse`] tolerates but the spec forbids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExecLint {
+    /// A `%` followed by a letter the spec does not define.
+    UnknownCode(char),
+    /// `%d`, `%D`, `%n`, `%N`, `%v`, or `%m`, which launchers ignore.
+    DeprecatedCode(char),
+    /// More than one of `%f`, `%F`, `%u`, and `%U`.
+    SeveralTargetCodes,
+    /// `%F` or `%U` sharing an argument with other text.
+    ListCodeNotAlone(char),
+}
+
+impl fmt::Display for ExecLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCode(code) => write!(f, "unknown field code `%{code}`"),
+            Self::DeprecatedCode(code) => write!(f, "deprecated field code `%{code}`"),
+            Self::SeveralTargetCodes => {
+                write!(f, "only one of `%f`, `%F`, `%u`, and `%U` may be used")
+            }
+            Self::ListCodeNotAlone(code) => {
+                write!(f, "`%{code}` must be an argument on its own")
+            }
+        }
+    }
+}
+
+/// One piece of an argument that takes a single value.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ExecPart {
+    /// Literal text.
+    Text(String),
+    /// `%f`: a single local file.
+    File,
+    /// `%u`: a single URL.
+    Url,
+    /// `%k`: the location of the desktop file.
+    Location,
+}
+
+/// One argument of a parsed `Exec` value.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ExecArg {
+    /// An argument built from literal text and single-value placeholders.
+    Parts(Vec<ExecPart>),
+    /// `%F`: any number of local files, one argument each.
+    Files,
+    /// `%U`: any number of URLs, one argument each.
+    Urls,
+}
+
+/// A tokenized `Exec` value with field codes resolved to [`ExecArg`]s.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ExecCommand {
+    args: Vec<ExecArg>,
+}
+
+impl ExecCommand {
+    /// Parse a raw `Exec` value, expanding `%i` and `%c` from `fields`.
+    pub(crate) fn parse(value: &str, fields: ExecFields<'_>) -> Result<Self, ExecError> {
+        let mut args = Vec::new();
+        for raw in tokenize(value)? {
+            raw.finish_into(&mut args, fields);
+        }
+        Ok(Self { args })
+    }
+
+    /// Check a raw `Exec` value's field codes against the spec.
+    pub(crate) fn lint(value: &str) -> Result<Vec<ExecLint>, ExecError> {
+        let mut lints = Vec::new();
+        let mut target_codes = 0;
+        for raw in tokenize(value)? {
+            for piece in &raw.pieces {
+                let RawPiece::Code(code) = *piece else {
+                    continue;
+                };
+                match code {
+                    'f' | 'u' => target_codes += 1,
+                    'F' | 'U' => {
+                        target_codes += 1;
+                        if raw.pieces.len() > 1 {
+                            lints.push(ExecLint::ListCodeNotAlone(code));
+                        }
+                    }
+                    'i' | 'c' | 'k' => {}
+                    'd' | 'D' | 'n' | 'N' | 'v' | 'm' => lints.push(ExecLint::DeprecatedCode(code)),
+                    _ => lints.push(ExecLint::UnknownCode(code)),
+                }
+            }
+        }
+        if target_codes > 1 {
+            lints.push(ExecLint::SeveralTargetCodes);
+        }
+        Ok(lints)
+    }
+
+    /// Build a command that runs `program` with no further arguments.
+    pub(crate) fn program(program: impl Into<String>) -> Self {
+        Self {
+            args: vec![ExecArg::Parts(vec![ExecPart::Text(program.into())])],
+        }
+    }
+
+    /// Resolve the command to argv with every file and URL placeholder left empty.
+    pub(crate) fn argv(&self, location: Option<&Path>) -> Vec<String> {
+        self.expand(&[], location)
+    }
+
+    /// Resolve one argv per process that launching with `targets` should start.
+    ///
+    /// `%F`/`%U` take every target in a single process and `%f`/`%u` start one
+    /// process per target. Commands without file codes ignore the targets.
+    pub(crate) fn invocations(
+        &self,
+        targets: &[LaunchTarget],
+        location: Option<&Path>,
+    ) -> Vec<Vec<String>> {
+        let takes_list = self
+            .args
+            .iter()
+            .any(|arg| matches!(arg, ExecArg::Files | ExecArg::Urls));
+        let takes_single = self.args.iter().any(|arg| match arg {
+            ExecArg::Parts(parts) => parts
+                .iter()
+                .any(|part| matches!(part, ExecPart::File | ExecPart::Url)),
+            ExecArg::Files | ExecArg::Urls => false,
+        });
+
+        if targets.is_empty() || takes_list || !takes_single {
+            return vec![self.expand(targets, location)];
+        }
+        targets
+            .iter()
+            .map(|target| self.expand(std::slice::from_ref(target), location))
+            .collect()
+    }
+
+    /// Arguments that consisted only of placeholders are dropped when they expand
+    /// to nothing, as the spec requires when no file or URL is passed.
+    fn expand(&self, targets: &[LaunchTarget], location: Option<&Path>) -> Vec<String> {
+        let location = location.map(|path| path.to_string_lossy());
+        let first = targets.first();
+        let mut argv = Vec::with_capacity(self.args.len() + targets.len());
+
+        for arg in &self.args {
+            let parts = match arg {
+                ExecArg::Files => {
+                    argv.extend(targets.iter().map(LaunchTarget::as_file_arg));
+                    continue;
+                }
+                ExecArg::Urls => {
+                    argv.extend(targets.iter().map(LaunchTarget::as_url_arg));
+                    continue;
+                }
+                ExecArg::Parts(parts) => parts,
+            };
+            let mut value = String::new();
+            let mut has_text = parts.is_empty();
+            for part in parts {
+                match part {
+                    ExecPart::Text(text) => {
+                        has_text = true;
+                        value.push_str(text);
+                    }
+                    ExecPart::Location => value.push_str(location.as_deref().unwrap_or("")),
+                    ExecPart::File => value.extend(first.map(LaunchTarget::as_file_arg)),
+                    ExecPart::Url => value.extend(first.map(LaunchTarget::as_url_arg)),
+                }
+            }
+            if has_text || !value.is_empty() {
+                argv.push(value);
+            }
+        }
+
+        argv
+    }
+
+    /// Shell-quoted form of [`Self::argv`], suitable for display or `--no-exec`.
+    pub(crate) fn display(&self, location: Option<&Path>) -> String {
+        shell_words::join(self.argv(location))
+    }
+}
+
+/// Split a raw `Exec` value into arguments, keeping field codes unresolved.
+fn tokenize(value: &str) -> Result<Vec<RawArg>, ExecError> {
+    let unescaped = unescape_string(value);
+    let mut raw_args = Vec::new();
+    let mut current = RawArg::default();
+    let mut quote: Option<char> = None;
+    let mut chars = unescaped.chars();
+
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (None, ' ' | '\t' | '\n') => raw_args.push(std::mem::take(&mut current)),
+            (None, '"' | '\'') => {
+                quote = Some(ch);
+                current.quoted = true;
+            }
+            (Some(open), _) if ch == open => quote = None,
+            (None | Some('"'), '\\') => {
+                let escaped = chars.next().ok_or(ExecError::TrailingBackslash)?;
+                // Inside double quotes only the spec's reserved characters are
+                // escapable; anything else keeps its backslash.
+                if quote.is_some() && !matches!(escaped, '"' | '`' | '$' | '\\') {
+                    current.push_char('\\');
+                }
+                current.push_char(escaped);
+            }
+            (_, '%') => match chars.next() {
+                Some(code) => current.push_code(code),
+                None => current.push_char('%'),
+            },
+            _ => current.push_char(ch),
+        }
+    }
+
+    if quote.is_some() {
+        return Err(ExecError::UnterminatedQuote);
+    }
+    raw_args.push(current);
+    Ok(raw_args)
+}
+
+/// Apply the general string escapes (`\s`, `\n`, `\t`, `\r`, `\\`).
+///
+/// Unknown sequences are kept verbatim so that the quoting layer can still see
+/// escapes such as `\"`.
+fn unescape_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            _ => {
+                out.push('\\');
+                continue;
+            }
+        }
+        chars.next();
+    }
+
+    out
+}
+
+#[derive(Debug)]
+enum RawPiece {
+    Text(String),
+    Code(char),
+}
+
+#[derive(Debug, Default)]
+struct RawArg {
+    pieces: Vec<RawPiece>,
+    quoted: bool,
+}
+
+impl RawArg {
+    fn push_char(&mut self, ch: char) {
+        match self.pieces.last_mut() {
+            Some(RawPiece::Text(text)) => text.push(ch),
+            _ => self.pieces.push(RawPiece::Text(ch.to_string())),
+        }
+    }
+
+    fn push_code(&mut self, code: char) {
+        if code == '%' {
+            self.push_char('%');
+        } else {
+            self.pieces.push(RawPiece::Code(code));
+        }
+    }
+
+    fn finish_into(self, args: &mut Vec<ExecArg>, fields: ExecFields<'_>) {
+        match self.pieces.as_slice() {
+            [] if !self.quoted => return,
+            [RawPiece::Code('F')] => return args.push(ExecArg::Files),
+            [RawPiece::Code('U')] => return args.push(ExecArg::Urls),
+            [RawPiece::Code('i')] => {
+                if let Some(icon) = fields.icon.filter(|icon| !icon.is_empty()) {
+                    args.push(text_arg("--icon"));
+                    args.push(text_arg(icon));
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let mut parts = Vec::with_capacity(self.pieces.len());
+        for piece in self.pieces {
+            let part = match piece {
+                RawPiece::Text(text) => ExecPart::Text(text),
+                RawPiece::Code('f' | 'F') => ExecPart::File,
+                RawPiece::Code('u' | 'U') => ExecPart::Url,
+                RawPiece::Code('k') => ExecPart::Location,
+                RawPiece::Code('c') => ExecPart::Text(fields.name.to_string()),
+                RawPiece::Code('i') => ExecPart::Text(fields.icon.unwrap_or("").to_string()),
+                // Deprecated (%d, %D, %n, %N, %v, %m) and unknown codes expand to nothing.
+                RawPiece::Code(_) => continue,
+            };
+            parts.push(part);
+        }
+
+        if parts.is_empty() && !self.quoted {
+            return;
+        }
+        args.push(ExecArg::Parts(parts));
+    }
+}
+
+fn text_arg(text: &str) -> ExecArg {
+    ExecArg::Parts(vec![ExecPart::Text(text.to_string())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecArg, ExecCommand, ExecError, ExecFields, ExecPart};
+    use crate::desktop::LaunchTarget;
+    use std::path::{Path, PathBuf};
+
+    fn fields() -> ExecFields<'static> {
+        ExecFields {
+            name: "Image Viewer",
+            icon: Some("viewer"),
+        }
+    }
+
+    fn parse(value: &str) -> ExecCommand {
+        ExecCommand::parse(value, fields()).expect("exec value should parse")
+    }
+
+    #[test]
+    fn quoted_arguments_keep_embedded_file_codes() {
+        let command = parse(r#"sh -c "foo %f""#);
+
+        assert_eq!(
+            command.args[2],
+            ExecArg::Parts(vec![ExecPart::Text("foo ".to_string()), ExecPart::File])
+        );
+        assert_eq!(command.argv(None), ["sh", "-c", "foo "]);
+    }
+
+    #[test]
+    fn list_codes_become_standalone_placeholders() {
+        let command = parse("/usr/bin/editor %F --new %U");
+
+        assert_eq!(command.args[1], ExecArg::Files);
+        assert_eq!(command.args[3], ExecArg::Urls);
+        assert_eq!(command.argv(None), ["/usr/bin/editor", "--new"]);
+    }
+
+    #[test]
+    fn icon_name_and_location_codes_expand() {
+        let command = parse("viewer %i --title=%c %k");
+
+        assert_eq!(
+            command.argv(Some(Path::new("/usr/share/applications/viewer.desktop"))),
+            [
+                "viewer",
+                "--icon",
+                "viewer",
+                "--title=Image Viewer",
+                "/usr/share/applications/viewer.desktop",
+            ]
+        );
+    }
+
+    #[test]
+    fn icon_code_is_dropped_without_icon() {
+        let command = ExecCommand::parse(
+            "viewer %i",
+            ExecFields {
+                name: "Viewer",
+                icon: None,
+            },
+        )
+        .expect("exec value should parse");
+
+        assert_eq!(command.argv(None), ["viewer"]);
+    }
+
+    #[test]
+    fn escapes_follow_string_and_quoting_rules() {
+        // `\\\\` in the file is `\\` after string unescaping, then `\` once quoted.
+        let command = parse(r#"printf "a \"b\" \$HOME \\\\ %%""#);
+
+        assert_eq!(command.argv(None), ["printf", r#"a "b" $HOME \ %"#]);
+    }
+
+    #[test]
+    fn empty_quoted_argument_is_preserved() {
+        assert_eq!(
+            parse(r#"tool "" --flag"#).argv(None),
+            ["tool", "", "--flag"]
+        );
+    }
+
+    #[test]
+    fn deprecated_codes_are_removed() {
+        assert_eq!(parse("app %d %m --x").argv(None), ["app", "--x"]);
+    }
+
+    #[test]
+    fn unterminated_quote_is_rejected() {
+        assert_eq!(
+            ExecCommand::parse(r#"sh -c "echo"#, fields()),
+            Err(ExecError::UnterminatedQuote)
+        );
+    }
+
+    #[test]
+    fn display_quotes_arguments_for_the_shell() {
+        assert_eq!(
+            parse(r#"env "FOO=a b" app %U"#).display(None),
+            "env 'FOO=a b' app"
+        );
+    }
+
+    fn files(paths: &[&str]) -> Vec<LaunchTarget> {
+        paths
+            .iter()
+            .map(|path| LaunchTarget::File(PathBuf::from(path)))
+            .collect()
+    }
+
+    #[test]
+    fn list_codes_take_every_target_in_one_process() {
+        let invocations = parse("editor --files %F").invocations(&files(&["/a", "/b"]), None);
+
+        assert_eq!(invocations, [vec!["editor", "--files", "/a", "/b"]]);
+    }
+
+    #[test]
+    fn single_codes_start_one_process_per_target() {
+        let invocations = parse(r#"sh -c "view %f""#).invocations(&files(&["/a", "/b"]), None);
+
+        assert_eq!(
+            invocations,
+            [vec!["sh", "-c", "view /a"], vec!["sh", "-c", "view /b"]]
+        );
+    }
+
+    #[test]
+    fn url_codes_pass_urls_through() {
+        let targets = [LaunchTarget::Url("https://example.com".to_string())];
+        let invocations = parse("browser %u").invocations(&targets, None);
+
+        assert_eq!(invocations, [vec!["browser", "https://example.com"]]);
+    }
+
+    #[test]
+    fn commands_without_file_codes_ignore_targets() {
+        let invocations = parse("settings --panel").invocations(&files(&["/a", "/b"]), None);
+
+        assert_eq!(invocations, [vec!["settings", "--panel"]]);
+    }
+}
@@ -0,0 +1,297 @@
+//! Resolving `Icon` values to image files through XDG icon themes.
+
+mod theme;
+
+use super::dirs::icon_base_dirs;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use theme::IconTheme;
+
+const FALLBACK_THEME: &str = "hicolor";
+
+/// Icon lookups for one theme, size, and scale.
+///
+/// Parsed themes are kept for the lifetime of the lookup; results are not, so
+/// callers can cache them however suits them.
+#[derive(Debug)]
+pub(crate) struct IconLookup {
+    theme: String,
+    size: u32,
+    scale: u32,
+    base_dirs: Vec<PathBuf>,
+    themes: HashMap<String, Option<IconTheme>>,
+}
+
+impl IconLookup {
+    /// Look up icons in `theme`, or the desktop's configured theme when `None`.
+    pub(crate) fn new(theme: Option<&str>, size: u32, scale: u32) -> Self {
+        let theme = theme
+            .map(str::to_string)
+            .or_else(configured_theme)
+            .unwrap_or_else(|| FALLBACK_THEME.to_string());
+        Self::with_base_dirs(theme, size, scale, icon_base_dirs())
+    }
+
+    fn with_base_dirs(theme: String, size: u32, scale: u32, base_dirs: Vec<PathBuf>) -> Self {
+        Self {
+            theme,
+            size: size.max(1),
+            scale: scale.max(1),
+            base_dirs,
+            themes: HashMap::new(),
+        }
+    }
+
+    /// Identifies the inputs that decide a result; lookups with equal keys agree.
+    pub(crate) fn cache_key(&self) -> String {
+        format!("{}:{}@{}", self.theme, self.size, self.scale)
+    }
+
+    /// Directories whose modification marks cached results as stale.
+    ///
+    /// Besides the base directories and theme roots this lists each theme's
+    /// size subdirectories, since installing an icon only touches its leaf
+    /// directory. Missing ones are included so that creating them counts too.
+    pub(crate) fn watched_dirs(&mut self) -> Vec<PathBuf> {
+        let mut dirs = self.base_dirs.clone();
+        for name in self.theme_chain() {
+            if let Some(Some(theme)) = self.themes.get(&name) {
+                dirs.extend(theme.watched_dirs());
+            }
+        }
+        dirs
+    }
+
+    /// Resolve an `Icon` value to a PNG file.
+    ///
+    /// Absolute paths are used as-is; names are searched in the theme, its
+    /// parents, `hicolor`, and finally directly in the base directories.
+    pub(crate) fn find(&mut self, icon: &str) -> Option<PathBuf> {
+        let icon = icon.trim();
+        if icon.is_empty() {
+            return None;
+        }
+        if Path::new(icon).is_absolute() {
+            return Path::new(icon).is_file().then(|| PathBuf::from(icon));
+        }
+
+        // Plenty of entries ship `Icon=name.png` even though the spec forbids it.
+        let name = icon
+            .strip_suffix(".png")
+            .or_else(|| icon.strip_suffix(".svg"))
+            .or_else(|| icon.strip_suffix(".xpm"))
+            .unwrap_or(icon);
+
+        for theme_name in self.theme_chain() {
+            let found = self
+                .themes
+                .get(&theme_name)
+                .and_then(Option::as_ref)
+                .and_then(|theme| theme.lookup(name, self.size, self.scale));
+            if found.is_some() {
+                return found;
+            }
+        }
+        theme::lookup_unthemed(name, &self.base_dirs)
+    }
+
+    /// The selected theme and its ancestors depth-first, then `hicolor`.
+    fn theme_chain(&mut self) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut pending = vec![self.theme.clone()];
+        while let Some(name) = pending.pop() {
+            if chain.contains(&name) {
+                continue;
+            }
+            let parents = self.theme(&name).map(|theme| theme.parents.clone());
+            chain.push(name);
+            if let Some(parents) = parents {
+                pending.extend(parents.into_iter().rev());
+            }
+        }
+        if !chain.iter().any(|name| name == FALLBACK_THEME) {
+            self.theme(FALLBACK_THEME);
+            chain.push(FALLBACK_THEME.to_string());
+        }
+        chain
+    }
+
+    fn theme(&mut self, name: &str) -> Option<&IconTheme> {
+        let base_dirs = &self.base_dirs;
+        self.themes
+            .entry(name.to_string())
+            .or_insert_with(|| IconTheme::load(name, base_dirs))
+            .as_ref()
+    }
+}
+
+/// The icon theme GTK is configured with, which most desktops keep in sync.
+fn configured_theme() -> Option<String> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.config_dir().to_path_buf()))?;
+
+    ["gtk-4.0", "gtk-3.0"].iter().find_map(|dir| {
+        let settings = fs::read_to_string(config_home.join(dir).join("settings.ini")).ok()?;
+        settings.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim().trim_matches('"');
+            (key.trim() == "gtk-icon-theme-name" && !value.is_empty()).then(|| value.to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IconLookup;
+    use crate::core::cache::IconCache;
+    use crate::testing::temp_dir;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().expect("path should have a parent"))
+            .expect("parent directory should be created");
+        fs::write(path, contents).expect("test file should be written");
+    }
+
+    fn theme_index(inherits: &str, dirs: &[(&str, u32)]) -> String {
+        let names: Vec<&str> = dirs.iter().map(|(name, _)| *name).collect();
+        let mut index = format!(
+            "[Icon Theme]\nName=Test\nInherits={inherits}\nDirectories={}\n",
+            names.join(",")
+        );
+        for (name, size) in dirs {
+            index.push_str(&format!("\n[{name}]\nSize={size}\nType=Fixed\n"));
+        }
+        index
+    }
+
+    #[test]
+    fn inherited_themes_and_hicolor_are_searched_in_order() {
+        let root = temp_dir("icon-inherit");
+        let icons = root.join("icons");
+        write(
+            &icons.join("Child/index.theme"),
+            &theme_index("Parent", &[("32/apps", 32)]),
+        );
+        write(
+            &icons.join("Parent/index.theme"),
+            &theme_index("", &[("32/apps", 32)]),
+        );
+        write(
+            &icons.join("hicolor/index.theme"),
+            &theme_index("", &[("48/apps", 48)]),
+        );
+        write(&icons.join("Parent/32/apps/editor.png"), "png");
+        write(&icons.join("hicolor/48/apps/editor.png"), "png");
+        write(&icons.join("hicolor/48/apps/player.png"), "png");
+
+        let mut lookup =
+            IconLookup::with_base_dirs("Child".to_string(), 32, 1, vec![icons.clone()]);
+
+        assert_eq!(
+            lookup.find("editor"),
+            Some(icons.join("Parent/32/apps/editor.png"))
+        );
+        assert_eq!(
+            lookup.find("player.png"),
+            Some(icons.join("hicolor/48/apps/player.png"))
+        );
+        assert_eq!(lookup.find("missing"), None);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn closest_size_wins_without_an_exact_match() {
+        let root = temp_dir("icon-closest");
+        let icons = root.join("icons");
+        write(
+            &icons.join("hicolor/index.theme"),
+            &theme_index("", &[("16/apps", 16), ("64/apps", 64), ("32/apps", 32)]),
+        );
+        for size in ["16", "64", "32"] {
+            write(&icons.join(format!("hicolor/{size}/apps/term.png")), "png");
+        }
+
+        let mut lookup =
+            IconLookup::with_base_dirs("hicolor".to_string(), 40, 1, vec![icons.clone()]);
+
+        assert_eq!(
+            lookup.find("term"),
+            Some(icons.join("hicolor/32/apps/term.png"))
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn pixmaps_and_absolute_paths_are_fallbacks() {
+        let root = temp_dir("icon-fallback");
+        let pixmaps = root.join("pixmaps");
+        write(&pixmaps.join("legacy.png"), "png");
+        let absolute = root.join("custom/icon.png");
+        write(&absolute, "png");
+
+        let mut lookup =
+            IconLookup::with_base_dirs("Missing".to_string(), 32, 1, vec![pixmaps.clone()]);
+
+        assert_eq!(lookup.find("legacy"), Some(pixmaps.join("legacy.png")));
+        assert_eq!(
+            lookup.find(absolute.to_str().expect("temp path should be UTF-8")),
+            Some(absolute.clone())
+        );
+        assert_eq!(lookup.cache_key(), "Missing:32@1");
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn icons_installed_after_a_cached_miss_are_found() {
+        let root = temp_dir("icon-installed-later");
+        let icons = root.join("icons");
+        write(
+            &icons.join("hicolor/index.theme"),
+            &theme_index("", &[("48x48/apps", 48)]),
+        );
+        write(&icons.join("hicolor/48x48/apps/editor.png"), "png");
+        let db = Arc::new(
+            redb::Database::create(root.join("cache.redb")).expect("database should be created"),
+        );
+        let cache = IconCache::new(db).expect("icon cache should initialize");
+
+        let mut lookup =
+            IconLookup::with_base_dirs("hicolor".to_string(), 48, 1, vec![icons.clone()]);
+        let resolved = HashMap::from([("player".to_string(), lookup.find("player"))]);
+        assert_eq!(resolved["player"], None);
+        cache
+            .store(&lookup.cache_key(), &lookup.watched_dirs(), &resolved)
+            .expect("miss should be cached");
+        assert_eq!(
+            cache
+                .load(&lookup.cache_key(), &lookup.watched_dirs())
+                .expect("cached miss should load"),
+            resolved
+        );
+
+        // Only the leaf size directory changes when a package installs an icon.
+        write(&icons.join("hicolor/48x48/apps/player.png"), "png");
+        let mut lookup =
+            IconLookup::with_base_dirs("hicolor".to_string(), 48, 1, vec![icons.clone()]);
+        let cached = cache
+            .load(&lookup.cache_key(), &lookup.watched_dirs())
+            .expect("stale row should load");
+        assert!(!cached.contains_key("player"));
+        assert_eq!(
+            lookup.find("player"),
+            Some(icons.join("hicolor/48x48/apps/player.png"))
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
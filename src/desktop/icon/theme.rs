@@ -0,0 +1,246 @@
+//! `index.theme` parsing and the size matching rules from the Icon Theme spec.
+
+use std::fs;
+use std::path::PathBuf;
+
+/// How a theme subdirectory's icons may be scaled, from its `Type` key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SizeKind {
+    Fixed,
+    Scalable { min: u32, max: u32 },
+    Threshold(u32),
+}
+
+/// One entry of `Directories` or `ScaledDirectories`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ThemeDir {
+    path: String,
+    size: u32,
+    scale: u32,
+    kind: SizeKind,
+}
+
+impl ThemeDir {
+    fn matches(&self, size: u32, scale: u32) -> bool {
+        if self.scale != scale {
+            return false;
+        }
+        match self.kind {
+            SizeKind::Fixed => self.size == size,
+            SizeKind::Scalable { min, max } => (min..=max).contains(&size),
+            SizeKind::Threshold(threshold) => {
+                (self.size.saturating_sub(threshold)..=self.size + threshold).contains(&size)
+            }
+        }
+    }
+
+    fn distance(&self, size: u32, scale: u32) -> u32 {
+        let wanted = size * scale;
+        let (min, max) = match self.kind {
+            SizeKind::Fixed => (self.size, self.size),
+            SizeKind::Scalable { min, max } => (min, max),
+            SizeKind::Threshold(threshold) => {
+                (self.size.saturating_sub(threshold), self.size + threshold)
+            }
+        };
+        if wanted < min * self.scale {
+            min * self.scale - wanted
+        } else {
+            wanted.saturating_sub(max * self.scale)
+        }
+    }
+}
+
+/// A parsed icon theme together with the base directories that contain it.
+#[derive(Debug)]
+pub(super) struct IconTheme {
+    pub(super) parents: Vec<String>,
+    roots: Vec<PathBuf>,
+    dirs: Vec<ThemeDir>,
+}
+
+impl IconTheme {
+    /// Load `name` from the first base directory that has its `index.theme`.
+    pub(super) fn load(name: &str, base_dirs: &[PathBuf]) -> Option<Self> {
+        let roots: Vec<PathBuf> = base_dirs
+            .iter()
+            .map(|base| base.join(name))
+            .filter(|root| root.is_dir())
+            .collect();
+        let index = roots
+            .iter()
+            .find_map(|root| fs::read_to_string(root.join("index.theme")).ok())?;
+        let mut theme = Self::parse(&index);
+        theme.roots = roots;
+        Some(theme)
+    }
+
+    fn parse(index: &str) -> Self {
+        let sections = parse_sections(index);
+        let main = sections
+            .iter()
+            .find(|(name, _)| name == "Icon Theme")
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default();
+        let list = |key: &str| -> Vec<String> {
+            lookup(main, key)
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut dir_names = list("Directories");
+        dir_names.extend(list("ScaledDirectories"));
+        let dirs = dir_names
+            .into_iter()
+            .filter_map(|path| {
+                let keys = sections
+                    .iter()
+                    .find(|(name, _)| *name == path)
+                    .map(|(_, keys)| keys.as_slice())?;
+                theme_dir(path, keys)
+            })
+            .collect();
+
+        Self {
+            parents: list("Inherits"),
+            roots: Vec::new(),
+            dirs,
+        }
+    }
+
+    /// Theme roots and every listed subdirectory under each, whether or not it
+    /// exists yet, used to notice when icons are added or removed on disk.
+    pub(super) fn watched_dirs(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.roots.iter().flat_map(|root| {
+            std::iter::once(root.clone()).chain(self.dirs.iter().map(|dir| root.join(&dir.path)))
+        })
+    }
+
+    /// `LookupIcon` from the spec: an exact size match, else the closest size.
+    pub(super) fn lookup(&self, icon: &str, size: u32, scale: u32) -> Option<PathBuf> {
+        let file_name = format!("{icon}.png");
+        let candidates: Vec<(&ThemeDir, PathBuf)> = self
+            .dirs
+            .iter()
+            .flat_map(|dir| {
+                self.roots
+                    .iter()
+                    .map(move |root| (dir, root.join(&dir.path)))
+            })
+            .map(|(dir, path)| (dir, path.join(&file_name)))
+            .collect();
+
+        if let Some((_, path)) = candidates
+            .iter()
+            .find(|(dir, path)| dir.matches(size, scale) && path.is_file())
+        {
+            return Some(path.clone());
+        }
+
+        candidates
+            .into_iter()
+            .filter(|(_, path)| path.is_file())
+            .min_by_key(|(dir, _)| dir.distance(size, scale))
+            .map(|(_, path)| path)
+    }
+}
+
+fn theme_dir(path: String, keys: &[(String, String)]) -> Option<ThemeDir> {
+    let number = |key: &str| lookup(keys, key).and_then(|value| value.trim().parse().ok());
+    let size = number("Size")?;
+    let kind = match lookup(keys, "Type").map(str::trim) {
+        Some("Fixed") => SizeKind::Fixed,
+        Some("Scalable") => SizeKind::Scalable {
+            min: number("MinSize").unwrap_or(size),
+            max: number("MaxSize").unwrap_or(size),
+        },
+        _ => SizeKind::Threshold(number("Threshold").unwrap_or(2)),
+    };
+    Some(ThemeDir {
+        path,
+        size,
+        scale: number("Scale").unwrap_or(1),
+        kind,
+    })
+}
+
+type Section = (String, Vec<(String, String)>);
+
+fn parse_sections(contents: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            sections.push((name.to_string(), Vec::new()));
+        } else if let Some((key, value)) = line.split_once('=')
+            && let Some((_, keys)) = sections.last_mut()
+        {
+            keys.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    sections
+}
+
+fn lookup<'a>(keys: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    keys.iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Find `icon` directly inside a base directory, as the spec's fallback does.
+pub(super) fn lookup_unthemed(icon: &str, base_dirs: &[PathBuf]) -> Option<PathBuf> {
+    base_dirs
+        .iter()
+        .map(|base| base.join(format!("{icon}.png")))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IconTheme, SizeKind};
+
+    const INDEX: &str = "[Icon Theme]\nName=Test\nInherits=hicolor\n\
+        Directories=16x16/apps,48x48/apps,scalable/apps\nScaledDirectories=24x24@2/apps\n\n\
+        [16x16/apps]\nSize=16\nType=Fixed\n\n\
+        [48x48/apps]\nSize=48\n\n\
+        [scalable/apps]\nSize=64\nType=Scalable\nMinSize=8\nMaxSize=512\n\n\
+        [24x24@2/apps]\nSize=24\nScale=2\nType=Fixed\n";
+
+    #[test]
+    fn parses_directories_inherits_and_defaults() {
+        let theme = IconTheme::parse(INDEX);
+
+        assert_eq!(theme.parents, ["hicolor"]);
+        assert_eq!(theme.dirs.len(), 4);
+        assert_eq!(theme.dirs[1].kind, SizeKind::Threshold(2));
+        assert_eq!(theme.dirs[3].scale, 2);
+    }
+
+    #[test]
+    fn size_matching_follows_directory_type() {
+        let theme = IconTheme::parse(INDEX);
+        let [fixed, threshold, scalable, scaled] = &theme.dirs[..] else {
+            panic!("four directories should be parsed");
+        };
+
+        assert!(fixed.matches(16, 1) && !fixed.matches(17, 1));
+        assert!(threshold.matches(50, 1) && !threshold.matches(51, 1));
+        assert!(scalable.matches(300, 1) && !scalable.matches(300, 2));
+        assert!(scaled.matches(24, 2) && !scaled.matches(24, 1));
+        assert_eq!(fixed.distance(20, 1), 4);
+        assert_eq!(threshold.distance(32, 1), 14);
+        assert_eq!(scaled.distance(16, 1), 32);
+    }
+}
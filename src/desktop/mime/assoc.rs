@@ -0,0 +1,235 @@
+//! `mimeapps.list` and `mimeinfo.cache` lookups for a single MIME type.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where an application's claim on a MIME type comes from, strongest first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum AssociationRank {
+    /// Listed under `[Default Applications]` in a `mimeapps.list`.
+    Default,
+    /// Listed under `[Added Associations]` in a `mimeapps.list`.
+    Added,
+    /// Listed in a `mimeinfo.cache` generated by update-desktop-database.
+    Cached,
+    /// Only the entry's own `MimeType` key mentions the type.
+    Declared,
+}
+
+/// Desktop IDs associated with one MIME type across the XDG association files.
+#[derive(Debug, Default)]
+pub(crate) struct Associations {
+    defaults: Vec<String>,
+    added: Vec<String>,
+    cached: Vec<String>,
+    removed: HashSet<String>,
+}
+
+impl Associations {
+    /// Load associations for `mime` from the standard XDG locations.
+    pub(crate) fn load(mime: &str, application_dirs: &[PathBuf]) -> Self {
+        let lists = mimeapps_list_paths(application_dirs);
+        let caches: Vec<PathBuf> = application_dirs
+            .iter()
+            .map(|dir| dir.join("mimeinfo.cache"))
+            .collect();
+        Self::from_files(mime, &lists, &caches)
+    }
+
+    /// `lists` must be ordered from highest to lowest precedence.
+    fn from_files(mime: &str, lists: &[PathBuf], caches: &[PathBuf]) -> Self {
+        let mut associations = Self::default();
+
+        for path in lists {
+            let Ok(contents) = fs::read_to_string(path) else {
+                continue;
+            };
+            for (section, ids) in entries_for(&contents, mime) {
+                match section {
+                    "Default Applications" => extend_unique(&mut associations.defaults, ids),
+                    "Added Associations" => extend_unique(&mut associations.added, ids),
+                    "Removed Associations" => associations.removed.extend(ids),
+                    _ => {}
+                }
+            }
+        }
+
+        for path in caches {
+            let Ok(contents) = fs::read_to_string(path) else {
+                continue;
+            };
+            for (section, ids) in entries_for(&contents, mime) {
+                if section == "MIME Cache" {
+                    extend_unique(&mut associations.cached, ids);
+                }
+            }
+        }
+
+        associations
+    }
+
+    /// Rank `desktop_id`, or `None` when it should not be offered at all.
+    ///
+    /// Removed associations only suppress lower tiers; an explicit default still wins.
+    pub(crate) fn rank(&self, desktop_id: &str, declares: bool) -> Option<AssociationRank> {
+        let listed = |ids: &[String]| ids.iter().any(|id| id == desktop_id);
+        if listed(&self.defaults) {
+            return Some(AssociationRank::Default);
+        }
+        if self.removed.contains(desktop_id) {
+            return None;
+        }
+        if listed(&self.added) {
+            Some(AssociationRank::Added)
+        } else if listed(&self.cached) {
+            Some(AssociationRank::Cached)
+        } else if declares {
+            Some(AssociationRank::Declared)
+        } else {
+            None
+        }
+    }
+}
+
+fn extend_unique(target: &mut Vec<String>, ids: Vec<String>) {
+    for id in ids {
+        if !target.contains(&id) {
+            target.push(id);
+        }
+    }
+}
+
+/// Yields `(section, desktop ids)` for every line whose key is `mime`.
+fn entries_for<'a>(contents: &'a str, mime: &str) -> Vec<(&'a str, Vec<String>)> {
+    let mut section = "";
+    let mut entries = Vec::new();
+
+    for line in contents.lines().map(str::trim) {
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            section = name;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim().eq_ignore_ascii_case(mime) {
+            let ids = value
+                .split(';')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect();
+            entries.push((section, ids));
+        }
+    }
+
+    entries
+}
+
+/// `mimeapps.list` search order from the XDG MIME Applications spec, with
+/// desktop-specific files ahead of the generic one in every directory.
+fn mimeapps_list_paths(application_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.config_dir().to_path_buf()));
+    let config_dirs = std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string());
+    let desktops: Vec<String> = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|desktop| !desktop.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut dirs: Vec<PathBuf> = config_home.into_iter().collect();
+    dirs.extend(
+        config_dirs
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from),
+    );
+    dirs.extend(application_dirs.iter().cloned());
+
+    dirs.iter()
+        .flat_map(|dir| list_names(dir, &desktops))
+        .collect()
+}
+
+fn list_names(dir: &Path, desktops: &[String]) -> Vec<PathBuf> {
+    desktops
+        .iter()
+        .map(|desktop| dir.join(format!("{desktop}-mimeapps.list")))
+        .chain(std::iter::once(dir.join("mimeapps.list")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssociationRank, Associations};
+    use crate::testing::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn defaults_added_and_cache_rank_in_order() {
+        let dir = temp_dir("mime-assoc-ranks");
+        let user_list = dir.join("user-mimeapps.list");
+        let cache = dir.join("mimeinfo.cache");
+        fs::write(
+            &user_list,
+            "[Default Applications]\nimage/png=imv.desktop;\n\n\
+             [Added Associations]\nimage/png=gimp.desktop;imv.desktop;\n",
+        )
+        .expect("mimeapps.list should be written");
+        fs::write(
+            &cache,
+            "[MIME Cache]\nimage/png=feh.desktop;gimp.desktop;\nimage/jpeg=other.desktop;\n",
+        )
+        .expect("mimeinfo.cache should be written");
+
+        let associations = Associations::from_files("image/png", &[user_list], &[cache]);
+
+        assert_eq!(
+            associations.rank("imv.desktop", false),
+            Some(AssociationRank::Default)
+        );
+        assert_eq!(
+            associations.rank("gimp.desktop", false),
+            Some(AssociationRank::Added)
+        );
+        assert_eq!(
+            associations.rank("feh.desktop", false),
+            Some(AssociationRank::Cached)
+        );
+        assert_eq!(
+            associations.rank("viewer.desktop", true),
+            Some(AssociationRank::Declared)
+        );
+        assert_eq!(associations.rank("other.desktop", false), None);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn removed_associations_hide_declared_handlers() {
+        let dir = temp_dir("mime-assoc-removed");
+        let list = dir.join("mimeapps.list");
+        fs::write(
+            &list,
+            "[Removed Associations]\ntext/plain=libreoffice-writer.desktop;\n",
+        )
+        .expect("mimeapps.list should be written");
+
+        let associations = Associations::from_files("text/plain", &[list], &[]);
+
+        assert_eq!(associations.rank("libreoffice-writer.desktop", true), None);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
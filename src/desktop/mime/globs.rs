@@ -0,0 +1,213 @@
+//! File-name glob matching against shared-mime-info's `globs2` tables.
+//!
+//! The installed database under each XDG data directory is used when there is
+//! one; the bundled table only covers systems without shared-mime-info.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+static GLOBS: OnceLock<Vec<Glob>> = OnceLock::new();
+
+/// Glob line that drops a type's globs from less important directories.
+const NO_GLOBS: &str = "__NOGLOBS__";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Glob {
+    weight: u32,
+    mime: String,
+    pattern: String,
+    case_sensitive: bool,
+}
+
+fn globs() -> &'static [Glob] {
+    GLOBS.get_or_init(|| load_installed(&crate::desktop::dirs::mime_dirs()).unwrap_or_else(bundled))
+}
+
+fn bundled() -> Vec<Glob> {
+    parse_table(include_str!("globs2"))
+}
+
+/// Globs from the `globs2` files in `mime_dirs`, most important first, or
+/// `None` when none of them has one.
+///
+/// Globs for a type add up across directories, except that a `__NOGLOBS__`
+/// line drops the type's globs from the less important ones.
+fn load_installed(mime_dirs: &[PathBuf]) -> Option<Vec<Glob>> {
+    let mut found = false;
+    let mut globs = Vec::new();
+    let mut claimed: HashSet<String> = HashSet::new();
+
+    for dir in mime_dirs {
+        let Ok(table) = fs::read_to_string(dir.join("globs2")) else {
+            continue;
+        };
+        found = true;
+
+        let (cleared, table_globs): (Vec<Glob>, Vec<Glob>) = parse_table(&table)
+            .into_iter()
+            .partition(|glob| glob.pattern == NO_GLOBS);
+        globs.extend(
+            table_globs
+                .into_iter()
+                .filter(|glob| !claimed.contains(&glob.mime)),
+        );
+        claimed.extend(cleared.into_iter().map(|glob| glob.mime));
+    }
+
+    found.then_some(globs)
+}
+
+fn parse_table(table: &str) -> Vec<Glob> {
+    table
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, ':');
+            let weight = fields.next()?.parse().ok()?;
+            let mime = fields.next()?.to_string();
+            let pattern = fields.next()?.to_string();
+            let case_sensitive = fields
+                .next()
+                .is_some_and(|flags| flags.split(',').any(|flag| flag == "cs"));
+            Some(Glob {
+                weight,
+                mime,
+                pattern,
+                case_sensitive,
+            })
+        })
+        .collect()
+}
+
+/// Returns the MIME type whose glob best matches `file_name`.
+pub(super) fn match_file_name(file_name: &str) -> Option<&'static str> {
+    best_match(globs(), file_name)
+}
+
+/// Follows shared-mime-info precedence: highest weight first, then the longest
+/// pattern, then the glob listed first, i.e. from the more important directory.
+fn best_match<'a>(globs: &'a [Glob], file_name: &str) -> Option<&'a str> {
+    let lowered = file_name.to_lowercase();
+    globs
+        .iter()
+        .rev()
+        .filter(|glob| {
+            if glob.case_sensitive {
+                wildcard_match(&glob.pattern, file_name)
+            } else {
+                wildcard_match(&glob.pattern.to_lowercase(), &lowered)
+            }
+        })
+        .max_by_key(|glob| (glob.weight, glob.pattern.len()))
+        .map(|glob| glob.mime.as_str())
+}
+
+/// Matches `*` (any run) and `?` (one character); other characters are literal.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Glob, best_match, bundled, load_installed, wildcard_match};
+    use crate::testing::temp_dir;
+    use std::fs;
+    use std::sync::OnceLock;
+
+    fn bundled_match(file_name: &str) -> Option<&'static str> {
+        static BUNDLED: OnceLock<Vec<Glob>> = OnceLock::new();
+        best_match(BUNDLED.get_or_init(bundled), file_name)
+    }
+
+    #[test]
+    fn extensions_match_case_insensitively() {
+        assert_eq!(bundled_match("Shot.PNG"), Some("image/png"));
+        assert_eq!(bundled_match("notes.md"), Some("text/markdown"));
+    }
+
+    #[test]
+    fn longest_pattern_wins_at_equal_weight() {
+        assert_eq!(
+            bundled_match("release.tar.gz"),
+            Some("application/x-compressed-tar")
+        );
+        assert_eq!(bundled_match("dump.gz"), Some("application/gzip"));
+    }
+
+    #[test]
+    fn case_sensitive_literals_require_exact_case() {
+        assert_eq!(bundled_match("Makefile"), Some("text/x-makefile"));
+        assert_eq!(bundled_match("MAKEFILE"), None);
+    }
+
+    #[test]
+    fn installed_tables_merge_unless_a_type_clears_its_globs() {
+        let dir = temp_dir("mime-globs-installed");
+        let (user, system, missing) = (dir.join("user"), dir.join("system"), dir.join("none"));
+        fs::create_dir_all(&user).expect("user mime dir should be created");
+        fs::create_dir_all(&system).expect("system mime dir should be created");
+        fs::write(
+            user.join("globs2"),
+            "50:text/x-custom:*.txt\n50:application/x-gone:__NOGLOBS__\n\
+             50:application/x-gone:*.went\n",
+        )
+        .expect("user globs2 should be written");
+        fs::write(
+            system.join("globs2"),
+            "# generated\n50:text/x-custom:*.cst\n50:text/plain:*.txt\n\
+             50:application/x-gone:*.gone\n50:application/x-blend:*.blend:cs\n",
+        )
+        .expect("system globs2 should be written");
+
+        let globs = load_installed(&[missing.clone(), user, system]).expect("tables exist");
+        // A user glob adds to the system ones and wins a tie against them.
+        assert_eq!(best_match(&globs, "a.txt"), Some("text/x-custom"));
+        assert_eq!(best_match(&globs, "a.cst"), Some("text/x-custom"));
+        // `__NOGLOBS__` drops the system globs but keeps the user's own.
+        assert_eq!(best_match(&globs, "a.gone"), None);
+        assert_eq!(best_match(&globs, "a.went"), Some("application/x-gone"));
+        assert_eq!(
+            best_match(&globs, "scene.blend"),
+            Some("application/x-blend")
+        );
+        assert_eq!(best_match(&globs, "scene.BLEND"), None);
+        assert!(load_installed(&[missing]).is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn wildcard_supports_prefix_patterns() {
+        assert!(wildcard_match("README*", "README.old"));
+        assert!(wildcard_match("*.t?t", "a.txt"));
+        assert!(!wildcard_match("*.txt", "a.txt.bak"));
+    }
+}
@@ -0,0 +1,111 @@
+//! MIME type detection and application associations for `--open-with`.
+
+mod assoc;
+mod globs;
+
+pub(crate) use assoc::{AssociationRank, Associations};
+
+use super::LaunchTarget;
+use std::io::Read;
+use std::path::Path;
+
+const FALLBACK_MIME: &str = "application/octet-stream";
+
+/// Detect the MIME type of a launch target.
+///
+/// URLs map to `x-scheme-handler/<scheme>`; local files are matched by name and,
+/// failing that, sniffed for plain text.
+pub(crate) fn detect(target: &LaunchTarget) -> String {
+    match target {
+        LaunchTarget::Url(url) if !url.starts_with("file://") => {
+            let scheme = url
+                .split_once(':')
+                .map_or(url.as_str(), |(scheme, _)| scheme);
+            format!("x-scheme-handler/{}", scheme.to_ascii_lowercase())
+        }
+        _ => detect_path(Path::new(&target.as_file_arg())),
+    }
+}
+
+fn detect_path(path: &Path) -> String {
+    if path.is_dir() {
+        return "inode/directory".to_string();
+    }
+
+    let by_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(globs::match_file_name);
+    if let Some(mime) = by_name {
+        return mime.to_string();
+    }
+
+    if looks_like_text(path) {
+        "text/plain".to_string()
+    } else {
+        FALLBACK_MIME.to_string()
+    }
+}
+
+fn looks_like_text(path: &Path) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(512);
+    if file.take(512).read_to_end(&mut head).is_err() {
+        return false;
+    }
+    !head.contains(&0) && std::str::from_utf8(&head).is_ok()
+}
+
+/// Whether a `MimeType` value declared by an entry covers `mime`.
+///
+/// Accepts `type/*` wildcards, and treats every `text/*` type as a subclass of
+/// `text/plain` the way shared-mime-info does.
+pub(crate) fn declares(declared: &str, mime: &str) -> bool {
+    if declared.eq_ignore_ascii_case(mime) {
+        return true;
+    }
+    if let Some(major) = declared.strip_suffix("/*") {
+        return mime
+            .split_once('/')
+            .is_some_and(|(mime_major, _)| mime_major.eq_ignore_ascii_case(major));
+    }
+    declared.eq_ignore_ascii_case("text/plain") && mime.starts_with("text/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{declares, detect};
+    use crate::desktop::LaunchTarget;
+    use std::path::PathBuf;
+
+    #[test]
+    fn urls_map_to_scheme_handlers() {
+        let target = LaunchTarget::Url("HTTPS://example.com".to_string());
+
+        assert_eq!(detect(&target), "x-scheme-handler/https");
+    }
+
+    #[test]
+    fn file_urls_are_detected_by_name() {
+        let target = LaunchTarget::Url("file:///tmp/does-not-exist.pdf".to_string());
+
+        assert_eq!(detect(&target), "application/pdf");
+    }
+
+    #[test]
+    fn unknown_missing_files_fall_back_to_octet_stream() {
+        let target = LaunchTarget::File(PathBuf::from("/nonexistent/fsel-blob.unknownext"));
+
+        assert_eq!(detect(&target), "application/octet-stream");
+    }
+
+    #[test]
+    fn wildcards_and_text_subclasses_are_declared() {
+        assert!(declares("image/*", "image/png"));
+        assert!(declares("text/plain", "text/x-rust"));
+        assert!(!declares("text/plain", "application/json"));
+        assert!(!declares("image/png", "image/jpeg"));
+    }
+}
@@ -3,21 +3,39 @@ use ratatui::widgets::ListItem;
 use std::fmt;
 use std::path::{Path, PathBuf};
 
+mod available;
+mod config_entry;
 mod dirs;
 mod discover;
+mod exec;
+mod icon;
+pub(crate) mod mime;
 mod parse;
+mod target;
+mod validate;
+mod watch;
+mod whatis;
 
+pub(crate) use available::ExecAvailability;
+pub use available::ExecCheck;
 pub(crate) use dirs::application_dirs;
-pub(crate) use discover::desktop_file_id;
 pub use discover::{DiscoverOptions, read_with_options};
+pub(crate) use discover::{desktop_file_id, load_actions, walk_desktop_files};
+pub(crate) use exec::ExecCommand;
+pub(crate) use icon::IconLookup;
+pub(crate) use target::LaunchTarget;
+pub(crate) use validate::{Severity, validate};
+pub(crate) use watch::{DesktopChange, DesktopWatcher};
 
 /// An XDG Specification app with full desktop-entry metadata.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct App {
     /// App name (Name field).
     pub name: String,
-    /// Command to run (Exec field).
+    /// Command to run (Exec field), shell-quoted with field codes resolved for display.
     pub command: String,
+    /// Parsed Exec field used to build argv at launch time.
+    pub(crate) exec: ExecCommand,
     /// App description/comment (Comment field).
     pub description: String,
     /// Generic name of application (GenericName field).
@@ -46,12 +64,17 @@ pub struct App {
     pub startup_wm_class: Option<String>,
     /// Command to test if executable exists (TryExec field).
     pub try_exec: Option<String>,
-    /// Desktop Entry type (usually "Application").
+    /// Desktop Entry type: "Application", "Link", or "Directory".
     pub entry_type: String,
+    /// Target of a `Type=Link` entry (URL field).
+    pub url: Option<String>,
     /// Desktop file ID for tracking.
     pub desktop_id: Option<String>,
     #[serde(skip)]
     pub(crate) source_path: Option<PathBuf>,
+    /// Declared in `[[entries]]` rather than read from a desktop file.
+    #[serde(skip)]
+    pub(crate) from_config: bool,
 
     /// Matching score (used in UI).
     pub score: i64,
@@ -83,6 +106,9 @@ impl App {
     }
 
     pub(crate) fn entry_key(&self) -> Option<crate::core::hidden_entries::EntryKey> {
+        if self.from_config {
+            return Some(crate::core::hidden_entries::EntryKey::config(&self.name));
+        }
         let source_path = self.source_path.as_deref()?;
         Some(match self.desktop_id.as_deref() {
             Some(desktop_id) => {
@@ -92,7 +118,22 @@ impl App {
         })
     }
 
+    /// The key launch history, frecency, and pins are stored under.
+    ///
+    /// This is the entry key, so entries that share a name keep separate
+    /// counts and a renamed entry keeps its own. Entries built without a
+    /// source fall back to their name.
+    pub(crate) fn store_key(&self) -> String {
+        match self.entry_key() {
+            Some(entry_key) => entry_key.as_str().to_string(),
+            None => self.name.clone(),
+        }
+    }
+
     pub(crate) fn source_display(&self) -> Option<String> {
+        if self.from_config {
+            return Some("config.toml [[entries]]".to_string());
+        }
         self.source_path
             .as_deref()
             .map(|path| path.to_string_lossy().into_owned())
@@ -105,6 +146,27 @@ impl App {
     pub(crate) fn source_path(&self) -> Option<&Path> {
         self.source_path.as_deref()
     }
+
+    /// Whether this entry is a raw `$PATH` executable rather than a desktop entry.
+    pub(crate) fn is_path_executable(&self) -> bool {
+        self.source_path.as_deref().is_some_and(|path| {
+            path.extension()
+                .is_none_or(|extension| extension != "desktop")
+        })
+    }
+
+    pub(crate) fn kind(&self) -> EntryKind {
+        match self.entry_type.as_str() {
+            "Link" => EntryKind::Link,
+            "Directory" => EntryKind::Directory,
+            _ => EntryKind::Application,
+        }
+    }
+
+    /// Names of the `[Desktop Action …]` sections this entry declares.
+    pub(crate) fn action_names(&self) -> &[String] {
+        self.actions.as_deref().unwrap_or_default()
+    }
 }
 
 impl PartialEq for App {
@@ -162,6 +224,17 @@ impl<'a> From<&'a App> for ListItem<'a> {
     }
 }
 
+/// The `Type` of a desktop entry, which decides what selecting it does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EntryKind {
+    /// Runs its `Exec` command.
+    Application,
+    /// Opens its `URL` with the configured opener.
+    Link,
+    /// Groups the entries that share its categories.
+    Directory,
+}
+
 #[derive(Default)]
 struct Action {
     name: String,
@@ -1,3 +1,4 @@
+use super::exec::{ExecCommand, ExecFields};
 use super::{Action, App};
 use eyre::eyre;
 use std::convert::AsRef;
@@ -47,6 +48,69 @@ fn parse_semicolon_list(value: &str) -> Vec<String> {
         .collect()
 }
 
+/// One meaningful line of a desktop file.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Line<'a> {
+    /// A group header as written, brackets included.
+    Group(&'a str),
+    /// A `Key=Value` or `Key[locale]=Value` line.
+    Entry(Entry<'a>),
+    /// A line the parser skips, with the reason it is invalid.
+    Malformed(&'static str),
+}
+
+/// A key/value line split into its parts, all trimmed.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Entry<'a> {
+    /// The key as written, including any locale.
+    pub(super) key: &'a str,
+    /// The key without its locale.
+    pub(super) name: &'a str,
+    pub(super) locale: Option<&'a str>,
+    pub(super) value: &'a str,
+}
+
+/// Non-blank, non-comment lines of `contents` with their 1-based line numbers.
+pub(super) fn scan_lines(contents: &str) -> impl Iterator<Item = (usize, Line<'_>)> {
+    contents.lines().enumerate().filter_map(|(index, line)| {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        Some((index + 1, scan_line(line)))
+    })
+}
+
+fn scan_line(line: &str) -> Line<'_> {
+    if line.starts_with('[') {
+        return Line::Group(line);
+    }
+    let Some((key, value)) = line.split_once('=') else {
+        return Line::Malformed("expected `Key=Value`");
+    };
+    let key = key.trim();
+    let value = value.trim();
+    if key.is_empty() {
+        return Line::Malformed("missing key before `=`");
+    }
+
+    let (name, locale) = match key.split_once('[') {
+        None => (key, None),
+        Some((name, rest)) => match rest.strip_suffix(']') {
+            Some(locale) if !locale.is_empty() && !locale.contains(['[', ']']) => {
+                (name, Some(locale))
+            }
+            _ => return Line::Malformed("malformed locale brackets in key"),
+        },
+    };
+    Line::Entry(Entry {
+        key,
+        name,
+        locale,
+        value,
+    })
+}
+
 #[derive(Default)]
 struct LocalizedField {
     base: Option<String>,
@@ -127,89 +191,96 @@ impl App {
         let mut startup_wm_class = None;
         let mut try_exec = None;
         let mut entry_type = None;
+        let mut url = None;
         let mut actions = None;
         let mut in_target_section = false;
 
-        for line in contents.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-
-            if line.starts_with('[') && in_target_section && line != pattern {
-                break;
-            }
-
-            if line == pattern {
-                in_target_section = true;
-                continue;
-            }
+        for (_, line) in scan_lines(contents) {
+            let entry = match line {
+                Line::Group(header) if header == pattern => {
+                    in_target_section = true;
+                    continue;
+                }
+                Line::Group(_) if in_target_section => break,
+                Line::Entry(entry) if in_target_section => entry,
+                Line::Group(_) | Line::Entry(_) | Line::Malformed(_) => continue,
+            };
 
-            if in_target_section && let Some((key, value)) = line.split_once('=') {
-                let key = key.trim();
-                let value = value.trim();
-                match key.split('[').next().unwrap_or(key) {
-                    "Type" => entry_type = Some(value.to_string()),
-                    "Name" => name.set(key, value, locales),
-                    "GenericName" => generic_name.set(key, value, locales),
-                    "Comment" => description.set(key, value, locales),
-                    "Keywords" if keywords.is_empty() => keywords = parse_semicolon_list(value),
-                    "Categories" if categories.is_empty() => {
-                        categories = parse_semicolon_list(value);
-                    }
-                    "MimeType" if mime_types.is_empty() => {
-                        mime_types = parse_semicolon_list(value);
-                    }
-                    "Icon" if icon.is_none() => icon = Some(value.to_string()),
-                    "Terminal" => terminal_exec = value.eq_ignore_ascii_case("true"),
-                    "Exec" if exec.is_none() => {
-                        let cleaned = value
-                            .split_whitespace()
-                            .filter(|part| !part.starts_with('%'))
-                            .collect::<Vec<_>>()
-                            .join(" ");
-                        exec = Some(cleaned);
-                    }
-                    "Path" if path.is_none() => path = Some(value.to_string()),
-                    "TryExec" if try_exec.is_none() => try_exec = Some(value.to_string()),
-                    "OnlyShowIn" if only_show_in.is_empty() => {
-                        only_show_in = parse_semicolon_list(value);
-                    }
-                    "NotShowIn" if not_show_in.is_empty() => {
-                        not_show_in = parse_semicolon_list(value);
-                    }
-                    "Hidden" => hidden = value.eq_ignore_ascii_case("true"),
-                    "NoDisplay" => no_display = value.eq_ignore_ascii_case("true"),
-                    "StartupNotify" => startup_notify = value.eq_ignore_ascii_case("true"),
-                    "StartupWMClass" if startup_wm_class.is_none() => {
-                        startup_wm_class = Some(value.to_string());
-                    }
-                    "Actions" if actions.is_none() && action.is_none() => {
-                        actions = Some(parse_semicolon_list(value));
-                    }
-                    _ => {}
+            let (key, value) = (entry.key, entry.value);
+            match entry.name {
+                "Type" => entry_type = Some(value.to_string()),
+                "Name" => name.set(key, value, locales),
+                "GenericName" => generic_name.set(key, value, locales),
+                "Comment" => description.set(key, value, locales),
+                "Keywords" if keywords.is_empty() => keywords = parse_semicolon_list(value),
+                "Categories" if categories.is_empty() => {
+                    categories = parse_semicolon_list(value);
+                }
+                "MimeType" if mime_types.is_empty() => {
+                    mime_types = parse_semicolon_list(value);
+                }
+                "Icon" if icon.is_none() => icon = Some(value.to_string()),
+                "Terminal" => terminal_exec = value.eq_ignore_ascii_case("true"),
+                "Exec" if exec.is_none() => exec = Some(value.to_string()),
+                "Path" if path.is_none() => path = Some(value.to_string()),
+                "TryExec" if try_exec.is_none() => try_exec = Some(value.to_string()),
+                "URL" if url.is_none() => url = Some(value.to_string()),
+                "OnlyShowIn" if only_show_in.is_empty() => {
+                    only_show_in = parse_semicolon_list(value);
                 }
+                "NotShowIn" if not_show_in.is_empty() => {
+                    not_show_in = parse_semicolon_list(value);
+                }
+                "Hidden" => hidden = value.eq_ignore_ascii_case("true"),
+                "NoDisplay" => no_display = value.eq_ignore_ascii_case("true"),
+                "StartupNotify" => startup_notify = value.eq_ignore_ascii_case("true"),
+                "StartupWMClass" if startup_wm_class.is_none() => {
+                    startup_wm_class = Some(value.to_string());
+                }
+                "Actions" if actions.is_none() && action.is_none() => {
+                    actions = Some(parse_semicolon_list(value));
+                }
+                _ => {}
             }
         }
 
         let entry_type = entry_type.unwrap_or_else(|| "Application".to_string());
-        if entry_type != "Application" {
-            return Err(eyre!("Not an Application type desktop entry"));
+        match entry_type.as_str() {
+            "Application" => {}
+            "Link" if url.is_none() && !(hidden && include_hidden) => {
+                return Err(eyre!("Missing required URL field"));
+            }
+            "Link" | "Directory" => {
+                // Neither runs a command, so `Exec` and actions do not apply.
+                exec = None;
+                actions = None;
+            }
+            _ => return Err(eyre!("Unsupported desktop entry type: {entry_type}")),
         }
 
-        let name = name
-            .into_value()
+        let translated_name = name.into_value();
+        let exec = match (
+            exec,
+            hidden && include_hidden || entry_type != "Application",
+        ) {
+            (Some(value), _) => {
+                let fields = ExecFields {
+                    name: translated_name.as_deref().unwrap_or_default(),
+                    icon: icon.as_deref(),
+                };
+                ExecCommand::parse(&value, fields)
+                    .map_err(|error| eyre!("Invalid Exec field: {error}"))?
+            }
+            (None, true) => ExecCommand::default(),
+            (None, false) => return Err(eyre!("Missing required Exec field")),
+        };
+        let name = translated_name
             .map(|value| match action {
                 Some(action) if !action.from.is_empty() => format!("{} ({value})", action.from),
                 None => value,
                 Some(_) => value,
             })
             .unwrap_or_else(|| "Unknown".to_string());
-        let command = match (exec, hidden && include_hidden) {
-            (Some(command), _) => command,
-            (None, true) => String::new(),
-            (None, false) => return Err(eyre!("Missing required Exec field")),
-        };
 
         if (hidden && !include_hidden) || (!hidden && filter_desktop && no_display) {
             return Err(eyre!("Application is hidden"));
@@ -221,7 +292,11 @@ impl App {
             pinned: false,
             last_access: None,
             name,
-            command,
+            command: match &url {
+                Some(url) if entry_type == "Link" => url.clone(),
+                _ => exec.display(None),
+            },
+            exec,
             description: description.into_value().unwrap_or_default(),
             generic_name: generic_name.into_value(),
             keywords,
@@ -237,8 +312,10 @@ impl App {
             startup_wm_class,
             try_exec,
             entry_type,
+            url,
             desktop_id: None,
             source_path: None,
+            from_config: false,
             actions,
             breakdown: None,
         })
@@ -256,7 +333,8 @@ impl App {
 
 #[cfg(test)]
 mod tests {
-    use super::{Action, App, LocalizedField};
+    use super::{Action, App, Entry, Line, LocalizedField, scan_lines};
+    use crate::desktop::EntryKind;
 
     #[test]
     fn parse_strips_exec_field_codes() {
@@ -269,6 +347,32 @@ mod tests {
         assert_eq!(app.command, "/usr/bin/editor");
     }
 
+    #[test]
+    fn parse_expands_icon_and_name_codes_into_exec() {
+        let app = App::parse(
+            "[Desktop Entry]\nType=Application\nName=Viewer\nExec=viewer %i --title %c %f\nIcon=viewer-icon",
+            false,
+        )
+        .expect("desktop entry should parse");
+
+        assert_eq!(
+            app.exec.argv(None),
+            ["viewer", "--icon", "viewer-icon", "--title", "Viewer"]
+        );
+        assert_eq!(app.command, "viewer --icon viewer-icon --title Viewer");
+    }
+
+    #[test]
+    fn parse_rejects_unterminated_exec_quote() {
+        assert!(
+            App::parse(
+                "[Desktop Entry]\nType=Application\nName=Broken\nExec=sh -c \"echo",
+                false,
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn hidden_tombstone_can_omit_exec_when_requested_by_discovery() {
         let app = App::parse_including_hidden(
@@ -288,6 +392,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn link_and_directory_entries_parse_without_exec() {
+        let link = App::parse(
+            "[Desktop Entry]\nType=Link\nName=Wiki\nURL=https://wiki.example.com/\nActions=Edit;",
+            false,
+        )
+        .expect("link entry should parse");
+        assert_eq!(link.kind(), EntryKind::Link);
+        assert_eq!(link.url.as_deref(), Some("https://wiki.example.com/"));
+        assert_eq!(link.command, "https://wiki.example.com/");
+        assert!(link.action_names().is_empty());
+
+        let directory = App::parse(
+            "[Desktop Entry]\nType=Directory\nName=Games\nCategories=Game;",
+            false,
+        )
+        .expect("directory entry should parse");
+        assert_eq!(directory.kind(), EntryKind::Directory);
+
+        assert!(App::parse("[Desktop Entry]\nType=Link\nName=Wiki", false).is_err());
+        assert!(App::parse("[Desktop Entry]\nType=Service\nName=Daemon\nExec=d", false).is_err());
+    }
+
     #[test]
     fn parse_action_uses_action_section_name() {
         let action = Action::default().name("OpenWindow").from("Editor");
@@ -348,4 +475,29 @@ mod tests {
 
         assert_eq!(app.name, "Open");
     }
+
+    #[test]
+    fn scan_lines_numbers_lines_and_flags_malformed_ones() {
+        let lines: Vec<_> =
+            scan_lines("# comment\n[Desktop Entry]\n\nName[de] = Editor\nbroken\nName[=x")
+                .collect();
+
+        assert_eq!(
+            lines,
+            [
+                (2, Line::Group("[Desktop Entry]")),
+                (
+                    4,
+                    Line::Entry(Entry {
+                        key: "Name[de]",
+                        name: "Name",
+                        locale: Some("de"),
+                        value: "Editor",
+                    })
+                ),
+                (5, Line::Malformed("expected `Key=Value`")),
+                (6, Line::Malformed("malformed locale brackets in key")),
+            ]
+        );
+    }
 }
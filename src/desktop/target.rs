@@ -0,0 +1,126 @@
+//! Files and URLs handed to a desktop entry at launch time.
+
+use std::path::{Path, PathBuf};
+
+/// One launch argument, classified so it can fill either file or URL field codes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum LaunchTarget {
+    /// A local path, made absolute so it survives the entry's `Path` working directory.
+    File(PathBuf),
+    /// Anything with a URL scheme, including `file://` URLs.
+    Url(String),
+}
+
+impl LaunchTarget {
+    /// Classify a command-line argument, resolving relative paths against `cwd`.
+    ///
+    /// Existing paths always win so that files such as `notes:today.txt` are not
+    /// mistaken for URLs.
+    pub(crate) fn from_arg(arg: &str, cwd: &Path) -> Self {
+        let path = Path::new(arg);
+        if !path.exists() && has_url_scheme(arg) {
+            return Self::Url(arg.to_string());
+        }
+
+        if path.is_absolute() {
+            Self::File(path.to_path_buf())
+        } else {
+            Self::File(cwd.join(path))
+        }
+    }
+
+    /// Value for `%f`/`%F`: `file://` URLs are turned back into local paths.
+    pub(crate) fn as_file_arg(&self) -> String {
+        match self {
+            Self::File(path) => path.to_string_lossy().into_owned(),
+            Self::Url(url) => file_url_path(url).unwrap_or_else(|| url.clone()),
+        }
+    }
+
+    /// Value for `%u`/`%U`: the spec allows local files to be passed as plain paths.
+    pub(crate) fn as_url_arg(&self) -> String {
+        match self {
+            Self::File(path) => path.to_string_lossy().into_owned(),
+            Self::Url(url) => url.clone(),
+        }
+    }
+}
+
+fn has_url_scheme(arg: &str) -> bool {
+    let Some((scheme, _)) = arg.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    scheme.len() > 1
+        && chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'))
+}
+
+fn file_url_path(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("file://")?;
+    let (host, path) = rest.split_at(rest.find('/')?);
+    if !host.is_empty() && host != "localhost" {
+        return None;
+    }
+    Some(percent_decode(path))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hex) = value.get(index + 1..index + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            index += 3;
+            continue;
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LaunchTarget;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn relative_paths_resolve_against_cwd() {
+        let target = LaunchTarget::from_arg("shots/screen.png", Path::new("/home/user"));
+
+        assert_eq!(
+            target,
+            LaunchTarget::File(PathBuf::from("/home/user/shots/screen.png"))
+        );
+    }
+
+    #[test]
+    fn scheme_arguments_are_urls() {
+        let target = LaunchTarget::from_arg("https://example.com/a b", Path::new("/"));
+
+        assert_eq!(target.as_url_arg(), "https://example.com/a b");
+        assert_eq!(target.as_file_arg(), "https://example.com/a b");
+    }
+
+    #[test]
+    fn file_urls_decode_to_local_paths() {
+        let target = LaunchTarget::from_arg("file:///tmp/my%20shot.png", Path::new("/"));
+
+        assert_eq!(target.as_file_arg(), "/tmp/my shot.png");
+        assert_eq!(target.as_url_arg(), "file:///tmp/my%20shot.png");
+    }
+
+    #[test]
+    fn remote_file_urls_are_left_untouched() {
+        let target = LaunchTarget::from_arg("file://server/share/a.txt", Path::new("/"));
+
+        assert_eq!(target.as_file_arg(), "file://server/share/a.txt");
+    }
+}
@@ -0,0 +1,419 @@
+//! Checking desktop files against the Desktop Entry specification.
+
+use super::available::program_on_path;
+use super::exec::{ExecCommand, ExecLint};
+use super::parse::{Entry, Line, scan_lines};
+use std::collections::HashSet;
+use std::fmt;
+
+const KNOWN_KEYS: &[&str] = &[
+    "Type",
+    "Version",
+    "Name",
+    "GenericName",
+    "NoDisplay",
+    "Comment",
+    "Icon",
+    "Hidden",
+    "OnlyShowIn",
+    "NotShowIn",
+    "DBusActivatable",
+    "TryExec",
+    "Exec",
+    "Path",
+    "Terminal",
+    "Actions",
+    "MimeType",
+    "Categories",
+    "Implements",
+    "Keywords",
+    "StartupNotify",
+    "StartupWMClass",
+    "URL",
+    "PrefersNonDefaultGPU",
+    "SingleMainWindow",
+];
+const ACTION_KEYS: &[&str] = &["Name", "Icon", "Exec"];
+const LOCALIZED_KEYS: &[&str] = &["Name", "GenericName", "Comment", "Icon", "Keywords"];
+const BOOLEAN_KEYS: &[&str] = &[
+    "NoDisplay",
+    "Hidden",
+    "DBusActivatable",
+    "Terminal",
+    "StartupNotify",
+    "PrefersNonDefaultGPU",
+    "SingleMainWindow",
+];
+const ENTRY_GROUP: &str = "Desktop Entry";
+const ACTION_GROUP_PREFIX: &str = "Desktop Action ";
+
+/// How serious a [`Diagnostic`] is; only errors fail validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// One problem found in a desktop file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    /// 1-based line the problem is on, or `None` for the file as a whole.
+    pub(crate) line: Option<usize>,
+    pub(crate) severity: Severity,
+    pub(crate) message: String,
+}
+
+/// A group header and the entries under it.
+struct Group<'a> {
+    name: &'a str,
+    line: usize,
+    entries: Vec<(usize, Entry<'a>)>,
+}
+
+impl<'a> Group<'a> {
+    fn get(&self, name: &str) -> Option<(usize, &'a str)> {
+        self.entries
+            .iter()
+            .find(|(_, entry)| entry.name == name && entry.locale.is_none())
+            .map(|(line, entry)| (*line, entry.value))
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    fn error(&mut self, line: Option<usize>, message: impl Into<String>) {
+        self.push(line, Severity::Error, message.into());
+    }
+
+    fn warning(&mut self, line: Option<usize>, message: impl Into<String>) {
+        self.push(line, Severity::Warning, message.into());
+    }
+
+    fn push(&mut self, line: Option<usize>, severity: Severity, message: String) {
+        self.diagnostics.push(Diagnostic {
+            line,
+            severity,
+            message,
+        });
+    }
+}
+
+/// Check `contents` and return its problems ordered by line.
+pub(crate) fn validate(contents: &str) -> Vec<Diagnostic> {
+    validate_with(contents, program_on_path)
+}
+
+fn validate_with(contents: &str, program_exists: impl Fn(&str) -> bool) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+    let groups = read_groups(contents, &mut report);
+
+    match groups.first() {
+        None => report.error(None, format!("missing [{ENTRY_GROUP}] group")),
+        Some(first) if first.name != ENTRY_GROUP => {
+            report.error(
+                Some(first.line),
+                format!("the first group must be [{ENTRY_GROUP}]"),
+            );
+        }
+        Some(_) => {}
+    }
+
+    let entry_group = groups.iter().find(|group| group.name == ENTRY_GROUP);
+    let listed_actions: Vec<&str> = entry_group
+        .and_then(|group| group.get("Actions"))
+        .map(|(_, value)| {
+            value
+                .split(';')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+        })
+        .into_iter()
+        .flatten()
+        .collect();
+
+    for group in &groups {
+        if group.name == ENTRY_GROUP {
+            check_entry_group(group, &groups, &program_exists, &mut report);
+        } else if let Some(action) = group.name.strip_prefix(ACTION_GROUP_PREFIX) {
+            if !listed_actions.contains(&action) {
+                report.warning(
+                    Some(group.line),
+                    format!("[{}] is not listed in Actions", group.name),
+                );
+            }
+            check_action_group(group, &mut report);
+        } else if !group.name.starts_with("X-") {
+            report.warning(Some(group.line), format!("unknown group [{}]", group.name));
+        }
+    }
+
+    report
+        .diagnostics
+        .sort_by_key(|diagnostic| diagnostic.line.unwrap_or(0));
+    report.diagnostics
+}
+
+fn read_groups<'a>(contents: &'a str, report: &mut Report) -> Vec<Group<'a>> {
+    let mut groups: Vec<Group<'a>> = Vec::new();
+    let mut seen_keys = HashSet::new();
+
+    for (line_number, line) in scan_lines(contents) {
+        match line {
+            Line::Group(header) => {
+                let Some(name) = header
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.strip_suffix(']'))
+                else {
+                    report.error(Some(line_number), "group header is missing its closing `]`");
+                    continue;
+                };
+                if groups.iter().any(|group| group.name == name) {
+                    report.error(Some(line_number), format!("duplicate group [{name}]"));
+                }
+                seen_keys.clear();
+                groups.push(Group {
+                    name,
+                    line: line_number,
+                    entries: Vec::new(),
+                });
+            }
+            Line::Entry(entry) => {
+                let Some(group) = groups.last_mut() else {
+                    report.error(
+                        Some(line_number),
+                        format!("`{}` is outside of any group", entry.key),
+                    );
+                    continue;
+                };
+                if !seen_keys.insert(entry.key) {
+                    report.error(Some(line_number), format!("duplicate key `{}`", entry.key));
+                }
+                group.entries.push((line_number, entry));
+            }
+            Line::Malformed(reason) => report.error(Some(line_number), reason),
+        }
+    }
+
+    groups
+}
+
+fn check_entry_group(
+    group: &Group<'_>,
+    groups: &[Group<'_>],
+    program_exists: &impl Fn(&str) -> bool,
+    report: &mut Report,
+) {
+    check_keys(group, KNOWN_KEYS, report);
+    let header = Some(group.line);
+
+    let entry_type = group.get("Type");
+    match entry_type {
+        None => report.error(header, "missing required key `Type`"),
+        Some((_, "Application" | "Link" | "Directory")) => {}
+        Some((line, value)) => report.error(Some(line), format!("unknown Type `{value}`")),
+    }
+    if group.get("Name").is_none() {
+        report.error(header, "missing required key `Name`");
+    }
+
+    if let Some((line, value)) = group.get("Exec") {
+        check_exec(line, value, report);
+    }
+    let dbus_activatable = group
+        .get("DBusActivatable")
+        .is_some_and(|(_, value)| value == "true");
+    match entry_type.map(|(_, value)| value) {
+        Some("Application") if group.get("Exec").is_none() && !dbus_activatable => {
+            report.error(header, "missing required key `Exec`");
+        }
+        Some("Link") if group.get("URL").is_none() => {
+            report.error(header, "missing required key `URL` for Type=Link");
+        }
+        _ => {}
+    }
+
+    if let Some((line, program)) = group.get("TryExec")
+        && !program_exists(program)
+    {
+        report.warning(
+            Some(line),
+            format!("TryExec program `{program}` is not installed"),
+        );
+    }
+
+    if let Some((line, value)) = group.get("Actions") {
+        for action in value
+            .split(';')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let section = format!("{ACTION_GROUP_PREFIX}{action}");
+            if !groups.iter().any(|group| group.name == section) {
+                report.error(
+                    Some(line),
+                    format!("action `{action}` has no [{section}] group"),
+                );
+            }
+        }
+    }
+}
+
+fn check_action_group(group: &Group<'_>, report: &mut Report) {
+    check_keys(group, ACTION_KEYS, report);
+    if group.get("Name").is_none() {
+        report.error(Some(group.line), "missing required key `Name`");
+    }
+    if let Some((line, value)) = group.get("Exec") {
+        check_exec(line, value, report);
+    }
+}
+
+fn check_keys(group: &Group<'_>, known: &[&str], report: &mut Report) {
+    for (line, entry) in &group.entries {
+        let line = Some(*line);
+        if entry.name.starts_with("X-") {
+            continue;
+        }
+        if !known.contains(&entry.name) {
+            report.warning(line, format!("unknown key `{}`", entry.name));
+            continue;
+        }
+        if entry.locale.is_some() && !LOCALIZED_KEYS.contains(&entry.name) {
+            report.warning(line, format!("`{}` cannot be localized", entry.name));
+        }
+        if BOOLEAN_KEYS.contains(&entry.name) && !matches!(entry.value, "true" | "false") {
+            report.error(
+                line,
+                format!(
+                    "`{}` must be `true` or `false`, not `{}`",
+                    entry.name, entry.value
+                ),
+            );
+        }
+    }
+}
+
+fn check_exec(line: usize, value: &str, report: &mut Report) {
+    let line = Some(line);
+    match ExecCommand::lint(value) {
+        Err(error) => report.error(line, format!("invalid Exec: {error}")),
+        Ok(lints) => {
+            for lint in lints {
+                match lint {
+                    ExecLint::DeprecatedCode(_) => report.warning(line, lint.to_string()),
+                    _ => report.error(line, lint.to_string()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, Severity, validate_with};
+    use std::collections::HashMap;
+
+    /// Messages per line, as `severity: message`.
+    fn summarize(diagnostics: &[Diagnostic]) -> HashMap<usize, Vec<String>> {
+        let mut by_line: HashMap<usize, Vec<String>> = HashMap::new();
+        for diagnostic in diagnostics {
+            by_line
+                .entry(diagnostic.line.unwrap_or(0))
+                .or_default()
+                .push(format!("{}: {}", diagnostic.severity, diagnostic.message));
+        }
+        by_line
+    }
+
+    #[test]
+    fn valid_entry_has_no_diagnostics() {
+        let diagnostics = validate_with(
+            "[Desktop Entry]\nType=Application\nName=Editor\nName[de]=Editor\n\
+             Exec=editor %F\nTryExec=editor\nActions=New;\nX-Vendor-Key=1\n\n\
+             [Desktop Action New]\nName=New Window\nExec=editor --new\n",
+            |_| true,
+        );
+
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn problems_are_reported_with_their_lines() {
+        let contents = "Stray=1\n\
+                        [Desktop Entry]\n\
+                        Name[de=Kaputt\n\
+                        Exec=editor %f %U --flag=%x\n\
+                        TryExec=missing-editor\n\
+                        Terminal=yes\n\
+                        Frobnicate=1\n\
+                        Actions=New;Gone;\n\
+                        not a key value line\n\
+                        [Desktop Action New]\n\
+                        Exec=editor --new\n\
+                        [Desktop Action Extra]\n\
+                        Name=Extra\n\
+                        Exec=editor --extra\n";
+        let diagnostics = validate_with(contents, |program| program == "editor");
+        let by_line = summarize(&diagnostics);
+
+        assert_eq!(by_line[&1], ["error: `Stray` is outside of any group"]);
+        assert!(by_line[&2].contains(&"error: missing required key `Type`".to_string()));
+        assert!(by_line[&2].contains(&"error: missing required key `Name`".to_string()));
+        assert_eq!(by_line[&3], ["error: malformed locale brackets in key"]);
+        assert_eq!(
+            by_line[&4],
+            [
+                "error: unknown field code `%x`",
+                "error: only one of `%f`, `%F`, `%u`, and `%U` may be used",
+            ]
+        );
+        assert_eq!(
+            by_line[&5],
+            ["warning: TryExec program `missing-editor` is not installed"]
+        );
+        assert_eq!(
+            by_line[&6],
+            ["error: `Terminal` must be `true` or `false`, not `yes`"]
+        );
+        assert_eq!(by_line[&7], ["warning: unknown key `Frobnicate`"]);
+        assert_eq!(
+            by_line[&8],
+            ["error: action `Gone` has no [Desktop Action Gone] group"]
+        );
+        assert_eq!(by_line[&9], ["error: expected `Key=Value`"]);
+        assert_eq!(by_line[&10], ["error: missing required key `Name`"]);
+        assert_eq!(
+            by_line[&12],
+            ["warning: [Desktop Action Extra] is not listed in Actions"]
+        );
+        assert!(
+            diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.severity == Severity::Error)
+        );
+    }
+
+    #[test]
+    fn link_entries_need_a_url_instead_of_exec() {
+        let diagnostics = validate_with("[Desktop Entry]\nType=Link\nName=Docs\n", |_| true);
+        let by_line = summarize(&diagnostics);
+
+        assert_eq!(
+            by_line[&1],
+            ["error: missing required key `URL` for Type=Link"]
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Live updates: re-reading desktop files as they change on disk.
+
+use super::App;
+use super::DiscoverOptions;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc;
+
+/// The entries one desktop file contributes now; empty once it is gone.
+#[derive(Debug)]
+pub(crate) struct DesktopChange {
+    pub(crate) path: PathBuf,
+    pub(crate) apps: Vec<App>,
+}
+
+/// Watches the application directories and re-parses files that change.
+///
+/// Only available with inotify; elsewhere [`DesktopWatcher::spawn`] returns
+/// `None` and the list stays as it was at startup.
+pub(crate) struct DesktopWatcher {
+    receiver: mpsc::Receiver<Vec<DesktopChange>>,
+}
+
+impl DesktopWatcher {
+    /// Start watching `dirs`, or `None` when they cannot be watched.
+    pub(crate) fn spawn(
+        dirs: Vec<PathBuf>,
+        db: &Arc<redb::Database>,
+        options: DiscoverOptions,
+    ) -> Option<Self> {
+        let receiver = platform::spawn(dirs, Arc::clone(db), options)?;
+        Some(Self { receiver })
+    }
+
+    /// Changes that arrived since the last call, without blocking.
+    pub(crate) fn pending(&self) -> Vec<DesktopChange> {
+        self.receiver.try_iter().flatten().collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::super::discover::{
+        FileLoader, current_desktop, load_history_cache, walk_desktop_files,
+    };
+    use super::super::{DiscoverOptions, ExecAvailability};
+    use super::DesktopChange;
+    use crate::core::cache::DesktopCache;
+    use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Installers write several files in a row; let them finish first.
+    const SETTLE_DELAY: Duration = Duration::from_millis(200);
+    /// Desktop files are found up to five levels deep, so their directories
+    /// are at most four.
+    const MAX_DIR_DEPTH: usize = 4;
+
+    /// Changes noticed in one batch of inotify events.
+    #[derive(Default)]
+    struct Batch {
+        files: BTreeSet<PathBuf>,
+        created_dirs: Vec<PathBuf>,
+        removed_dirs: Vec<PathBuf>,
+        overflowed: bool,
+    }
+
+    struct Watcher {
+        inotify: Inotify,
+        watched: HashMap<WatchDescriptor, PathBuf>,
+        known: HashSet<PathBuf>,
+        dirs: Vec<PathBuf>,
+        db: Arc<redb::Database>,
+        options: DiscoverOptions,
+        current_desktop: Option<Vec<String>>,
+    }
+
+    pub(super) fn spawn(
+        dirs: Vec<PathBuf>,
+        db: Arc<redb::Database>,
+        options: DiscoverOptions,
+    ) -> Option<mpsc::Receiver<Vec<DesktopChange>>> {
+        let mut watcher = Watcher {
+            inotify: Inotify::init().ok()?,
+            watched: HashMap::new(),
+            known: HashSet::new(),
+            current_desktop: current_desktop(options.filter_desktop),
+            dirs,
+            db,
+            options,
+        };
+        // Watches go in before the caller scans, so nothing slips between the two.
+        for dir in watcher.dirs.clone() {
+            watcher.watch_tree(&dir, 0);
+        }
+        if watcher.watched.is_empty() {
+            return None;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || watcher.run(&sender));
+        Some(receiver)
+    }
+
+    impl Watcher {
+        fn run(mut self, sender: &mpsc::Sender<Vec<DesktopChange>>) {
+            self.known = walk_desktop_files(&self.dirs).into_iter().collect();
+            let mut buffer = [0u8; 4096];
+
+            loop {
+                let mut batch = Batch::default();
+                match self.inotify.read_events_blocking(&mut buffer) {
+                    Ok(events) => self.collect(events, &mut batch),
+                    Err(_) => return,
+                }
+                thread::sleep(SETTLE_DELAY);
+                while let Ok(events) = self.inotify.read_events(&mut buffer) {
+                    let mut events = events.peekable();
+                    if events.peek().is_none() {
+                        break;
+                    }
+                    self.collect(events, &mut batch);
+                }
+
+                let changes = self.apply(batch);
+                if !changes.is_empty() && sender.send(changes).is_err() {
+                    return;
+                }
+            }
+        }
+
+        fn collect<'a>(
+            &mut self,
+            events: impl Iterator<Item = inotify::Event<&'a std::ffi::OsStr>>,
+            batch: &mut Batch,
+        ) {
+            for event in events {
+                if event.mask.contains(EventMask::Q_OVERFLOW) {
+                    batch.overflowed = true;
+                    continue;
+                }
+                if event.mask.contains(EventMask::IGNORED) {
+                    self.watched.remove(&event.wd);
+                    continue;
+                }
+                let (Some(dir), Some(name)) = (self.watched.get(&event.wd), event.name) else {
+                    continue;
+                };
+                let path = dir.join(name);
+
+                if event.mask.contains(EventMask::ISDIR) {
+                    if event
+                        .mask
+                        .intersects(EventMask::CREATE | EventMask::MOVED_TO)
+                    {
+                        batch.created_dirs.push(path);
+                    } else if event
+                        .mask
+                        .intersects(EventMask::DELETE | EventMask::MOVED_FROM)
+                    {
+                        batch.removed_dirs.push(path);
+                    }
+                } else if path.extension().and_then(|ext| ext.to_str()) == Some("desktop") {
+                    batch.files.insert(path);
+                }
+            }
+        }
+
+        fn apply(&mut self, mut batch: Batch) -> Vec<DesktopChange> {
+            if batch.overflowed {
+                batch.files.extend(self.known.iter().cloned());
+                batch.files.extend(walk_desktop_files(&self.dirs));
+            }
+            for dir in &batch.removed_dirs {
+                let removed = self.known.iter().filter(|path| path.starts_with(dir));
+                batch.files.extend(removed.cloned());
+            }
+            for dir in &batch.created_dirs {
+                let depth = self.depth_of(dir);
+                self.watch_tree(dir, depth);
+                batch
+                    .files
+                    .extend(walk_desktop_files(std::slice::from_ref(dir)));
+            }
+            if batch.files.is_empty() {
+                return Vec::new();
+            }
+
+            let desktop_cache = DesktopCache::new(Arc::clone(&self.db)).ok();
+            let history_cache = load_history_cache(&self.db);
+            let availability = ExecAvailability::load(&self.db, self.options.exec_check);
+            let loader = FileLoader {
+                dirs: &self.dirs,
+                // Always re-read: an edit within the same second keeps the mtime.
+                desktop_cache: None,
+                history_cache: &history_cache,
+                availability: &availability,
+                current_desktop: self.current_desktop.as_deref(),
+                options: self.options,
+            };
+
+            let mut changes = Vec::new();
+            let mut apps_to_cache = Vec::new();
+            for path in batch.files {
+                let entries = path.is_file().then(|| loader.load(&path)).flatten();
+                let was_known = if path.is_file() {
+                    !self.known.insert(path.clone())
+                } else {
+                    self.known.remove(&path)
+                };
+                let (apps, parsed) = entries
+                    .map(|entries| (entries.apps, entries.parsed))
+                    .unwrap_or_default();
+                if let Some(app) = parsed {
+                    apps_to_cache.push((path.clone(), app));
+                }
+                if was_known || !apps.is_empty() {
+                    changes.push(DesktopChange { path, apps });
+                }
+            }
+            availability.save();
+
+            if !apps_to_cache.is_empty()
+                && let Some(cache) = desktop_cache
+            {
+                let _ = cache.batch_set(apps_to_cache);
+            }
+            changes
+        }
+
+        fn watch_tree(&mut self, dir: &Path, depth: usize) {
+            let mask = WatchMask::CREATE
+                | WatchMask::CLOSE_WRITE
+                | WatchMask::MOVED_TO
+                | WatchMask::MOVED_FROM
+                | WatchMask::DELETE
+                | WatchMask::ONLYDIR;
+            let Ok(descriptor) = self.inotify.watches().add(dir, mask) else {
+                return;
+            };
+            self.watched.insert(descriptor, dir.to_path_buf());
+
+            if depth >= MAX_DIR_DEPTH {
+                return;
+            }
+            let Ok(entries) = fs::read_dir(dir) else {
+                return;
+            };
+            for entry in entries.filter_map(Result::ok) {
+                if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                    self.watch_tree(&entry.path(), depth + 1);
+                }
+            }
+        }
+
+        fn depth_of(&self, dir: &Path) -> usize {
+            self.dirs
+                .iter()
+                .filter_map(|root| dir.strip_prefix(root).ok())
+                .map(|relative| relative.components().count())
+                .min()
+                .unwrap_or(0)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use super::DesktopChange;
+    use crate::desktop::DiscoverOptions;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::sync::mpsc;
+
+    pub(super) fn spawn(
+        _dirs: Vec<PathBuf>,
+        _db: Arc<redb::Database>,
+        _options: DiscoverOptions,
+    ) -> Option<mpsc::Receiver<Vec<DesktopChange>>> {
+        None
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::{DesktopChange, DesktopWatcher};
+    use crate::desktop::DiscoverOptions;
+    use crate::testing::temp_dir;
+    use std::fs;
+
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn wait_for_change(watcher: &DesktopWatcher) -> Vec<DesktopChange> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            let changes = watcher.pending();
+            if !changes.is_empty() {
+                return changes;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        Vec::new()
+    }
+
+    #[test]
+    fn added_and_removed_files_are_reported() {
+        let root = temp_dir("watch-add-remove");
+        let apps_dir = root.join("applications");
+        fs::create_dir_all(&apps_dir).expect("applications dir should be created");
+        let db = Arc::new(
+            redb::Database::create(root.join("cache.redb")).expect("database should be created"),
+        );
+        let watcher =
+            DesktopWatcher::spawn(vec![apps_dir.clone()], &db, DiscoverOptions::default())
+                .expect("inotify should be available");
+
+        let vendor = apps_dir.join("vendor");
+        fs::create_dir_all(&vendor).expect("vendor dir should be created");
+        let desktop_path = vendor.join("editor.desktop");
+        fs::write(
+            &desktop_path,
+            "[Desktop Entry]\nType=Application\nName=Editor\nExec=/bin/true\n",
+        )
+        .expect("desktop entry should be written");
+
+        let changes = wait_for_change(&watcher);
+        let change = changes
+            .iter()
+            .find(|change| change.path == desktop_path)
+            .expect("new entry should be reported");
+        assert_eq!(change.apps.len(), 1);
+        assert_eq!(
+            change.apps[0].desktop_id.as_deref(),
+            Some("vendor-editor.desktop")
+        );
+
+        fs::remove_file(&desktop_path).expect("desktop entry should be removed");
+        let changes = wait_for_change(&watcher);
+        assert!(
+            changes
+                .iter()
+                .any(|change| change.path == desktop_path && change.apps.is_empty())
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
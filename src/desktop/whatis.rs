@@ -0,0 +1,257 @@
+//! One-line manual page summaries (`whatis`) for `$PATH` executables.
+
+use crate::core::cache::ManCache;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Names per `man -f` call, well below any argument length limit.
+const NAMES_PER_LOOKUP: usize = 256;
+
+/// Where man-db keeps the whatis databases of the system hierarchies.
+const MAN_DB_CACHE_DIR: &str = "/var/cache/man";
+
+/// Manual hierarchies searched when `$MANPATH` is unset.
+const DEFAULT_MAN_DIRS: &[&str] = &["/usr/share/man", "/usr/local/share/man", "/usr/local/man"];
+
+/// Summaries for the executables in `paths` that have a manual page.
+///
+/// Results are cached per path and mtime, so `man -f` only runs for
+/// executables that are new or changed since the last scan, or that had no
+/// page before the whatis index last changed.
+pub(crate) fn summaries(db: &Arc<redb::Database>, paths: &[PathBuf]) -> HashMap<PathBuf, String> {
+    let files: Vec<_> = paths
+        .iter()
+        .filter_map(|path| {
+            let mtime = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+            Some((path.clone(), mtime))
+        })
+        .collect();
+    let stamp = index_stamp(Path::new(MAN_DB_CACHE_DIR), &man_dirs());
+    let cache = ManCache::new(Arc::clone(db)).ok();
+    let mut known = cache
+        .as_ref()
+        .and_then(|cache| cache.get_many(&files, stamp).ok())
+        .unwrap_or_default();
+
+    let missing: Vec<_> = files
+        .into_iter()
+        .filter(|(path, _)| !known.contains_key(path))
+        .collect();
+    let names: Vec<String> = missing
+        .iter()
+        .filter_map(|(path, _)| path.file_name()?.to_str().map(str::to_string))
+        .collect();
+    // Without a working `man`, nothing is cached so a later install is noticed.
+    if !missing.is_empty()
+        && let Some(found) = lookup(&names)
+    {
+        let entries: Vec<_> = missing
+            .into_iter()
+            .map(|(path, mtime)| {
+                let summary = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| found.get(name).cloned());
+                known.insert(path.clone(), summary.clone());
+                (path, mtime, summary)
+            })
+            .collect();
+        if let Some(cache) = &cache {
+            let _ = cache.store_many(entries, stamp);
+        }
+    }
+
+    known
+        .into_iter()
+        .filter_map(|(path, summary)| Some((path, summary?)))
+        .collect()
+}
+
+/// Manual hierarchies from `$MANPATH`, or the usual system ones.
+fn man_dirs() -> Vec<PathBuf> {
+    let from_env: Vec<PathBuf> = std::env::var_os("MANPATH")
+        .map(|value| {
+            std::env::split_paths(&value)
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if from_env.is_empty() {
+        DEFAULT_MAN_DIRS.iter().map(PathBuf::from).collect()
+    } else {
+        from_env
+    }
+}
+
+/// The newest modification among the whatis indexes and the command sections
+/// they cover, or `None` when there are none.
+///
+/// man-db keeps `index.*` databases in `cache_dir` and its per-locale or
+/// per-hierarchy subdirectories, mandoc keeps `mandoc.db` in each hierarchy.
+/// The `man1`/`man6`/`man8` directories change as soon as a page is installed,
+/// before any index catches up.
+fn index_stamp(cache_dir: &Path, man_dirs: &[PathBuf]) -> Option<SystemTime> {
+    let mut candidates = index_files(cache_dir);
+    if let Ok(entries) = fs::read_dir(cache_dir) {
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                candidates.extend(index_files(&entry.path()));
+            }
+        }
+    }
+    for dir in man_dirs {
+        candidates.push(dir.join("mandoc.db"));
+        candidates.push(dir.join("index.db"));
+        for section in ["man1", "man6", "man8"] {
+            candidates.push(dir.join(section));
+        }
+    }
+
+    candidates
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .max()
+}
+
+/// man-db database files (`index.db`, `index.bt`, …) directly inside `dir`.
+fn index_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("index."))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Runs `man -f` over `names`; `None` when `man` cannot be run at all.
+fn lookup(names: &[String]) -> Option<HashMap<String, String>> {
+    let mut found = HashMap::new();
+    for chunk in names.chunks(NAMES_PER_LOOKUP) {
+        // Exits non-zero when any name has no page; the rest are still printed.
+        let output = Command::new("man")
+            .arg("-f")
+            .args(chunk)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        for (name, summary) in parse_whatis(&String::from_utf8_lossy(&output.stdout)) {
+            found.entry(name).or_insert(summary);
+        }
+    }
+    Some(found)
+}
+
+/// Parses `name (section) - summary` lines, preferring user commands (1), then
+/// system commands (8) and games (6) when a name has several pages.
+fn parse_whatis(output: &str) -> HashMap<String, String> {
+    let mut best: HashMap<String, (u8, String)> = HashMap::new();
+    for line in output.lines() {
+        let Some((page, summary)) = line.split_once(" - ") else {
+            continue;
+        };
+        let page = page.trim_end();
+        let Some((name, section)) = page
+            .strip_suffix(')')
+            .and_then(|page| page.rsplit_once('('))
+        else {
+            continue;
+        };
+        let (name, summary) = (name.trim(), summary.trim());
+        if name.is_empty() || summary.is_empty() {
+            continue;
+        }
+
+        let rank = match section.chars().next() {
+            Some('1') => 0,
+            Some('8') => 1,
+            Some('6') => 2,
+            _ => 3,
+        };
+        match best.get(name) {
+            Some((best_rank, _)) if *best_rank <= rank => {}
+            _ => {
+                best.insert(name.to_string(), (rank, summary.to_string()));
+            }
+        }
+    }
+
+    best.into_iter()
+        .map(|(name, (_, summary))| (name, summary))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{index_stamp, parse_whatis};
+    use crate::testing::temp_dir;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn whatis_output_prefers_command_sections() {
+        let parsed = parse_whatis(
+            "printf (3)           - formatted output conversion\n\
+             printf (1)           - format and print data\n\
+             ls (1)               - list directory contents\n\
+             mount (8)            - mount a filesystem\n\
+             mount (2)            - mount filesystem\n\
+             tool: nothing appropriate.\n\
+             [ (1)                - check file types and compare values\n",
+        );
+
+        assert_eq!(parsed["printf"], "format and print data");
+        assert_eq!(parsed["ls"], "list directory contents");
+        assert_eq!(parsed["mount"], "mount a filesystem");
+        assert_eq!(parsed["["], "check file types and compare values");
+        assert!(!parsed.contains_key("tool"));
+    }
+
+    #[test]
+    fn index_stamp_follows_the_newest_index_or_section() {
+        let dir = temp_dir("whatis-index-stamp");
+        let (cache_dir, man_dir) = (dir.join("cache"), dir.join("man"));
+        fs::create_dir_all(cache_dir.join("fr")).expect("cache dir should be created");
+        fs::create_dir_all(man_dir.join("man1")).expect("man dir should be created");
+        let set_mtime = |path: &std::path::Path, secs: u64| {
+            fs::File::options()
+                .append(true)
+                .create(true)
+                .open(path)
+                .and_then(|file| {
+                    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                })
+                .expect("mtime should be set");
+        };
+        let stamp = || index_stamp(&cache_dir, std::slice::from_ref(&man_dir));
+
+        fs::File::open(man_dir.join("man1"))
+            .and_then(|dir| dir.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(10)))
+            .expect("section mtime should be set");
+        assert_eq!(
+            stamp(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(10))
+        );
+
+        set_mtime(&cache_dir.join("fr").join("index.db"), 20);
+        set_mtime(&cache_dir.join("unrelated"), 30);
+        assert_eq!(
+            stamp(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(20))
+        );
+
+        set_mtime(&man_dir.join("mandoc.db"), 40);
+        assert_eq!(
+            stamp(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(40))
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
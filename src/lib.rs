@@ -13,6 +13,8 @@ mod desktop;
 mod modes;
 mod platform;
 mod strings;
+#[cfg(test)]
+mod testing;
 mod ui;
 
 /// Runs the application entrypoint and dispatches to the selected mode.
@@ -12,7 +12,7 @@ pub(crate) fn handle_maintenance_command(
 ) -> Result<bool> {
     if cli.clear_history {
         clear_history(db)?;
-        println!("Launch history and pins cleared successfully!");
+        println!("Launch history, pins, and learned queries cleared successfully!");
         println!(
             "To fully remove the database, delete {}",
             data_dir.display()
@@ -23,6 +23,15 @@ pub(crate) fn handle_maintenance_command(
     if cli.clear_cache {
         let cache = crate::core::cache::DesktopCache::new(Arc::clone(db))?;
         cache.clear().wrap_err("Error clearing cache")?;
+        crate::core::cache::IconCache::new(Arc::clone(db))?
+            .clear()
+            .wrap_err("Error clearing icon cache")?;
+        crate::core::cache::ExecCache::new(Arc::clone(db))?
+            .clear()
+            .wrap_err("Error clearing program lookup cache")?;
+        crate::core::cache::ManCache::new(Arc::clone(db))?
+            .clear()
+            .wrap_err("Error clearing manual summary cache")?;
         println!("Desktop file cache cleared successfully!");
         return Ok(true);
     }
@@ -139,6 +148,12 @@ fn clear_history(db: &redb::Database) -> Result<()> {
     {
         let mut history_table = write_txn.open_table(crate::core::cache::HISTORY_TABLE)?;
         let mut pinned_table = write_txn.open_table(crate::core::cache::PINNED_TABLE)?;
+        let mut selections_table =
+            write_txn.open_table(crate::core::cache::QUERY_SELECTIONS_TABLE)?;
+        let mut launch_hours_table =
+            write_txn.open_table(crate::core::cache::LAUNCH_HOURS_TABLE)?;
+        let mut unresolved_table =
+            write_txn.open_table(crate::core::cache::UNRESOLVED_NAMES_TABLE)?;
 
         let history_keys: Vec<String> = history_table
             .iter()?
@@ -148,6 +163,18 @@ fn clear_history(db: &redb::Database) -> Result<()> {
             .iter()?
             .map(|result| result.map(|(key, _)| key.value().to_string()))
             .collect::<Result<_, _>>()?;
+        let queries: Vec<String> = selections_table
+            .iter()?
+            .map(|result| result.map(|(query, _)| query.value().to_string()))
+            .collect::<Result<_, _>>()?;
+        let launch_hours_keys: Vec<String> = launch_hours_table
+            .iter()?
+            .map(|result| result.map(|(key, _)| key.value().to_string()))
+            .collect::<Result<_, _>>()?;
+        let unresolved_names: Vec<String> = unresolved_table
+            .iter()?
+            .map(|result| result.map(|(name, _)| name.value().to_string()))
+            .collect::<Result<_, _>>()?;
 
         for key in history_keys {
             history_table.remove(key.as_str())?;
@@ -155,6 +182,15 @@ fn clear_history(db: &redb::Database) -> Result<()> {
         for key in pinned_keys {
             pinned_table.remove(key.as_str())?;
         }
+        for query in queries {
+            selections_table.remove(query.as_str())?;
+        }
+        for key in launch_hours_keys {
+            launch_hours_table.remove(key.as_str())?;
+        }
+        for name in unresolved_names {
+            unresolved_table.remove(name.as_str())?;
+        }
     }
     write_txn.commit().wrap_err("Error clearing database")?;
 
@@ -201,7 +237,26 @@ mod tests {
             ))
             .expect("record should be inserted");
 
+        crate::core::database::record_selection(&db, "ex", "v1:config:Example")
+            .expect("selection should record");
+        crate::core::database::mark_unresolved_name(&db, "Gone")
+            .expect("unresolved name should record");
+        crate::core::database::record_launch_hour(
+            &db,
+            "v1:config:Example",
+            crate::core::ranking::current_unix_seconds(),
+        )
+        .expect("launch should record");
+        assert!(!crate::core::database::load_launch_hours(&db).is_empty());
+
         clear_history(&db).expect("history should clear");
+        assert!(
+            crate::core::database::load_query_selections(&db)
+                .shares_for("ex")
+                .is_empty()
+        );
+        assert!(crate::core::database::load_launch_hours(&db).is_empty());
+        assert!(crate::core::database::load_unresolved_names(&db).is_empty());
         crate::core::cache::DesktopCache::new(Arc::clone(&db))
             .expect("cache should initialize")
             .clear()
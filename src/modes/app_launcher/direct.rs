@@ -20,24 +20,17 @@ pub(crate) fn launch_program_directly(
     let hidden_store = HiddenEntryStore::new(std::sync::Arc::clone(db))?;
     let hidden_entry_keys = hidden_store.entry_keys()?;
 
-    if !cli.auto_hide_duplicates
-        && let Some(app) = find_history_exact_name_match(
-            db,
-            &history_cache,
-            &hidden_entry_keys,
-            program_name,
-            cli,
-        )?
-    {
-        return launch_or_print(cli, db, &app);
-    }
+    if !cli.auto_hide_duplicates {
+        let launched = launched_apps(db, &history_cache, &hidden_entry_keys, cli, program_name)?;
+        if let Some(app) = find_history_exact_name_match(&launched, program_name) {
+            return launch_or_print(cli, db, app);
+        }
 
-    if !cli.auto_hide_duplicates
-        && matches!(cli.match_mode, cli::MatchMode::Fuzzy)
-        && let Some(app) =
-            find_history_best_match(db, &history_cache, &hidden_entry_keys, program_name, cli)?
-    {
-        return launch_or_print(cli, db, &app);
+        if matches!(cli.match_mode, cli::MatchMode::Fuzzy)
+            && let Some(app) = find_history_best_match(&launched, program_name)
+        {
+            return launch_or_print(cli, db, app);
+        }
     }
 
     let (all_apps, hidden_summary) = load_available_apps(db, cli, &hidden_entry_keys);
@@ -80,49 +73,82 @@ fn launch_or_print(
     app: &desktop::App,
 ) -> Result<()> {
     if cli.no_exec {
-        println!("{}", app.command);
-        return Ok(());
+        return super::launch::print_commands(app, cli);
     }
 
     super::launch::launch_app(app, cli, db)
 }
 
-fn find_history_exact_name_match(
+/// Launchable entries from the launch history that `program_name` could pick,
+/// most launched first.
+///
+/// Only rows named `program_name` (or starting with it, for fuzzy matching) are
+/// resolved. Name-keyed rows that match no entry are remembered, since finding
+/// that out takes a scan of every application directory.
+fn launched_apps(
     db: &std::sync::Arc<redb::Database>,
     history_cache: &cache::HistoryCache,
     hidden_entry_keys: &HashSet<EntryKey>,
-    program_name: &str,
     cli: &cli::Opts,
-) -> Result<Option<desktop::App>> {
+    program_name: &str,
+) -> Result<Vec<desktop::App>> {
     let program_name_lower = program_name.to_lowercase();
+    let name_matches = |name: &str| {
+        let name = name.to_lowercase();
+        match cli.match_mode {
+            cli::MatchMode::Fuzzy => name.starts_with(&program_name_lower),
+            _ => name == program_name_lower,
+        }
+    };
 
-    for app_name in history_cache.history.keys() {
-        if app_name.to_lowercase() == program_name_lower
-            && let Some(app) =
-                super::search::find_app_by_name_fast(db, app_name, cli, hidden_entry_keys)?
-        {
-            return Ok(Some(app));
+    let mut keys = Vec::new();
+    let mut legacy_names = Vec::new();
+    for key in history_cache.history.keys() {
+        match EntryKey::parse(key) {
+            Some(entry_key) => keys.push(entry_key),
+            // Rows the launcher has not migrated yet are still keyed by name.
+            None if name_matches(key) => legacy_names.push(key),
+            None => {}
         }
     }
 
-    Ok(None)
+    let mut apps =
+        super::search::find_apps_by_keys(db, &keys, cli, hidden_entry_keys, name_matches)?;
+    if !legacy_names.is_empty() {
+        let unresolved = crate::core::database::load_unresolved_names(db);
+        for name in legacy_names {
+            if unresolved.contains(name) {
+                continue;
+            }
+            match super::search::find_app_by_name_fast(db, name, cli, hidden_entry_keys)? {
+                Some(app) => apps.push(app),
+                None => crate::core::database::mark_unresolved_name(db, name)?,
+            }
+        }
+    }
+    apps.retain(|app| matches_category(app, cli));
+    apps.sort_by(|a, b| b.history.cmp(&a.history).then_with(|| a.name.cmp(&b.name)));
+    Ok(apps)
 }
 
-fn find_history_best_match(
-    db: &std::sync::Arc<redb::Database>,
-    history_cache: &cache::HistoryCache,
-    hidden_entry_keys: &HashSet<EntryKey>,
+fn find_history_exact_name_match<'a>(
+    launched: &'a [desktop::App],
     program_name: &str,
-    cli: &cli::Opts,
-) -> Result<Option<desktop::App>> {
-    if let Some((app_name, _)) = history_cache.get_best_match(program_name)
-        && let Some(app) =
-            super::search::find_app_by_name_fast(db, app_name, cli, hidden_entry_keys)?
-    {
-        return Ok(Some(app));
-    }
+) -> Option<&'a desktop::App> {
+    let program_name_lower = program_name.to_lowercase();
+    launched
+        .iter()
+        .find(|app| app.name.to_lowercase() == program_name_lower)
+}
 
-    Ok(None)
+fn find_history_best_match<'a>(
+    launched: &'a [desktop::App],
+    program_name: &str,
+) -> Option<&'a desktop::App> {
+    let program_name_lower = program_name.to_lowercase();
+    launched
+        .iter()
+        .find(|app| app.name.to_lowercase().starts_with(&program_name_lower))
 }
 
 fn load_available_apps(
@@ -136,12 +162,14 @@ fn load_available_apps(
     let application_dirs = desktop::application_dirs();
     let apps_receiver = desktop::read_with_options(
         application_dirs.clone(),
+        cli.config_entries.clone(),
         db,
         desktop::DiscoverOptions {
             filter_desktop: cli.filter_desktop,
             filter_actions: cli.filter_actions,
             list_executables: cli.list_executables_in_path,
             auto_hide_duplicates: cli.auto_hide_duplicates,
+            exec_check: cli.exec_check,
         },
     );
 
@@ -149,6 +177,12 @@ fn load_available_apps(
     while let Ok(app) = apps_receiver.recv() {
         all_apps.push(app);
     }
+    if let Err(error) =
+        crate::core::database::migrate_name_keys(db, &mut all_apps, &application_dirs)
+    {
+        eprintln!("Warning: Failed to migrate launch history: {}", error);
+    }
+    all_apps.retain(|app| matches_category(app, cli));
     crate::core::hidden_entries::eligible_apps(
         &all_apps,
         hidden_entry_keys,
@@ -159,6 +193,12 @@ fn load_available_apps(
     )
 }
 
+fn matches_category(app: &desktop::App, cli: &cli::Opts) -> bool {
+    cli.category
+        .as_deref()
+        .is_none_or(|category| crate::core::state::in_category(app, category))
+}
+
 fn confirm_first_launch(app_name: &str) -> Result<bool> {
     eprint!("Launch {} [Y/n]? ", app_name);
     io::stderr().flush()?;
@@ -177,7 +217,28 @@ fn select_match_for_mode(
     match match_mode {
         cli::MatchMode::Exact => select_exact_match(apps, program_name),
         cli::MatchMode::Fuzzy => select_best_match(apps, program_name),
+        cli::MatchMode::Extended => select_extended_match(apps, program_name),
+    }
+}
+
+fn select_extended_match(apps: Vec<desktop::App>, program_name: &str) -> Option<desktop::App> {
+    let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+    let query = crate::core::ranking::ExtendedQuery::parse(program_name);
+    let mut best_app: Option<(desktop::App, i64)> = None;
+
+    for app in apps {
+        let exec_name = strings::extract_exec_name(&app.command);
+        let Some(matched) = query.match_fields(&[&app.name, exec_name], &mut matcher) else {
+            continue;
+        };
+        let score = apply_rank_boosts(i64::from(matched.score), &app);
+        match &best_app {
+            Some((_, current_best_score)) if score <= *current_best_score => {}
+            _ => best_app = Some((app, score)),
+        }
     }
+
+    best_app.map(|(app, _)| app)
 }
 
 fn select_best_match(apps: Vec<desktop::App>, program_name: &str) -> Option<desktop::App> {
@@ -294,10 +355,15 @@ fn apply_rank_boosts(mut final_score: i64, app: &desktop::App) -> i64 {
 
 #[cfg(test)]
 mod tests {
-    use super::{score_candidate, select_match_for_mode};
-    use crate::cli::MatchMode;
+    use super::{launched_apps, score_candidate, select_match_for_mode};
+    use crate::cli::{MatchMode, Opts};
+    use crate::core::cache::HistoryCache;
+    use crate::core::database::{load_unresolved_names, migrate_name_keys};
     use crate::desktop::App;
     use nucleo_matcher::{Config, Matcher};
+    use std::collections::HashSet;
+    use std::path::Path;
+    use std::sync::Arc;
 
     fn app(name: &str, command: &str) -> App {
         App::parse(
@@ -309,6 +375,52 @@ mod tests {
         .expect("test desktop entry should parse")
     }
 
+    #[test]
+    fn history_lookup_skips_other_names_and_remembers_orphans() {
+        let dir = crate::testing::temp_dir("direct-orphans");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+        let write_txn = db.begin_write().expect("write should begin");
+        {
+            let mut history = write_txn
+                .open_table(crate::core::cache::HISTORY_TABLE)
+                .expect("history table should open");
+            history
+                .insert("FselOrphanAlpha", 3)
+                .expect("history should insert");
+            history
+                .insert("FselOrphanBeta", 1)
+                .expect("history should insert");
+        }
+        write_txn.commit().expect("write should commit");
+
+        let cli = Opts {
+            match_mode: MatchMode::Fuzzy,
+            ..Opts::default()
+        };
+        let lookup = |program_name: &str| {
+            let history_cache = HistoryCache::load(&db).expect("history should load");
+            launched_apps(&db, &history_cache, &HashSet::new(), &cli, program_name)
+                .expect("lookup should succeed")
+        };
+
+        assert!(lookup("FselOrphanA").is_empty());
+        assert_eq!(
+            load_unresolved_names(&db),
+            HashSet::from(["FselOrphanAlpha".to_string()])
+        );
+        assert!(lookup("FselOrphanA").is_empty());
+
+        let mut alpha = app("FselOrphanAlpha", "true");
+        alpha.desktop_id = Some("alpha.desktop".to_string());
+        alpha.set_source_path(Path::new("/usr/share/applications/alpha.desktop"));
+        migrate_name_keys(&db, &mut [alpha], &[]).expect("migration should succeed");
+        assert!(load_unresolved_names(&db).is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
     #[test]
     fn exact_name_match_beats_other_candidates() {
         let selected = select_match_for_mode(
@@ -366,4 +478,20 @@ mod tests {
 
         assert_eq!(selected.name, "Steam Store");
     }
+
+    #[test]
+    fn extended_mode_applies_anchors_and_negation_to_program_launch() {
+        let apps = || {
+            vec![
+                app("Firefox Nightly", "firefox-nightly"),
+                app("Firefox", "firefox"),
+                app("Thunderbird", "thunderbird"),
+            ]
+        };
+
+        let selected = select_match_for_mode(apps(), "^fire !nightly", MatchMode::Extended)
+            .expect("extended query should select an app");
+        assert_eq!(selected.name, "Firefox");
+        assert!(select_match_for_mode(apps(), "^bird", MatchMode::Extended).is_none());
+    }
 }
@@ -1,7 +1,8 @@
-use crate::cli::Opts;
+use crate::cli::{Opts, PinnedOrderMode};
 use crate::core::hidden_entries::{HiddenEntryStore, NewHiddenEntry};
 use crate::core::ranking::current_unix_seconds;
 use crate::core::state::{Message, State};
+use crate::desktop::EntryKind;
 use crate::ui::InputEvent as Event;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
@@ -15,7 +16,7 @@ pub(crate) fn handle_event(
 ) {
     match event {
         Event::Input(key) => handle_key_event(state, key, cli, db, hidden_store, total_height),
-        Event::Mouse(mouse_event) => handle_mouse_event(state, mouse_event, cli, total_height),
+        Event::Mouse(mouse_event) => handle_mouse_event(state, mouse_event, cli, db, total_height),
         Event::Tick | Event::Render => {}
     }
 }
@@ -31,17 +32,34 @@ fn handle_key_event(
     let max_visible = max_visible_items(total_height, cli);
     state.clear_status_message();
 
+    if state.category_picker().is_some() && handle_category_picker_key(state, key, cli) {
+        refresh_info(state, cli);
+        return;
+    }
+
     let msg = if cli.keybinds.matches_exit(key.code, key.modifiers) {
         Message::Exit
     } else if cli.keybinds.matches_select(key.code, key.modifiers) {
+        if open_selected_directory(state, db) {
+            refresh_info(state, cli);
+            return;
+        }
         Message::Select
     } else if cli.keybinds.matches_up(key.code, key.modifiers) {
         Message::MoveUp
     } else if cli.keybinds.matches_down(key.code, key.modifiers) {
         Message::MoveDown
     } else if cli.keybinds.matches_left(key.code, key.modifiers) {
+        if state.leave_actions() {
+            refresh_info(state, cli);
+            return;
+        }
         Message::MoveUp
     } else if cli.keybinds.matches_right(key.code, key.modifiers) {
+        if open_selected_actions(state, cli, db) {
+            refresh_info(state, cli);
+            return;
+        }
         Message::MoveDown
     } else if cli.keybinds.matches_backspace(key.code, key.modifiers) {
         Message::Backspace
@@ -49,6 +67,14 @@ fn handle_key_event(
         toggle_selected_pin(state, db);
         refresh_info(state, cli);
         return;
+    } else if cli.keybinds.matches_move_pin_up(key.code, key.modifiers) {
+        move_selected_pin(state, db, true);
+        refresh_info(state, cli);
+        return;
+    } else if cli.keybinds.matches_move_pin_down(key.code, key.modifiers) {
+        move_selected_pin(state, db, false);
+        refresh_info(state, cli);
+        return;
     } else if cli.keybinds.matches_hide(key.code, key.modifiers) {
         match hide_selected_entry(state, hidden_store) {
             Ok(Some(name)) => {
@@ -61,6 +87,12 @@ fn handle_key_event(
         }
         refresh_info(state, cli);
         return;
+    } else if cli.keybinds.matches_category(key.code, key.modifiers) {
+        if state.action_parent().is_none() {
+            state.toggle_category_picker();
+        }
+        refresh_info(state, cli);
+        return;
     } else if cli.keybinds.matches_unhide_last(key.code, key.modifiers) {
         match unhide_last_entry(state, hidden_store) {
             Ok(Some((name, true))) => state.set_status_message(format!("Restored {name}")),
@@ -101,7 +133,28 @@ fn handle_key_event(
     refresh_info(state, cli);
 }
 
-fn handle_mouse_event(state: &mut State, mouse_event: MouseEvent, cli: &Opts, total_height: u16) {
+/// Keys the open category strip takes over: Left and Right pick a category,
+/// Enter or Esc close the strip. Returns whether `key` was one of them.
+fn handle_category_picker_key(state: &mut State, key: KeyEvent, cli: &Opts) -> bool {
+    if cli.keybinds.matches_left(key.code, key.modifiers) {
+        state.move_category_picker(false);
+    } else if cli.keybinds.matches_right(key.code, key.modifiers) {
+        state.move_category_picker(true);
+    } else if key.code == KeyCode::Esc || cli.keybinds.matches_select(key.code, key.modifiers) {
+        state.close_category_picker();
+    } else {
+        return false;
+    }
+    true
+}
+
+fn handle_mouse_event(
+    state: &mut State,
+    mouse_event: MouseEvent,
+    cli: &Opts,
+    db: &std::sync::Arc<redb::Database>,
+    total_height: u16,
+) {
     let metrics = list_metrics(total_height, cli);
 
     let msg = match mouse_event.kind {
@@ -113,6 +166,10 @@ fn handle_mouse_event(state: &mut State, mouse_event: MouseEvent, cli: &Opts, to
                     cli.hard_stop,
                     metrics.max_visible,
                 );
+                if open_selected_directory(state, db) {
+                    refresh_info(state, cli);
+                    return;
+                }
                 Message::Select
             } else {
                 Message::Tick
@@ -165,29 +222,113 @@ fn toggle_selected_pin(state: &mut State, db: &std::sync::Arc<redb::Database>) {
         return;
     };
 
-    let Ok(is_pinned) = crate::core::database::toggle_pin(db, &app.name) else {
+    let key = app.store_key();
+    let Ok(is_pinned) = crate::core::database::toggle_pin(db, &key) else {
         return;
     };
 
-    for entry in &mut state.apps {
-        if entry.name == app.name {
+    for entry in state.all_apps_mut() {
+        if entry.store_key() == key {
             entry.pinned = is_pinned;
         }
     }
 
-    let frecency_data = crate::core::database::load_frecency(db);
+    state.frecency_data = crate::core::database::load_frecency(db);
     state.pin_timestamps = crate::core::database::load_pin_timestamps(db);
-    crate::core::ranking::sort_by_ranking(
-        &mut state.apps,
-        &frecency_data,
-        state.ranking_mode,
-        state.pinned_order_mode,
-        &state.pin_timestamps,
-        current_unix_seconds(),
-    );
+    state.pin_order = crate::core::database::load_pin_order(db);
+    state.resort(current_unix_seconds());
     state.refresh_visibility();
 }
 
+/// Swap the selected pinned app with the pinned app shown above or below it in
+/// the manual pinned order.
+fn move_selected_pin(state: &mut State, db: &std::sync::Arc<redb::Database>, up: bool) {
+    if state.pinned_order_mode != PinnedOrderMode::Manual {
+        state.set_status_message("Set pinned_order = \"manual\" to reorder pinned apps");
+        return;
+    }
+    let Some(index) = state.selected else {
+        return;
+    };
+    let Some(app) = state.shown.get(index) else {
+        return;
+    };
+    if !app.pinned {
+        state.set_status_message("Only pinned apps can be reordered");
+        return;
+    }
+    let key = app.store_key();
+    let Some(neighbour) = state.pin_neighbour(&key, up) else {
+        return;
+    };
+
+    if let Err(error) = crate::core::database::swap_pins(db, &key, neighbour) {
+        state.set_status_message(format!("Could not reorder pins: {error}"));
+        return;
+    }
+    state.set_pin_order(crate::core::database::load_pin_order(db));
+}
+
+/// Drill into the selected entry's desktop actions.
+///
+/// Returns `false` when the entry has none, so the key falls back to moving the
+/// selection like it always has.
+fn open_selected_actions(
+    state: &mut State,
+    cli: &Opts,
+    db: &std::sync::Arc<redb::Database>,
+) -> bool {
+    if open_selected_directory(state, db) {
+        return true;
+    }
+    if state.action_parent().is_some() {
+        return false;
+    }
+    let Some(app) = state.selected.and_then(|index| state.shown.get(index)) else {
+        return false;
+    };
+    if app.action_names().is_empty() {
+        return false;
+    }
+
+    let parent_name = app.name.clone();
+    let mut actions = crate::desktop::load_actions(app, cli.filter_desktop);
+    if let Ok(history_cache) = crate::core::cache::HistoryCache::load(db) {
+        actions = actions
+            .into_iter()
+            .map(|action| history_cache.apply_to_app(action))
+            .collect();
+    }
+
+    if !state.enter_actions(&parent_name, actions) {
+        state.set_status_message(format!("No actions available for {parent_name}"));
+    }
+    true
+}
+
+/// Open the selected `Type=Directory` entry as a group of the entries it
+/// categorizes. Returns `false` for any other kind of entry.
+fn open_selected_directory(state: &mut State, db: &std::sync::Arc<redb::Database>) -> bool {
+    let Some(app) = state.selected.and_then(|index| state.shown.get(index)) else {
+        return false;
+    };
+    if app.kind() != EntryKind::Directory || state.action_parent().is_some() {
+        return false;
+    }
+
+    let directory = app.clone();
+    // Opening a group counts as using it, so busy groups rank like busy apps.
+    if let Err(error) =
+        crate::core::database::record_access(db, &directory.store_key(), state.half_life_secs)
+    {
+        state.set_status_message(format!("Could not record access: {error}"));
+    }
+    if !state.enter_directory(&directory) {
+        state.set_status_message(format!("No entries in {}", directory.name));
+    }
+    true
+}
+
 fn hide_selected_entry(
     state: &mut State,
     hidden_store: &HiddenEntryStore,
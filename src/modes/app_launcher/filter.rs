@@ -0,0 +1,30 @@
+use crate::cli::Opts;
+use crate::common::{FilterRow, print_filter_rows};
+use crate::desktop::App;
+use serde_json::Value;
+
+/// Prints the ranked launcher matches for `--filter`.
+pub(super) fn print_ranked(apps: &[App], cli: &Opts) -> eyre::Result<()> {
+    let rows: Vec<FilterRow> = apps.iter().map(row).collect();
+    print_filter_rows(
+        &rows,
+        cli.filter_format.unwrap_or_default(),
+        cli.filter_explain,
+    )
+}
+
+fn row(app: &App) -> FilterRow {
+    FilterRow {
+        score: app.score,
+        text: app.name.clone(),
+        fields: vec![
+            ("name", Value::from(app.name.as_str())),
+            ("command", Value::from(app.command.as_str())),
+            ("desktop_id", Value::from(app.desktop_id.as_deref())),
+            ("pinned", Value::from(app.pinned)),
+        ],
+        // Unset when the query is empty and entries are in ranking order.
+        breakdown: serde_json::to_value(&app.breakdown)
+            .expect("Score breakdowns should be serializable to json"),
+    }
+}
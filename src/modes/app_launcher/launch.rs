@@ -1,5 +1,6 @@
 // Application launching utilities
 
+use crate::desktop::{EntryKind, LaunchTarget};
 use eyre::Result;
 use std::env;
 use std::ffi::CString;
@@ -13,14 +14,53 @@ fn split_command(command: &str, name: &str) -> Result<Vec<String>> {
     shell_words::split(command).map_err(|e| eyre::eyre!("Invalid {name}: {e}"))
 }
 
+/// Resolve the argv of every process that launching `app` with the CLI's targets starts.
+pub(crate) fn invocations(
+    app: &crate::desktop::App,
+    cli: &crate::cli::Opts,
+) -> Result<Vec<Vec<String>>> {
+    match app.kind() {
+        EntryKind::Application => {}
+        EntryKind::Link => {
+            let mut argv = split_command(&cli.link_opener, "link_opener")?;
+            argv.extend(app.url.clone());
+            return Ok(vec![argv]);
+        }
+        EntryKind::Directory => {
+            return Err(eyre::eyre!(
+                "'{}' is a directory entry and cannot be launched",
+                app.name
+            ));
+        }
+    }
+
+    let cwd = env::current_dir()?;
+    let targets: Vec<LaunchTarget> = cli
+        .launch_targets
+        .iter()
+        .map(|arg| LaunchTarget::from_arg(arg, &cwd))
+        .collect();
+    Ok(app.exec.invocations(&targets, app.source_path()))
+}
+
+/// Print the commands a launch would run, one per line, without running them.
+pub(crate) fn print_commands(app: &crate::desktop::App, cli: &crate::cli::Opts) -> Result<()> {
+    for argv in invocations(app, cli)? {
+        println!("{}", shell_words::join(argv));
+    }
+    Ok(())
+}
+
 /// Launch an application using the resolved launch prefix and terminal wrapper.
 pub fn launch_app(
     app: &crate::desktop::App,
     cli: &crate::cli::Opts,
     db: &std::sync::Arc<redb::Database>,
 ) -> Result<()> {
-    let commands = shell_words::split(&app.command)?;
-    if commands.is_empty() {
+    // Targets are resolved before changing into the entry's working directory so
+    // relative paths keep pointing where the user meant.
+    let invocations = invocations(app, cli)?;
+    if invocations.iter().any(Vec::is_empty) {
         return Err(eyre::eyre!("Empty command for app '{}'", app.name));
     }
     if crate::cli::DEBUG_ENABLED.load(std::sync::atomic::Ordering::Relaxed) {
@@ -35,106 +75,101 @@ pub fn launch_app(
     }
 
     if cli.tty && app.is_terminal {
-        use std::os::unix::process::CommandExt;
+        return exec_in_tty(app, cli, db, invocations);
+    }
 
+    for commands in invocations {
+        let mut exec = build_command(cli, app, commands)?;
         if crate::cli::DEBUG_ENABLED.load(std::sync::atomic::Ordering::Relaxed) {
-            crate::core::debug_logger::log_event("TTY mode: Replacing fsel with target app");
-            crate::core::debug_logger::log_launch(app, &app.command);
+            let cmd_str = format!(
+                "{} {}",
+                exec.get_program().to_string_lossy(),
+                exec.get_args()
+                    .map(|a| a.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            crate::core::debug_logger::log_launch(app, &cmd_str);
         }
+        exec.spawn()?;
+    }
 
-        // Validate the target executable before recording history / committing.
-        // Resolve commands[0] on PATH (if it has no slash) or check the given path,
-        // and ensure it exists and is executable. This prevents failed execs from
-        // being recorded as successful launches.
-        let exe_path_opt: Option<std::path::PathBuf> = {
-            let cmd0: &str = &commands[0];
-            let cmd_path = std::path::Path::new(cmd0);
-            if cmd0.contains('/') {
-                // Path provided directly
-                if cmd_path.exists() && cmd_path.is_file() {
-                    #[cfg(unix)]
-                    {
-                        use std::os::unix::fs::PermissionsExt;
-                        match cmd_path.metadata() {
-                            Ok(md) => {
-                                if (md.permissions().mode() & 0o111) != 0 {
-                                    Some(cmd_path.to_path_buf())
-                                } else {
-                                    None
-                                }
-                            }
-                            Err(_) => None,
-                        }
-                    }
-                    #[cfg(not(unix))]
-                    {
-                        Some(cmd_path.to_path_buf())
-                    }
-                } else {
-                    None
-                }
-            } else {
-                // Search PATH
-                if let Ok(pathvar) = env::var("PATH") {
-                    let mut found: Option<std::path::PathBuf> = None;
-                    for dir in pathvar.split(':') {
-                        let candidate = std::path::Path::new(dir).join(cmd0);
-                        if candidate.exists() && candidate.is_file() {
-                            #[cfg(unix)]
-                            {
-                                use std::os::unix::fs::PermissionsExt;
-                                if let Ok(md) = candidate.metadata()
-                                    && (md.permissions().mode() & 0o111) != 0
-                                {
-                                    found = Some(candidate);
-                                    break;
-                                }
-                            }
-                            #[cfg(not(unix))]
-                            {
-                                found = Some(candidate);
-                                break;
-                            }
-                        }
-                    }
-                    found
-                } else {
-                    None
-                }
-            }
-        };
+    record_launch(app, cli, db)
+}
 
-        let exe_path = match exe_path_opt {
-            Some(p) => p,
-            None => {
-                return Err(eyre::eyre!(
-                    "Executable not found or not executable: {}",
-                    commands[0]
-                ));
-            }
-        };
+/// Runs every invocation in this terminal, replacing fsel with the last one.
+fn exec_in_tty(
+    app: &crate::desktop::App,
+    cli: &crate::cli::Opts,
+    db: &std::sync::Arc<redb::Database>,
+    invocations: Vec<Vec<String>>,
+) -> Result<()> {
+    use std::os::unix::process::CommandExt;
 
-        // Record history and frecency BEFORE exec since we disappear after
-        let value = app.history + 1;
-        let write_txn = db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(crate::core::cache::HISTORY_TABLE)?;
-            table.insert(app.name.as_str(), value)?;
-        }
-        write_txn.commit()?;
+    if crate::cli::DEBUG_ENABLED.load(std::sync::atomic::Ordering::Relaxed) {
+        crate::core::debug_logger::log_event("TTY mode: Replacing fsel with target app");
+        crate::core::debug_logger::log_launch(app, &app.command);
+    }
 
-        if let Err(e) = crate::core::database::record_access(db, &app.name) {
-            eprintln!("Warning: Failed to update frecency: {}", e);
-        }
+    // Validate every target executable before recording history / committing, so
+    // failed execs are not recorded as successful launches.
+    let mut resolved = Vec::with_capacity(invocations.len());
+    for commands in invocations {
+        let exe_path = resolve_executable(&commands[0]).ok_or_else(|| {
+            eyre::eyre!("Executable not found or not executable: {}", commands[0])
+        })?;
+        resolved.push((exe_path, commands));
+    }
 
-        let mut exec = process::Command::new(exe_path);
-        exec.args(&commands[1..]);
+    // Record history and frecency BEFORE exec since we disappear after
+    record_launch(app, cli, db)?;
 
-        let err = exec.exec();
-        // If we're here, exec failed
-        return Err(err.into());
+    let Some((last_exe, last_commands)) = resolved.pop() else {
+        return Ok(());
+    };
+    // Single-file entries opened with several files run one after another.
+    for (exe_path, commands) in resolved {
+        let mut command = process::Command::new(exe_path);
+        command.args(&commands[1..]);
+        set_startup_env(&mut command, app, cli);
+        command.status()?;
     }
 
+    let mut command = process::Command::new(last_exe);
+    command.args(&last_commands[1..]);
+    set_startup_env(&mut command, app, cli);
+    let err = command.exec();
+    // If we're here, exec failed
+    Err(err.into())
+}
+
+/// Resolves `cmd0` on PATH (if it has no slash) or checks the given path.
+fn resolve_executable(cmd0: &str) -> Option<std::path::PathBuf> {
+    let is_executable = |path: &std::path::Path| {
+        use std::os::unix::fs::PermissionsExt;
+        path.is_file()
+            && path
+                .metadata()
+                .is_ok_and(|md| (md.permissions().mode() & 0o111) != 0)
+    };
+
+    if cmd0.contains('/') {
+        let cmd_path = std::path::Path::new(cmd0);
+        return is_executable(cmd_path).then(|| cmd_path.to_path_buf());
+    }
+
+    let pathvar = env::var("PATH").ok()?;
+    pathvar
+        .split(':')
+        .map(|dir| std::path::Path::new(dir).join(cmd0))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn build_command(
+    cli: &crate::cli::Opts,
+    app: &crate::desktop::App,
+    commands: Vec<String>,
+) -> Result<process::Command> {
     let mut runner = cli.launch_prefix.clone();
     if app.is_terminal {
         runner.extend(split_command(&cli.terminal_launcher, "terminal_launcher")?);
@@ -143,6 +178,7 @@ pub fn launch_app(
 
     let mut exec = process::Command::new(&runner[0]);
     exec.args(&runner[1..]);
+    set_startup_env(&mut exec, app, cli);
 
     // Ensure detached launches always get their own session and null stdio
     if cli.detach {
@@ -189,40 +225,63 @@ pub fn launch_app(
         exec.stderr(process::Stdio::null());
     }
 
-    if crate::cli::DEBUG_ENABLED.load(std::sync::atomic::Ordering::Relaxed) {
-        let cmd_str = format!(
-            "{} {}",
-            exec.get_program().to_string_lossy(),
-            exec.get_args()
-                .map(|a| a.to_string_lossy())
-                .collect::<Vec<_>>()
-                .join(" ")
-        );
-        crate::core::debug_logger::log_launch(app, &cmd_str);
-    }
+    Ok(exec)
+}
 
-    exec.spawn()?;
+/// Hand `app` its own startup ID, and never the one fsel itself was started with.
+///
+/// systemd-run scopes and `uwsm app` carry the variables over to the app.
+fn set_startup_env(
+    command: &mut process::Command,
+    app: &crate::desktop::App,
+    cli: &crate::cli::Opts,
+) {
+    let startup_id = super::startup::startup_id(app, cli);
+    for name in super::startup::STARTUP_ENV_VARS {
+        match &startup_id {
+            Some(startup_id) => command.env(name, startup_id),
+            None => command.env_remove(name),
+        };
+    }
+}
 
+fn record_launch(
+    app: &crate::desktop::App,
+    cli: &crate::cli::Opts,
+    db: &std::sync::Arc<redb::Database>,
+) -> Result<()> {
     // log it for history
     let value = app.history + 1;
+    let key = app.store_key();
     let write_txn = db.begin_write()?;
     {
         let mut table = write_txn.open_table(crate::core::cache::HISTORY_TABLE)?;
-        table.insert(app.name.as_str(), value)?;
+        table.insert(key.as_str(), value)?;
     }
     write_txn.commit()?;
 
     // Update frecency (modern usage tracking)
-    if let Err(e) = crate::core::database::record_access(db, &app.name) {
+    if let Err(e) = crate::core::database::record_access(db, &key, cli.frecency_half_life_secs) {
         eprintln!("Warning: Failed to update frecency: {}", e);
     }
+    if let Err(e) = crate::core::database::record_launch_hour(
+        db,
+        &key,
+        crate::core::ranking::current_unix_seconds(),
+    ) {
+        eprintln!("Warning: Failed to record launch time: {}", e);
+    }
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::split_command;
+    use super::{record_launch, split_command};
+    use crate::core::ranking::{LaunchHours, current_unix_seconds};
+    use crate::desktop::App;
+    use crate::testing::temp_dir;
+    use std::sync::Arc;
 
     #[test]
     fn split_command_handles_quotes() {
@@ -240,4 +299,36 @@ mod tests {
                 .is_empty()
         );
     }
+
+    #[test]
+    fn a_tui_launch_counts_once_towards_its_hour() {
+        let dir = temp_dir("launch-hours-once");
+        let db = Arc::new(
+            redb::Database::create(dir.join("history.redb")).expect("database should be created"),
+        );
+        let cli = crate::cli::Opts::default();
+        let app = App::parse(
+            "[Desktop Entry]\nType=Application\nName=Mail\nExec=true",
+            false,
+        )
+        .expect("test desktop entry should parse");
+        let key = app.store_key();
+
+        // The TUI records the access on Enter, then launches through `record_launch`.
+        let before = current_unix_seconds();
+        crate::core::database::record_access(&db, &key, cli.frecency_half_life_secs)
+            .expect("access should record");
+        record_launch(&app, &cli, &db).expect("launch should record");
+        let after = current_unix_seconds();
+
+        let once_at = |unix_secs| {
+            let mut hours = LaunchHours::default();
+            hours.record(unix_secs);
+            hours
+        };
+        let recorded = &crate::core::database::load_launch_hours(&db)[&key];
+        assert!(*recorded == once_at(before) || *recorded == once_at(after));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
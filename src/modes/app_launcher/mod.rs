@@ -3,10 +3,14 @@
 mod admin;
 mod direct;
 mod events;
+mod filter;
 pub mod launch;
+mod open_with;
 pub mod run;
 pub mod search;
 mod session;
+mod startup;
+pub mod validate;
 
 // Re-export the run function
 pub use run::run;
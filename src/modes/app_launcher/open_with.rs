@@ -0,0 +1,87 @@
+//! Narrowing the launcher to the handlers of one file or URL (`--open-with`).
+
+use crate::desktop::mime::{self, AssociationRank, Associations};
+use crate::desktop::{App, LaunchTarget};
+use std::path::PathBuf;
+
+/// The MIME type of the `--open-with` target and the entries that handle it.
+pub(crate) struct OpenWith {
+    /// Detected MIME type of the target.
+    pub(crate) mime: String,
+    /// Handlers ordered by association strength, then by the incoming order.
+    pub(crate) apps: Vec<App>,
+}
+
+/// Keep only the entries that can open `target`, defaults and explicit
+/// associations first.
+///
+/// `apps` should already be in ranking order; it is kept within each tier.
+pub(crate) fn handlers_for(
+    apps: Vec<App>,
+    target: &str,
+    application_dirs: &[PathBuf],
+) -> eyre::Result<OpenWith> {
+    let target = LaunchTarget::from_arg(target, &std::env::current_dir()?);
+    let mime = mime::detect(&target);
+    let associations = Associations::load(&mime, application_dirs);
+    Ok(OpenWith {
+        apps: rank_handlers(apps, &mime, &associations),
+        mime,
+    })
+}
+
+fn rank_handlers(apps: Vec<App>, mime: &str, associations: &Associations) -> Vec<App> {
+    let mut ranked: Vec<(AssociationRank, App)> = apps
+        .into_iter()
+        .filter_map(|app| {
+            // Desktop actions share their parent's ID with a `#Action` suffix and
+            // are never association targets on their own.
+            let desktop_id = app.desktop_id.as_deref().filter(|id| !id.contains('#'))?;
+            let declares = app
+                .mime_types
+                .iter()
+                .any(|declared| mime::declares(declared, mime));
+            let rank = associations.rank(desktop_id, declares)?;
+            Some((rank, app))
+        })
+        .collect();
+
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked.into_iter().map(|(_, app)| app).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rank_handlers;
+    use crate::desktop::App;
+    use crate::desktop::mime::Associations;
+
+    fn app(name: &str, desktop_id: &str, mime_types: &str) -> App {
+        let mut app = App::parse(
+            format!(
+                "[Desktop Entry]\nType=Application\nName={name}\nExec={name}\nMimeType={mime_types}"
+            ),
+            false,
+        )
+        .expect("test desktop entry should parse");
+        app.desktop_id = Some(desktop_id.to_string());
+        app
+    }
+
+    #[test]
+    fn only_declared_handlers_are_kept_in_incoming_order() {
+        let handlers = rank_handlers(
+            vec![
+                app("Feh", "feh.desktop", "image/png;"),
+                app("Editor", "editor.desktop", "text/plain;"),
+                app("Imv", "imv.desktop", "image/*;"),
+                app("Feh (Slideshow)", "feh.desktop#Slideshow", "image/png;"),
+            ],
+            "image/png",
+            &Associations::default(),
+        );
+
+        let names: Vec<&str> = handlers.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, ["Feh", "Imv"]);
+    }
+}
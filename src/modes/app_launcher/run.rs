@@ -1,9 +1,10 @@
 //! Application launcher mode
 
 use crate::cli::Opts;
-use crate::core::ranking::{current_unix_seconds, sort_by_ranking};
+use crate::core::ranking::{SortOptions, current_unix_seconds, sort_by_ranking};
 use crate::core::state::State;
-use crate::ui::{InputConfig, InputEvent as Event, UI};
+use crate::desktop::DesktopWatcher;
+use crate::ui::{AppIcons, InputConfig, InputEvent as Event, UI};
 use eyre::{Result, WrapErr};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
@@ -39,34 +40,70 @@ pub async fn run(cli: Opts) -> Result<()> {
 
     super::admin::initialize_test_mode(&cli);
 
+    let discover_options = crate::desktop::DiscoverOptions {
+        filter_desktop: cli.filter_desktop,
+        filter_actions: cli.filter_actions,
+        list_executables: cli.list_executables_in_path,
+        auto_hide_duplicates: cli.auto_hide_duplicates,
+        exec_check: cli.exec_check,
+    };
+    // Started before the scan so files added while it runs are not missed.
+    let watcher = if cli.stdout || cli.open_with.is_some() || cli.filter_query.is_some() {
+        None
+    } else {
+        DesktopWatcher::spawn(application_dirs.clone(), &db, discover_options)
+    };
     let apps_rx = crate::desktop::read_with_options(
         application_dirs.clone(),
+        cli.config_entries.clone(),
         &db,
-        crate::desktop::DiscoverOptions {
-            filter_desktop: cli.filter_desktop,
-            filter_actions: cli.filter_actions,
-            list_executables: cli.list_executables_in_path,
-            auto_hide_duplicates: cli.auto_hide_duplicates,
-        },
+        discover_options,
     );
 
     let mut all_apps = Vec::with_capacity(500);
     while let Ok(app) = apps_rx.recv() {
         all_apps.push(app);
     }
+    if let Err(error) =
+        crate::core::database::migrate_name_keys(&db, &mut all_apps, &application_dirs)
+    {
+        eprintln!("Warning: Failed to migrate launch history: {}", error);
+    }
 
     let frecency_data = crate::core::database::load_frecency(&db);
     let frecency_count = frecency_data.len();
     let mut pin_timestamps = crate::core::database::load_pin_timestamps(&db);
+    let pin_order = crate::core::database::load_pin_order(&db);
+    let launch_hours = crate::core::database::load_launch_hours(&db);
     sort_by_ranking(
         &mut all_apps,
-        &frecency_data,
-        cli.ranking_mode,
-        cli.pinned_order_mode,
-        &pin_timestamps,
-        current_unix_seconds(),
+        SortOptions {
+            frecency_data: &frecency_data,
+            ranking_mode: cli.ranking_mode,
+            frecency_model: cli.frecency_model,
+            half_life_secs: cli.frecency_half_life_secs,
+            pinned_order_mode: cli.pinned_order_mode,
+            pin_timestamps: &pin_timestamps,
+            pin_order: &pin_order,
+            launch_hours: &launch_hours,
+            now_secs: current_unix_seconds(),
+        },
     );
 
+    let mut open_with_status = None;
+    if let Some(target) = &cli.open_with {
+        let open_with = super::open_with::handlers_for(all_apps, target, &application_dirs)?;
+        if open_with.apps.is_empty() {
+            return Err(eyre::eyre!(
+                "No applications can open '{}' ({})",
+                target,
+                open_with.mime
+            ));
+        }
+        open_with_status = Some(format!("Open {target} ({})", open_with.mime));
+        all_apps = open_with.apps;
+    }
+
     let discovered_count = all_apps.len();
 
     let mut state = State::new(
@@ -78,6 +115,15 @@ pub async fn run(cli: Opts) -> Result<()> {
         cli.pinned_order_mode,
         std::mem::take(&mut pin_timestamps),
     );
+    state.pin_order = pin_order;
+    state.launch_hours = launch_hours;
+    state.learning_weight = cli.query_learning_weight;
+    state.folding = cli.text_folding.clone();
+    if cli.query_learning_weight > 0 {
+        state.query_selections = crate::core::database::load_query_selections(&db);
+    }
+    state.frecency_model = cli.frecency_model;
+    state.half_life_secs = cli.frecency_half_life_secs;
     state.set_visibility_options(crate::core::hidden_entries::VisibilityOptions {
         auto_hide_duplicates: cli.auto_hide_duplicates,
         application_dirs,
@@ -90,11 +136,27 @@ pub async fn run(cli: Opts) -> Result<()> {
         state.hidden_summary(),
     );
 
-    if let Some(ref search) = cli.search_string {
+    if let Some(category) = &cli.category {
+        state.set_category(Some(category.clone()));
+    }
+    if let Some(search) = cli.filter_query.as_ref().or(cli.search_string.as_ref()) {
         state.query = search.clone();
     }
+    if let Some(status) = open_with_status {
+        state.set_status_message(status);
+    }
 
+    let filter_start = std::time::Instant::now();
     state.filter();
+    if cli.filter_query.is_some() {
+        crate::common::report_filter_timing(
+            &cli,
+            state.shown.len(),
+            state.apps.len(),
+            filter_start.elapsed(),
+        );
+        return super::filter::print_ranked(&state.shown, &cli);
+    }
     state.update_info(
         cli.highlight_color,
         cli.fancy_mode,
@@ -131,6 +193,11 @@ pub async fn run(cli: Opts) -> Result<()> {
     terminal.hide_cursor().wrap_err("Failed to hide cursor")?;
     terminal.clear().wrap_err("Failed to clear terminal")?;
 
+    let mut icons = cli
+        .show_icons
+        .then(|| AppIcons::detect(cli.icon_theme.as_deref(), Arc::clone(&db)))
+        .flatten();
+
     let mut input = InputConfig {
         disable_mouse: cli.disable_mouse,
         tick_rate: Duration::from_millis(16),
@@ -140,9 +207,24 @@ pub async fn run(cli: Opts) -> Result<()> {
     .init_async();
 
     loop {
+        if let Some(watcher) = &watcher {
+            let changes = watcher.pending();
+            if !changes.is_empty() {
+                state.apply_desktop_changes(changes);
+                state.update_info(
+                    cli.highlight_color,
+                    cli.fancy_mode,
+                    cli.verbose.unwrap_or(0),
+                );
+            }
+        }
+
         terminal.draw(|frame| {
-            UI::new().render(frame, &state, &cli);
+            UI::new().render(frame, &state, &cli, icons.as_mut());
         })?;
+        if let Some(icons) = icons.as_mut() {
+            icons.save();
+        }
 
         let Some(event) = input.next().await else {
             break;
@@ -164,16 +246,28 @@ pub async fn run(cli: Opts) -> Result<()> {
             if let Some(selected_idx) = state.selected
                 && let Some(app) = state.shown.get(selected_idx)
             {
-                if let Err(error) = crate::core::database::record_access(&db, &app.name) {
+                if let Err(error) = crate::core::database::record_access(
+                    &db,
+                    &app.store_key(),
+                    cli.frecency_half_life_secs,
+                ) {
                     eprintln!("Failed to record access: {}", error);
                 }
+                if cli.query_learning_weight > 0
+                    && let Err(error) = crate::core::database::record_selection(
+                        &db,
+                        &crate::core::ranking::strip_field_terms(&state.query),
+                        &app.store_key(),
+                    )
+                {
+                    eprintln!("Failed to record query selection: {}", error);
+                }
 
                 crate::ui::terminal::shutdown_terminal(cli.disable_mouse)?;
                 terminal_active.set(false);
 
                 if cli.no_exec {
-                    println!("{}", app.command);
-                    return Ok(());
+                    return super::launch::print_commands(app, &cli);
                 }
 
                 super::launch::launch_app(app, &cli, &db)?;
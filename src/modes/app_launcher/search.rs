@@ -17,12 +17,23 @@ pub fn find_app_by_name_fast(
 ) -> Result<Option<desktop::App>> {
     let desktop_cache = cache::DesktopCache::new(db.clone())?;
     let history_cache = cache::HistoryCache::load(db)?;
+    let availability = desktop::ExecAvailability::load(db, cli.exec_check);
+
+    if let Some(app) = cli.config_entries.iter().find(|app| app.name == app_name)
+        && !is_hidden(app, hidden_entry_keys)
+        && availability.is_available(app)
+    {
+        availability.save();
+        return Ok(Some(history_cache.apply_to_app(app.clone())));
+    }
 
     if let Ok(Some(app)) = desktop_cache.get_by_name(app_name)
         && !app.hidden
         && matches_current_desktop(&app, cli)
         && !is_hidden(&app, hidden_entry_keys)
+        && availability.is_available(&app)
     {
+        availability.save();
         return Ok(Some(history_cache.apply_to_app(app)));
     }
 
@@ -45,15 +56,68 @@ pub fn find_app_by_name_fast(
                 && app.name == app_name
                 && matches_current_desktop(&app, cli)
                 && !is_hidden(&app, hidden_entry_keys)
+                && availability.is_available(&app)
             {
+                availability.save();
                 return Ok(Some(history_cache.apply_to_app(app)));
             }
         }
     }
 
+    availability.save();
     Ok(None)
 }
 
+/// Resolve stored entry keys back to launchable apps named to satisfy
+/// `name_matches`, skipping keys whose entry is gone, hidden, or unavailable.
+///
+/// Like name lookups, this covers desktop and config entries but not desktop
+/// actions or `$PATH` executables.
+pub(crate) fn find_apps_by_keys(
+    db: &std::sync::Arc<redb::Database>,
+    keys: &[EntryKey],
+    cli: &cli::Opts,
+    hidden_entry_keys: &HashSet<EntryKey>,
+    name_matches: impl Fn(&str) -> bool,
+) -> Result<Vec<desktop::App>> {
+    let desktop_cache = cache::DesktopCache::new(db.clone())?;
+    let history_cache = cache::HistoryCache::load(db)?;
+    let availability = desktop::ExecAvailability::load(db, cli.exec_check);
+    let application_dirs = crate::desktop::application_dirs();
+
+    let mut apps = Vec::new();
+    for key in keys {
+        let app = if let Some(name) = key.config_name() {
+            if !name_matches(name) {
+                continue;
+            }
+            cli.config_entries
+                .iter()
+                .find(|app| app.entry_key().as_ref() == Some(key))
+                .cloned()
+        } else if let Some(source_path) = key.source_path()
+            && key.desktop_id().is_some()
+        {
+            load_app_from_path(&desktop_cache, &application_dirs, &source_path, cli)?
+                .filter(|app| app.entry_key().as_ref() == Some(key))
+        } else {
+            None
+        };
+
+        if let Some(app) = app
+            && name_matches(&app.name)
+            && matches_current_desktop(&app, cli)
+            && !is_hidden(&app, hidden_entry_keys)
+            && availability.is_available(&app)
+        {
+            apps.push(history_cache.apply_to_app(app));
+        }
+    }
+
+    availability.save();
+    Ok(apps)
+}
+
 fn is_hidden(app: &desktop::App, hidden_entry_keys: &HashSet<EntryKey>) -> bool {
     app.entry_key()
         .is_some_and(|entry_key| hidden_entry_keys.contains(&entry_key))
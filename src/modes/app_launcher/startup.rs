@@ -0,0 +1,146 @@
+// Startup notification: the activation token handed to launched apps
+
+use std::process;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variables a launched app reads its startup ID from: Wayland
+/// activation first, then the X11 startup-notification spec.
+pub(crate) const STARTUP_ENV_VARS: [&str; 2] = ["XDG_ACTIVATION_TOKEN", "DESKTOP_STARTUP_ID"];
+
+static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+/// The startup ID for one launch of `app`, or `None` when the entry does not
+/// take part in startup notification.
+///
+/// With `activation_token_command` set, its first line of output is used, so a
+/// compositor helper can mint a real activation token. Without one, or when it
+/// fails, a spec-style ID (`fsel-PID-CLASS-SEQ_TIMEms`) is made up locally.
+pub(crate) fn startup_id(app: &crate::desktop::App, cli: &crate::cli::Opts) -> Option<String> {
+    if !app.startup_notify && app.startup_wm_class.is_none() {
+        return None;
+    }
+
+    if let Some(command) = cli.activation_token_command.as_deref() {
+        match token_from_command(command, app) {
+            Ok(token) => return Some(token),
+            Err(error) => {
+                if crate::cli::DEBUG_ENABLED.load(Ordering::Relaxed) {
+                    crate::core::debug_logger::log_event(&format!(
+                        "Activation token command failed, using a local startup ID: {error}"
+                    ));
+                }
+            }
+        }
+    }
+
+    Some(local_startup_id(app))
+}
+
+fn token_from_command(command: &str, app: &crate::desktop::App) -> eyre::Result<String> {
+    let argv = shell_words::split(command)
+        .map_err(|error| eyre::eyre!("Invalid activation_token_command: {error}"))?;
+    let Some((program, args)) = argv.split_first() else {
+        return Err(eyre::eyre!("activation_token_command is empty"));
+    };
+
+    let output = process::Command::new(program)
+        .args(args)
+        .env("FSEL_STARTUP_APP_NAME", &app.name)
+        .env(
+            "FSEL_STARTUP_APP_ID",
+            app.desktop_id.as_deref().unwrap_or_default(),
+        )
+        .env(
+            "FSEL_STARTUP_WM_CLASS",
+            app.startup_wm_class.as_deref().unwrap_or_default(),
+        )
+        .env_remove(STARTUP_ENV_VARS[0])
+        .env_remove(STARTUP_ENV_VARS[1])
+        .stdin(process::Stdio::null())
+        .stderr(process::Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(eyre::eyre!("exited with {}", output.status));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.lines().next().map(str::trim) {
+        Some(token) if !token.is_empty() => Ok(token.to_string()),
+        _ => Err(eyre::eyre!("printed no token")),
+    }
+}
+
+fn local_startup_id(app: &crate::desktop::App) -> String {
+    let class = app
+        .startup_wm_class
+        .as_deref()
+        .or(app.desktop_id.as_deref())
+        .unwrap_or(&app.name);
+    // IDs are passed through environment variables and X properties, so keep
+    // them to a safe alphabet.
+    let class: String = class
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+
+    format!("fsel-{}-{class}-{sequence}_TIME{millis}", process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::startup_id;
+    use crate::cli::Opts;
+    use crate::desktop::App;
+
+    fn app(extra: &str) -> App {
+        App::parse(
+            format!("[Desktop Entry]\nType=Application\nName=Editor\nExec=editor\n{extra}"),
+            false,
+        )
+        .expect("test desktop entry should parse")
+    }
+
+    #[test]
+    fn only_notifying_entries_get_an_id() {
+        let cli = Opts::default();
+
+        assert!(startup_id(&app(""), &cli).is_none());
+        let id = startup_id(&app("StartupWMClass=Editor Main"), &cli)
+            .expect("entries with a WM class should get an id");
+        assert!(id.starts_with(&format!("fsel-{}-Editor_Main-", std::process::id())));
+        assert!(id.contains("_TIME"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_command_output_is_used_and_failures_fall_back() {
+        let mut cli = Opts {
+            activation_token_command: Some(
+                "sh -c 'printf \"token-for-%s\\n\" \"$FSEL_STARTUP_APP_NAME\"'".to_string(),
+            ),
+            ..Opts::default()
+        };
+        let notifying = app("StartupNotify=true");
+
+        assert_eq!(
+            startup_id(&notifying, &cli).as_deref(),
+            Some("token-for-Editor")
+        );
+
+        cli.activation_token_command = Some("sh -c 'exit 1'".to_string());
+        let fallback = startup_id(&notifying, &cli).expect("a local id should be used");
+        assert!(fallback.starts_with("fsel-"));
+    }
+}
@@ -0,0 +1,75 @@
+//! `--validate-desktop`: report spec problems in desktop files.
+
+use crate::desktop::{self, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Validate `paths` (files, or directories searched for `.desktop` files) and
+/// print one `file:line: severity: message` line per problem.
+///
+/// With no paths the application directories are checked. Fails when any file
+/// has an error; warnings alone do not.
+pub fn run(paths: &[PathBuf]) -> ExitCode {
+    let files = if paths.is_empty() {
+        desktop::walk_desktop_files(&desktop::application_dirs())
+    } else {
+        expand_paths(paths)
+    };
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    for file in &files {
+        let contents = match fs::read_to_string(file) {
+            Ok(contents) => contents,
+            Err(error) => {
+                println!("{}: error: {error}", file.display());
+                errors += 1;
+                continue;
+            }
+        };
+        for diagnostic in desktop::validate(&contents) {
+            match diagnostic.severity {
+                Severity::Error => errors += 1,
+                Severity::Warning => warnings += 1,
+            }
+            println!(
+                "{}: {}: {}",
+                location(file, diagnostic.line),
+                diagnostic.severity,
+                diagnostic.message
+            );
+        }
+    }
+
+    eprintln!(
+        "Checked {} desktop file(s): {errors} error(s), {warnings} warning(s)",
+        files.len()
+    );
+    if errors > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn expand_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut found = desktop::walk_desktop_files(std::slice::from_ref(path));
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files
+}
+
+fn location(file: &Path, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}:{line}", file.display()),
+        None => file.display().to_string(),
+    }
+}
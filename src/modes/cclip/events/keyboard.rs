@@ -15,6 +15,7 @@ enum KeyAction {
     Delete,
     Exit,
     Select,
+    ToggleMark,
     Input(char),
     Backspace,
     First,
@@ -24,7 +25,7 @@ enum KeyAction {
     Ignore,
 }
 
-fn key_action(keybinds: &Keybinds, key: KeyEvent) -> KeyAction {
+fn key_action(keybinds: &Keybinds, key: KeyEvent, multi_select: bool) -> KeyAction {
     let code = key.code;
     let modifiers = key.modifiers;
 
@@ -42,6 +43,8 @@ fn key_action(keybinds: &Keybinds, key: KeyEvent) -> KeyAction {
         KeyAction::Select
     } else if keybinds.matches_backspace(code, modifiers) {
         KeyAction::Backspace
+    } else if multi_select && keybinds.matches_toggle_mark(code, modifiers) {
+        KeyAction::ToggleMark
     } else if keybinds.matches_left(code, modifiers) {
         KeyAction::First
     } else if keybinds.matches_right(code, modifiers) {
@@ -66,7 +69,7 @@ pub(super) async fn handle_key_event(
 ) -> Result<EventOutcome> {
     let needs_redraw = true;
 
-    match key_action(&ctx.cli.keybinds, key) {
+    match key_action(&ctx.cli.keybinds, key, ctx.ui.multi_select()) {
         KeyAction::ImagePreview => {
             ctx.image_runtime
                 .show_fullscreen_preview(ctx.terminal, input)
@@ -114,6 +117,16 @@ pub(super) async fn handle_key_event(
                 });
             }
         }
+        KeyAction::ToggleMark => {
+            if matches!(ctx.ui.tag_mode, TagMode::Normal) {
+                if ctx.ui.toggle_mark() {
+                    handle_down(ctx)?;
+                } else if let Some(limit) = ctx.cli.multi_select_limit {
+                    ctx.ui
+                        .set_temp_message(format!("At most {limit} entries can be marked"));
+                }
+            }
+        }
         KeyAction::Input(character) => {
             push_char(ctx.ui, character);
         }
@@ -267,14 +280,16 @@ up = [{ key = "k", modifiers = "alt" }]
         assert_eq!(
             key_action(
                 &keybinds,
-                KeyEvent::new(KeyCode::Char('j'), KeyModifiers::ALT)
+                KeyEvent::new(KeyCode::Char('j'), KeyModifiers::ALT),
+                false
             ),
             KeyAction::Down
         );
         assert_eq!(
             key_action(
                 &keybinds,
-                KeyEvent::new(KeyCode::Char('k'), KeyModifiers::ALT)
+                KeyEvent::new(KeyCode::Char('k'), KeyModifiers::ALT),
+                false
             ),
             KeyAction::Up
         );
@@ -285,15 +300,29 @@ up = [{ key = "k", modifiers = "alt" }]
         let keybinds = configured_keybinds();
 
         assert_eq!(
-            key_action(&keybinds, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            key_action(
+                &keybinds,
+                KeyEvent::new(KeyCode::Down, KeyModifiers::NONE),
+                false
+            ),
             KeyAction::Ignore
         );
         assert_eq!(
             key_action(
                 &keybinds,
-                KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)
+                KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+                false
             ),
             KeyAction::Ignore
         );
     }
+
+    #[test]
+    fn mark_key_only_marks_in_multi_select_mode() {
+        let keybinds = Keybinds::default();
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+
+        assert_eq!(key_action(&keybinds, tab, true), KeyAction::ToggleMark);
+        assert_eq!(key_action(&keybinds, tab, false), KeyAction::Ignore);
+    }
 }
@@ -3,6 +3,9 @@ use super::EventContext;
 use eyre::{Result, WrapErr};
 
 pub(super) fn delete_selected_item(ctx: &mut EventContext<'_, '_>) -> Result<()> {
+    if ctx.ui.marked_count() > 0 {
+        return delete_marked_items(ctx);
+    }
     if let Some(selected) = ctx.ui.selected
         && selected < ctx.ui.shown.len()
     {
@@ -31,6 +34,45 @@ pub(super) fn delete_selected_item(ctx: &mut EventContext<'_, '_>) -> Result<()>
     Ok(())
 }
 
+/// Deletes every marked entry, stopping at the first failure, then reloads
+/// the history.
+fn delete_marked_items(ctx: &mut EventContext<'_, '_>) -> Result<()> {
+    let rowids: Vec<String> = ctx
+        .ui
+        .marked_items()
+        .into_iter()
+        .filter_map(|item| ctx.ui.get_cclip_rowid(item))
+        .collect();
+
+    let mut deleted = 0;
+    for rowid in &rowids {
+        if let Err(error) = super::super::select::delete_item(rowid) {
+            ctx.ui.set_temp_message(format!(
+                "Deleted {deleted} of {} entries; failed on {rowid}: {error}",
+                rowids.len()
+            ));
+            break;
+        }
+        deleted += 1;
+    }
+    if deleted == rowids.len() {
+        ctx.ui
+            .set_temp_message(format!("Deleted {deleted} marked entries"));
+    }
+
+    if deleted > 0 {
+        reload_visible_history(
+            ctx.ui,
+            ctx.cli,
+            ctx.tag_metadata_formatter,
+            ctx.options.show_line_numbers,
+            ctx.options.show_tag_color_names,
+            ctx.max_visible,
+        );
+    }
+    Ok(())
+}
+
 pub(super) fn copy_selected_and_exit(ctx: &mut EventContext<'_, '_>) -> Result<bool> {
     let Some(selected) = ctx.ui.selected else {
         return Ok(false);
@@ -42,6 +84,9 @@ pub(super) fn copy_selected_and_exit_at(
     ctx: &mut EventContext<'_, '_>,
     index: usize,
 ) -> Result<bool> {
+    if ctx.ui.marked_count() > 0 {
+        return copy_marked_and_exit(ctx);
+    }
     if index >= ctx.ui.shown.len() {
         return Ok(false);
     }
@@ -67,6 +112,38 @@ pub(super) fn copy_selected_and_exit_at(
     }
 }
 
+/// Copies the marked entries as one clipboard entry, in history order.
+fn copy_marked_and_exit(ctx: &mut EventContext<'_, '_>) -> Result<bool> {
+    let cclip_items = match ctx
+        .ui
+        .marked_items()
+        .into_iter()
+        .map(|item| super::super::CclipItem::from_line(item.original_line.clone()))
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(cclip_items) => cclip_items,
+        Err(error) => {
+            ctx.ui.set_temp_message(format!("Parse failed: {}", error));
+            return Ok(false);
+        }
+    };
+
+    let copied = match cclip_items.as_slice() {
+        [cclip_item] => cclip_item.copy_to_clipboard(),
+        _ => super::super::select::copy_joined_to_clipboard(&cclip_items),
+    };
+    if let Err(error) = copied {
+        ctx.ui.set_temp_message(format!("Copy failed: {}", error));
+        return Ok(false);
+    }
+
+    ctx.terminal
+        .show_cursor()
+        .wrap_err("Failed to show cursor")?;
+    let _ = crate::ui::terminal::shutdown_terminal(ctx.options.disable_mouse);
+    Ok(true)
+}
+
 pub(super) fn move_to_first(ui: &mut crate::ui::DmenuUI<'_>) {
     if !ui.shown.is_empty() {
         ui.selected = Some(0);
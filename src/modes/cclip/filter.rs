@@ -0,0 +1,102 @@
+use crate::cli::{MatchMode, Opts};
+use crate::common::{FilterRow, Item, item_breakdown, print_filter_rows, report_filter_timing};
+use crate::ui::DmenuUI;
+use serde_json::Value;
+use std::time::Instant;
+
+/// Prints the ranked clipboard rows matching `query` for `--filter`.
+pub(super) fn print_ranked(
+    cli: &Opts,
+    cclip_items: Vec<super::CclipItem>,
+    query: &str,
+) -> eyre::Result<()> {
+    let options = super::state::CclipOptions::from_cli(cli);
+    // Tag colors only change how rows are displayed, so a busy database is not fatal.
+    let tag_metadata = crate::core::database::open_history_db()
+        .map(|(db, _)| super::load_tag_metadata(&db))
+        .unwrap_or_default();
+    let items = super::items::build_items(
+        cclip_items.clone(),
+        &super::TagMetadataFormatter::new(tag_metadata),
+        options.show_line_numbers,
+        options.show_tag_color_names,
+    );
+
+    let rows = ranked_rows(cli, &cclip_items, items, query);
+    print_filter_rows(
+        &rows,
+        cli.filter_format.unwrap_or_default(),
+        cli.filter_explain,
+    )
+}
+
+/// Ranks `items` as the cclip picker would and maps each back to its entry.
+fn ranked_rows(
+    cli: &Opts,
+    cclip_items: &[super::CclipItem],
+    items: Vec<Item>,
+    query: &str,
+) -> Vec<FilterRow> {
+    let started = Instant::now();
+    let total = items.len();
+    let mut ui = DmenuUI::new(items, false, false);
+    ui.set_text_folding(cli.text_folding.clone());
+    ui.query = query.to_string();
+    ui.filter();
+    report_filter_timing(cli, ui.shown.len(), total, started.elapsed());
+
+    ui.shown
+        .iter()
+        .filter_map(|item| {
+            let entry = cclip_items.get(item.line_number - 1)?;
+            Some(FilterRow {
+                score: item.score,
+                text: entry.original_line.clone(),
+                fields: vec![
+                    ("rowid", Value::from(entry.rowid.as_str())),
+                    ("mime_type", Value::from(entry.mime_type.as_str())),
+                    ("preview", Value::from(entry.preview.as_str())),
+                    ("tags", Value::from(entry.tags.clone())),
+                ],
+                // The picker always matches clipboard rows fuzzily.
+                breakdown: item_breakdown(item, MatchMode::Fuzzy),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ranked_rows;
+    use crate::cli::Opts;
+    use crate::common::Item;
+    use crate::modes::cclip::CclipItem;
+
+    #[test]
+    fn rows_map_ranked_items_back_to_their_clipboard_entries() {
+        let entries: Vec<CclipItem> = [
+            "3\ttext/plain\tgit push origin\twork",
+            "2\ttext/plain\tlunch order",
+            "1\timage/png\tscreenshot of git log",
+        ]
+        .into_iter()
+        .map(|line| CclipItem::from_line(line.to_string()).unwrap())
+        .collect();
+        let items = entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                Item::new_simple(entry.original_line.clone(), entry.preview.clone(), idx + 1)
+            })
+            .collect();
+
+        let rows = ranked_rows(&Opts::default(), &entries, items, "git");
+        let rowids: Vec<&str> = rows
+            .iter()
+            .map(|row| row.fields[0].1.as_str().unwrap())
+            .collect();
+        assert_eq!(rowids, ["3", "1"]);
+        assert_eq!(rows[0].text, entries[0].original_line);
+        assert_eq!(rows[0].fields[3].1, serde_json::json!(["work"]));
+    }
+}
@@ -2,6 +2,7 @@
 
 mod commands;
 mod events;
+mod filter;
 mod image;
 mod items;
 mod metadata;
@@ -70,6 +70,10 @@ fn filter_line(ui: &DmenuUI<'_>, options: &CclipOptions) -> Line<'static> {
             Style::default().fg(options.input_text_color),
         ),
         Span::styled(") ", Style::default().fg(options.input_text_color)),
+        Span::styled(
+            ui.marked_status().unwrap_or_default(),
+            Style::default().fg(options.highlight_color),
+        ),
         Span::styled(">", Style::default().fg(options.highlight_color)),
         Span::styled("> ", Style::default().fg(options.input_text_color)),
         Span::styled(
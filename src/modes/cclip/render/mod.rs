@@ -76,7 +76,7 @@ pub(super) fn draw(
             .iter()
             .skip(ui.scroll_offset)
             .take(max_visible)
-            .map(|item| item.to_list_item(Some(tag_metadata_formatter)))
+            .map(|item| ui.marked_list_item(item, Some(tag_metadata_formatter)))
             .collect::<Vec<ListItem>>();
 
         let items_list = List::new(visible_items)
@@ -20,6 +20,9 @@ pub async fn run(cli: &Opts) -> Result<()> {
     }
 
     let cclip_items = load_history(cli)?;
+    if let Some(query) = &cli.filter_query {
+        return super::filter::print_ranked(cli, cclip_items, query);
+    }
     if cclip_items.is_empty() {
         if let Some(tag_name) = &cli.cclip_tag {
             println!("No clipboard items with tag '{}'", tag_name);
@@ -53,6 +56,10 @@ pub async fn run(cli: &Opts) -> Result<()> {
     terminal.clear().wrap_err("Failed to clear terminal")?;
 
     let mut ui = DmenuUI::new(items, options.wrap_long_lines, options.show_line_numbers);
+    ui.set_text_folding(cli.text_folding.clone());
+    if cli.multi_select {
+        ui.enable_multi_select(cli.multi_select_limit);
+    }
     if let Some(search) = &cli.search_string {
         ui.query = search.clone();
         ui.filter();
@@ -2,7 +2,7 @@
 
 use super::CclipItem;
 use eyre::{Result, eyre};
-use std::io;
+use std::io::{self, Write};
 use std::process::{Child, Command, Stdio};
 use std::time::{Duration, Instant};
 
@@ -103,6 +103,62 @@ impl CclipItem {
     }
 }
 
+/// Copy the text of several entries to the clipboard as a single entry, one
+/// per line, in the order given.
+pub fn copy_joined_to_clipboard(items: &[CclipItem]) -> Result<()> {
+    if std::env::var("WAYLAND_DISPLAY").is_err() {
+        return Err(eyre!("cclip mode requires a Wayland session"));
+    }
+    if let Some(item) = items.iter().find(|item| !item.is_text()) {
+        return Err(eyre!(
+            "entry {} is {}; only text entries can be joined",
+            item.rowid,
+            item.mime_type
+        ));
+    }
+
+    let contents = items
+        .iter()
+        .map(CclipItem::get_content_for_preview)
+        .collect::<Result<Vec<_>>>()?;
+    let joined = join_entries(contents);
+
+    let mut wl_copy_child = Command::new("wl-copy")
+        .args(["--type", "text/plain;charset=utf-8"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    wl_copy_child
+        .stdin
+        .take()
+        .ok_or_else(|| eyre!("failed to open wl-copy stdin"))?
+        .write_all(&joined)?;
+    wait_for_clipboard_provider_start(
+        &mut wl_copy_child,
+        "wl-copy",
+        CLIPBOARD_PROVIDER_STARTUP_TIMEOUT,
+    )?;
+
+    Ok(())
+}
+
+/// Joins entry contents with newlines, dropping each entry's own trailing
+/// newline so no blank lines appear between them.
+fn join_entries(contents: Vec<Vec<u8>>) -> Vec<u8> {
+    let mut joined = Vec::new();
+    for (index, mut content) in contents.into_iter().enumerate() {
+        if content.ends_with(b"\n") {
+            content.pop();
+        }
+        if index > 0 {
+            joined.push(b'\n');
+        }
+        joined.extend(content);
+    }
+    joined
+}
+
 fn wait_for_clipboard_provider_start(
     child: &mut Child,
     command: &str,
@@ -217,7 +273,7 @@ pub fn delete_tag(tag: &str) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ClipboardProviderState, wait_for_clipboard_provider_start};
+    use super::{ClipboardProviderState, join_entries, wait_for_clipboard_provider_start};
     use std::process::{Command, Stdio};
     use std::time::Duration;
 
@@ -258,4 +314,14 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn joined_entries_are_separated_by_single_newlines() {
+        let joined = join_entries(vec![
+            b"first\n".to_vec(),
+            b"second".to_vec(),
+            b"third\n".to_vec(),
+        ]);
+        assert_eq!(joined, b"first\nsecond\nthird");
+    }
 }
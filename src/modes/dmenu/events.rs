@@ -1,5 +1,6 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
+use crate::common::Item;
 use crate::ui::{DmenuUI, Keybinds};
 
 use super::options::DmenuOptions;
@@ -47,7 +48,17 @@ pub(super) fn handle_key_event(
         {
             ui.query.pop();
             ui.filter();
-            auto_select_if_single_match(ui, options);
+            if let Some(outcome) = auto_select_if_single_match(ui, options) {
+                return outcome;
+            }
+        }
+        (code, modifiers)
+            if ui.multi_select() && options.keybinds.matches_toggle_mark(code, modifiers) =>
+        {
+            ui.finish_filter();
+            if ui.toggle_mark() {
+                move_selection(ui, options, terminal_height, 1);
+            }
         }
         (code, modifiers)
             if matches_dmenu_binding(
@@ -87,7 +98,9 @@ pub(super) fn handle_key_event(
         (KeyCode::Char(ch), KeyModifiers::NONE) | (KeyCode::Char(ch), KeyModifiers::SHIFT) => {
             ui.query.push(ch);
             ui.filter();
-            auto_select_if_single_match(ui, options);
+            if let Some(outcome) = auto_select_if_single_match(ui, options) {
+                return outcome;
+            }
         }
         _ => {}
     }
@@ -172,6 +185,9 @@ pub(super) fn handle_mouse_event(
             let row_in_content = mouse_row - items_content_start;
             let clicked_item_index = ui.scroll_offset + row_in_content as usize;
 
+            if ui.marked_count() > 0 {
+                return LoopOutcome::Print(marked_output(ui, options));
+            }
             if clicked_item_index < ui.shown.len() {
                 return LoopOutcome::Print(selected_output(ui, options, clicked_item_index));
             }
@@ -194,8 +210,11 @@ pub(super) fn handle_mouse_event(
 }
 
 fn handle_submit(ui: &mut DmenuUI, options: &DmenuOptions) -> LoopOutcome {
-    auto_select_if_single_match(ui, options);
-
+    // Enter picks from the results of everything typed before it.
+    ui.finish_filter();
+    if ui.marked_count() > 0 {
+        return LoopOutcome::Print(marked_output(ui, options));
+    }
     if let Some(selected) = ui.selected
         && selected < ui.shown.len()
     {
@@ -214,19 +233,44 @@ fn handle_submit(ui: &mut DmenuUI, options: &DmenuOptions) -> LoopOutcome {
 }
 
 fn selected_output(ui: &DmenuUI, options: &DmenuOptions, selected: usize) -> String {
+    item_output(&ui.shown[selected], options)
+}
+
+/// Every marked item in input order, one per line (NUL-separated with
+/// `--dmenu0`).
+fn marked_output(ui: &DmenuUI, options: &DmenuOptions) -> String {
+    let separator = if options.null_separated { "\0" } else { "\n" };
+    ui.marked_items()
+        .into_iter()
+        .map(|item| item_output(item, options))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// What Enter prints for `item`: with `--index` its position in the input, like
+/// `--filter` prints, since a row in the filtered list means nothing to a script.
+fn item_output(item: &Item, options: &DmenuOptions) -> String {
     if options.index_mode {
-        selected.to_string()
+        (item.line_number - 1).to_string()
     } else if let Some(ref accept_cols) = options.accept_nth {
-        ui.shown[selected].get_accept_nth_output(accept_cols)
+        item.get_accept_nth_output(accept_cols)
     } else {
-        ui.shown[selected].original_line.clone()
+        item.original_line.clone()
     }
 }
 
-fn auto_select_if_single_match(ui: &mut DmenuUI, options: &DmenuOptions) {
-    if options.auto_select && ui.shown.len() == 1 {
-        ui.selected = Some(0);
+/// With `--auto-select`, accepts the only match once every input line has
+/// arrived, since a later line could still match, and the results are not
+/// left over from an earlier query.
+pub(super) fn auto_select_if_single_match(
+    ui: &mut DmenuUI,
+    options: &DmenuOptions,
+) -> Option<LoopOutcome> {
+    if !options.auto_select || ui.loading || !ui.is_filtered() || ui.shown.len() != 1 {
+        return None;
     }
+    ui.selected = Some(0);
+    Some(LoopOutcome::Print(selected_output(ui, options, 0)))
 }
 
 fn move_selection(ui: &mut DmenuUI, options: &DmenuOptions, terminal_height: u16, direction: i32) {
@@ -254,17 +298,22 @@ fn move_selection(ui: &mut DmenuUI, options: &DmenuOptions, terminal_height: u16
         Some(selected)
     };
 
-    let Some(new_selected) = ui.selected else {
+    scroll_to_selection(ui, options, terminal_height);
+}
+
+/// Scrolls the list just enough to keep the selected row visible.
+pub(super) fn scroll_to_selection(ui: &mut DmenuUI, options: &DmenuOptions, terminal_height: u16) {
+    let Some(selected) = ui.selected else {
         return;
     };
 
     let max_visible = options.max_visible_items(terminal_height);
     if max_visible == 0 {
         ui.scroll_offset = 0;
-    } else if new_selected < ui.scroll_offset {
-        ui.scroll_offset = new_selected;
-    } else if new_selected >= ui.scroll_offset + max_visible {
-        ui.scroll_offset = new_selected.saturating_sub(max_visible - 1);
+    } else if selected < ui.scroll_offset {
+        ui.scroll_offset = selected;
+    } else if selected >= ui.scroll_offset + max_visible {
+        ui.scroll_offset = selected.saturating_sub(max_visible - 1);
     }
 }
 
@@ -397,4 +446,126 @@ up = [{ key = "k", modifiers = "alt" }]
         );
         assert_eq!(backspace_ui.query, "a");
     }
+
+    #[test]
+    fn auto_select_accepts_a_single_match_only_after_input_ends() {
+        let cli = Opts {
+            dmenu_auto_select: true,
+            ..Opts::default()
+        };
+        let options = DmenuOptions::from_cli(&cli);
+        let mut ui = DmenuUI::new(
+            vec![
+                Item::new_simple("alpha".into(), "alpha".into(), 1),
+                Item::new_simple("beta".into(), "beta".into(), 2),
+            ],
+            false,
+            false,
+        );
+        ui.loading = true;
+        let type_b = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE);
+
+        let outcome = handle_key_event(&mut ui, type_b, &options, 20);
+        assert!(matches!(outcome, LoopOutcome::Continue));
+
+        ui.loading = false;
+        ui.query.clear();
+        let outcome = handle_key_event(&mut ui, type_b, &options, 20);
+        assert!(matches!(outcome, LoopOutcome::Print(output) if output == "beta"));
+    }
+
+    #[test]
+    fn submit_prints_marked_items_in_input_order() {
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let marked_ui = || {
+            let mut ui = DmenuUI::new(
+                ["a:1", "b:2", "c:3"]
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| Item::new(line.to_string(), index + 1, ":", None))
+                    .collect(),
+                false,
+                false,
+            );
+            ui.enable_multi_select(None);
+            ui.selected = Some(2);
+            ui
+        };
+
+        for (cli, expected) in [
+            (Opts::default(), "a:1\nc:3"),
+            (
+                Opts {
+                    dmenu_accept_nth: Some(vec![2]),
+                    dmenu_null_separated: true,
+                    ..Opts::default()
+                },
+                "1\u{0}3",
+            ),
+            (
+                Opts {
+                    dmenu_index_mode: true,
+                    ..Opts::default()
+                },
+                "0\n2",
+            ),
+        ] {
+            let options = DmenuOptions::from_cli(&cli);
+            let mut ui = marked_ui();
+            // Marking moves down, wrapping from the last row to the first.
+            handle_key_event(&mut ui, tab, &options, 20);
+            assert_eq!(ui.selected, Some(0));
+            handle_key_event(&mut ui, tab, &options, 20);
+
+            let outcome = handle_key_event(&mut ui, enter, &options, 20);
+            assert!(
+                matches!(&outcome, LoopOutcome::Print(output) if output == expected),
+                "expected {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn index_prints_input_positions_while_a_query_is_active() {
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let options = DmenuOptions::from_cli(&Opts {
+            dmenu_index_mode: true,
+            ..Opts::default()
+        });
+        let filtered_ui = |multi: bool| {
+            let mut ui = DmenuUI::new(
+                ["apple", "banana", "cherry", "blueberry"]
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| {
+                        Item::new_simple(line.to_string(), line.to_string(), index + 1)
+                    })
+                    .collect(),
+                false,
+                false,
+            );
+            if multi {
+                ui.enable_multi_select(None);
+            }
+            ui.query = "b".to_string();
+            ui.filter();
+            ui
+        };
+
+        // Without marks, Enter prints the highlighted item's input position.
+        let mut ui = filtered_ui(false);
+        let expected = (ui.shown[0].line_number - 1).to_string();
+        assert_ne!(expected, "0");
+        let outcome = handle_key_event(&mut ui, enter, &options, 20);
+        assert!(matches!(&outcome, LoopOutcome::Print(output) if *output == expected));
+
+        // Marked items print the same positions, in input order.
+        let mut ui = filtered_ui(true);
+        handle_key_event(&mut ui, tab, &options, 20);
+        handle_key_event(&mut ui, tab, &options, 20);
+        let outcome = handle_key_event(&mut ui, enter, &options, 20);
+        assert!(matches!(&outcome, LoopOutcome::Print(output) if output == "1\n3"));
+    }
 }
@@ -0,0 +1,84 @@
+use crate::cli::Opts;
+use crate::common::{FilterRow, Item, item_breakdown, print_filter_rows, report_filter_timing};
+use crate::ui::DmenuUI;
+use serde_json::Value;
+use std::time::Instant;
+
+/// Prints the ranked stdin lines matching `query` for `--filter`.
+pub(super) fn print_ranked(cli: &Opts, items: Vec<Item>, query: &str) -> eyre::Result<()> {
+    let rows = ranked_rows(cli, items, query);
+    print_filter_rows(
+        &rows,
+        cli.filter_format.unwrap_or_default(),
+        cli.filter_explain,
+    )
+}
+
+fn ranked_rows(cli: &Opts, items: Vec<Item>, query: &str) -> Vec<FilterRow> {
+    let started = Instant::now();
+    let total = items.len();
+    let mut ui = DmenuUI::new(items, false, false);
+    ui.set_match_mode(cli.match_mode);
+    ui.set_match_nth(cli.dmenu_match_nth.clone());
+    ui.set_text_folding(cli.text_folding.clone());
+    ui.query = query.to_string();
+    ui.filter();
+    report_filter_timing(cli, ui.shown.len(), total, started.elapsed());
+
+    ui.shown
+        .iter()
+        .map(|item| {
+            // The input index, since a rank in the filtered list means nothing to a script.
+            let index = item.line_number - 1;
+            // What the interactive picker would print for this line.
+            let text = if cli.dmenu_index_mode {
+                index.to_string()
+            } else if let Some(ref accept_cols) = cli.dmenu_accept_nth {
+                item.get_accept_nth_output(accept_cols)
+            } else {
+                item.original_line.clone()
+            };
+            FilterRow {
+                score: item.score,
+                fields: vec![
+                    ("index", Value::from(index)),
+                    ("text", Value::from(text.as_str())),
+                ],
+                text,
+                breakdown: item_breakdown(item, cli.match_mode),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ranked_rows;
+    use crate::cli::Opts;
+    use crate::common::Item;
+
+    fn items(lines: &[&str]) -> Vec<Item> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| Item::new(line.to_string(), idx + 1, " ", None))
+            .collect()
+    }
+
+    #[test]
+    fn rows_follow_the_interactive_ranking_and_output() {
+        let cli = Opts::default();
+        let rows = ranked_rows(&cli, items(&["f_o_o baz", "bar", "foo qux"]), "foo");
+        let texts: Vec<&str> = rows.iter().map(|row| row.text.as_str()).collect();
+        assert_eq!(texts, ["foo qux", "f_o_o baz"]);
+        assert!(rows[0].score > rows[1].score);
+
+        let cli = Opts {
+            dmenu_accept_nth: Some(vec![2]),
+            ..Opts::default()
+        };
+        let rows = ranked_rows(&cli, items(&["foobar baz", "bar", "foo qux"]), "");
+        let texts: Vec<&str> = rows.iter().map(|row| row.text.as_str()).collect();
+        assert_eq!(texts, ["baz", "bar", "qux"]);
+    }
+}
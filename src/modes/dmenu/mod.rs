@@ -1,10 +1,12 @@
 // Dmenu mode - verb-based organization
 
 mod events;
+mod filter;
 mod options;
 pub mod parse;
 mod render;
 pub mod run;
+mod stream;
 
 // Re-export the run function
 pub use run::run;
@@ -17,6 +17,7 @@ pub(super) struct DmenuOptions {
     pub(super) only_match: bool,
     pub(super) index_mode: bool,
     pub(super) accept_nth: Option<Vec<usize>>,
+    pub(super) null_separated: bool,
     pub(super) hard_stop: bool,
     pub(super) highlight_color: Color,
     pub(super) main_border_color: Color,
@@ -48,6 +49,7 @@ impl DmenuOptions {
             only_match: cli.dmenu_only_match,
             index_mode: cli.dmenu_index_mode,
             accept_nth: cli.dmenu_accept_nth.clone(),
+            null_separated: cli.dmenu_null_separated,
             hard_stop: cli.dmenu_hard_stop.unwrap_or(cli.hard_stop),
             highlight_color: cli.dmenu_highlight_color.unwrap_or(cli.highlight_color),
             main_border_color: cli.dmenu_main_border_color.unwrap_or(cli.main_border_color),
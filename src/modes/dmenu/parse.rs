@@ -2,45 +2,90 @@
 
 use crate::common::Item;
 use is_terminal::IsTerminal;
-use std::io::{self, BufRead};
+use std::io;
 
 /// Check if stdin is being piped to us
 pub fn is_stdin_piped() -> bool {
     !io::stdin().is_terminal()
 }
 
-/// Read all lines from stdin into a vector
-pub fn read_stdin_lines() -> io::Result<Vec<String>> {
-    let stdin = io::stdin();
-    let lines: Result<Vec<String>, io::Error> = stdin.lock().lines().collect();
-    lines
+/// Turns raw stdin entries into `Item`s one at a time, numbering them as
+/// they arrive.
+pub(super) struct ItemParser {
+    delimiter: String,
+    with_nth: Option<Vec<usize>>,
+    null_separated: bool,
+    /// Line number given to the last entry.
+    line_number: usize,
 }
 
-/// Read null-separated input from stdin
-pub fn read_stdin_null_separated() -> io::Result<Vec<String>> {
-    use std::io::Read;
-    let mut buffer = String::new();
-    io::stdin().read_to_string(&mut buffer)?;
+impl ItemParser {
+    pub(super) fn new(delimiter: &str, with_nth: Option<Vec<usize>>, null_separated: bool) -> Self {
+        Self {
+            delimiter: delimiter.to_string(),
+            with_nth,
+            null_separated,
+            line_number: 0,
+        }
+    }
 
-    let lines: Vec<String> = buffer
-        .split('\0')
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect();
+    /// Byte that ends one entry of input.
+    pub(super) fn separator(&self) -> u8 {
+        if self.null_separated { b'\0' } else { b'\n' }
+    }
 
-    Ok(lines)
+    /// Parses one entry, including its separator if present.
+    ///
+    /// Blank lines are skipped but still counted, so line numbers match the
+    /// input; empty NUL-separated entries are not counted.
+    pub(super) fn parse(&mut self, raw: &[u8]) -> Option<Item> {
+        let entry = match raw.strip_suffix(&[self.separator()]) {
+            Some(entry) => entry,
+            None => raw,
+        };
+        let entry = if self.null_separated {
+            if entry.is_empty() {
+                return None;
+            }
+            entry
+        } else {
+            entry.strip_suffix(b"\r").unwrap_or(entry)
+        };
+
+        self.line_number += 1;
+        let line = String::from_utf8_lossy(entry);
+        if line.trim().is_empty() {
+            return None;
+        }
+        Some(Item::new(
+            line.into_owned(),
+            self.line_number,
+            &self.delimiter,
+            self.with_nth.as_ref(),
+        ))
+    }
 }
 
-/// Parse stdin lines into Items
-pub fn parse_stdin_to_items(
-    lines: Vec<String>,
-    delimiter: &str,
-    with_nth: Option<&Vec<usize>>,
-) -> Vec<Item> {
-    lines
-        .into_iter()
-        .enumerate()
-        .filter(|(_, line)| !line.trim().is_empty()) // Skip empty lines
-        .map(|(idx, line)| Item::new(line, idx + 1, delimiter, with_nth))
-        .collect()
+#[cfg(test)]
+mod tests {
+    use super::ItemParser;
+
+    #[test]
+    fn blank_lines_are_skipped_but_keep_their_line_numbers() {
+        let mut parser = ItemParser::new(" ", None, false);
+        let items: Vec<_> = [&b"first\r\n"[..], b"  \n", b"third"]
+            .into_iter()
+            .filter_map(|raw| parser.parse(raw))
+            .map(|item| (item.line_number, item.original_line))
+            .collect();
+        assert_eq!(items, [(1, "first".to_string()), (3, "third".to_string())]);
+
+        let mut parser = ItemParser::new(" ", None, true);
+        let items: Vec<_> = [&b"a\nb\0"[..], b"\0", b"c"]
+            .into_iter()
+            .filter_map(|raw| parser.parse(raw))
+            .map(|item| (item.line_number, item.original_line))
+            .collect();
+        assert_eq!(items, [(1, "a\nb".to_string()), (2, "c".to_string())]);
+    }
 }
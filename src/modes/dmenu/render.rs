@@ -10,6 +10,10 @@ use crate::ui::DmenuUI;
 
 use super::options::DmenuOptions;
 
+/// Spinner frames shown in the items title while stdin is still being read.
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_FRAME_MS: u128 = 80;
+
 pub(super) fn draw_frame(
     frame: &mut Frame,
     ui: &mut DmenuUI,
@@ -61,7 +65,7 @@ pub(super) fn draw_frame(
         .iter()
         .skip(ui.scroll_offset)
         .take(max_visible)
-        .map(ListItem::from)
+        .map(|item| ui.marked_list_item(item, None))
         .collect::<Vec<ListItem>>();
 
     let items_list = List::new(visible_items)
@@ -69,7 +73,7 @@ pub(super) fn draw_frame(
             Block::default()
                 .borders(Borders::ALL)
                 .title(Span::styled(
-                    " Items ",
+                    items_title(ui),
                     Style::default()
                         .add_modifier(Modifier::BOLD)
                         .fg(options.header_title_color),
@@ -106,6 +110,10 @@ pub(super) fn draw_frame(
             Style::default().fg(options.input_text_color),
         ),
         Span::styled(") ", Style::default().fg(options.input_text_color)),
+        Span::styled(
+            ui.marked_status().unwrap_or_default(),
+            Style::default().fg(options.highlight_color),
+        ),
         Span::styled(">", Style::default().fg(options.highlight_color)),
         Span::styled("> ", Style::default().fg(options.input_text_color)),
         Span::styled(
@@ -161,3 +169,16 @@ pub(super) fn draw_frame(
     }
     frame.render_widget(input_paragraph, chunks[input_panel_index]);
 }
+
+/// The items panel title, with a spinner and the count read so far while
+/// stdin is still streaming in.
+fn items_title(ui: &DmenuUI) -> String {
+    if !ui.loading {
+        return " Items ".to_string();
+    }
+    let elapsed_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    let frame = SPINNER_FRAMES[(elapsed_ms / SPINNER_FRAME_MS) as usize % SPINNER_FRAMES.len()];
+    format!(" Items {frame} {} ", ui.shown.len() + ui.hidden.len())
+}
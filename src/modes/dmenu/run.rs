@@ -8,10 +8,20 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::widgets::ListState;
 use std::io;
 use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
 
-use super::events::{LoopOutcome, handle_key_event, handle_mouse_event};
+use super::events::{
+    LoopOutcome, auto_select_if_single_match, handle_key_event, handle_mouse_event,
+    scroll_to_selection,
+};
 use super::options::DmenuOptions;
+use super::parse::ItemParser;
 use super::render::draw_frame;
+use super::stream::StdinStream;
+
+/// How often the UI checks for new stdin items while input is still arriving.
+const STREAM_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Run dmenu mode
 pub fn run(cli: &Opts) -> Result<()> {
@@ -23,32 +33,37 @@ pub fn run(cli: &Opts) -> Result<()> {
         return Err(eyre::eyre!("dmenu mode requires input from stdin"));
     }
 
-    // Read stdin lines
-    let lines = if cli.dmenu_prompt_only {
-        vec![] // No input in prompt-only mode
-    } else if cli.dmenu_null_separated {
-        super::parse::read_stdin_null_separated().wrap_err("Failed to read from stdin")?
-    } else {
-        super::parse::read_stdin_lines().wrap_err("Failed to read from stdin")?
-    };
+    // Stdin is read on a background thread so the list fills in while it streams.
+    let mut stream = (!cli.dmenu_prompt_only).then(|| {
+        StdinStream::spawn(
+            io::stdin(),
+            ItemParser::new(
+                &cli.dmenu_delimiter,
+                cli.dmenu_with_nth.clone(),
+                cli.dmenu_null_separated,
+            ),
+        )
+    });
 
-    // Exit immediately if no input and exit_if_empty is set
-    if cli.dmenu_exit_if_empty && lines.is_empty() {
-        return Ok(());
+    if let Some(query) = &cli.filter_query {
+        let items = match stream {
+            Some(stream) => stream.read_all().wrap_err("Failed to read from stdin")?,
+            None => Vec::new(),
+        };
+        return super::filter::print_ranked(cli, items, query);
     }
 
-    // Also check if lines only contain empty strings
-    if cli.dmenu_exit_if_empty && lines.iter().all(|l| l.trim().is_empty()) {
+    // --exit-if-empty needs a first item, or the end of input, before the UI opens.
+    let items = match stream.as_mut() {
+        Some(stream) if cli.dmenu_exit_if_empty => stream.wait_items(),
+        Some(stream) => stream.try_items(),
+        None => Ok(Vec::new()),
+    }
+    .wrap_err("Failed to read from stdin")?;
+    if cli.dmenu_exit_if_empty && items.is_empty() {
         return Ok(());
     }
 
-    // Parse items
-    let items = super::parse::parse_stdin_to_items(
-        lines,
-        &cli.dmenu_delimiter,
-        cli.dmenu_with_nth.as_ref(),
-    );
-
     let options = DmenuOptions::from_cli(cli);
     crate::ui::terminal::setup_terminal(options.disable_mouse)?;
 
@@ -61,14 +76,67 @@ pub fn run(cli: &Opts) -> Result<()> {
         let input = options.input_config().init();
 
         let mut ui = build_ui(cli, items, options.highlight_color);
+        // Filtering gives way to keys typed meanwhile and resumes once they are handled.
+        ui.set_pending_keys(input.pending_keys());
+        ui.loading = stream.as_ref().is_some_and(|stream| !stream.is_done());
+        // A --select target may only arrive later; typing or moving cancels it.
+        let mut preselect_pending = !preselect(&mut ui, cli);
         let mut list_state = ListState::default();
 
+        if !ui.loading
+            && let Some(outcome) = auto_select_if_single_match(&mut ui, &options)
+        {
+            prepare_terminal_for_output(&mut terminal)?;
+            return Ok(outcome);
+        }
+
         loop {
+            if !ui.is_filtered() {
+                ui.filter();
+                ui.info(options.highlight_color);
+                if let Some(outcome) = auto_select_if_single_match(&mut ui, &options) {
+                    prepare_terminal_for_output(&mut terminal)?;
+                    return Ok(outcome);
+                }
+            }
+
+            if let Some(stream) = stream.as_mut().filter(|stream| !stream.is_done()) {
+                let items = stream.try_items().wrap_err("Failed to read from stdin")?;
+                if !items.is_empty() {
+                    ui.append_items(items);
+                    if preselect_pending {
+                        preselect_pending = !preselect(&mut ui, cli);
+                    }
+                    scroll_to_selection(&mut ui, &options, terminal.size()?.height);
+                }
+                if stream.is_done() {
+                    ui.loading = false;
+                    if let Some(outcome) = auto_select_if_single_match(&mut ui, &options) {
+                        prepare_terminal_for_output(&mut terminal)?;
+                        return Ok(outcome);
+                    }
+                }
+            }
+
             sync_update_mode(options.term_is_foot, true);
             terminal.draw(|frame| draw_frame(frame, &mut ui, &mut list_state, &options))?;
             sync_update_mode(options.term_is_foot, false);
 
-            match input.next()? {
+            // While stdin streams in, wake up regularly to show new items.
+            let event = if ui.loading {
+                match input.next_timeout(STREAM_POLL_INTERVAL) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(error) => return Err(error.into()),
+                }
+            } else {
+                input.next()?
+            };
+            if matches!(event, Event::Input(_) | Event::Mouse(_)) {
+                preselect_pending = false;
+            }
+
+            match event {
                 Event::Input(key) => {
                     match handle_key_event(&mut ui, key, &options, terminal.size()?.height) {
                         LoopOutcome::Continue => {}
@@ -133,6 +201,10 @@ fn build_ui<'a>(
     );
     ui.set_match_mode(cli.match_mode);
     ui.set_match_nth(cli.dmenu_match_nth.clone());
+    ui.set_text_folding(cli.text_folding.clone());
+    if cli.multi_select {
+        ui.enable_multi_select(cli.multi_select_limit);
+    }
 
     if let Some(ref search) = cli.search_string {
         ui.query = search.clone();
@@ -140,20 +212,6 @@ fn build_ui<'a>(
 
     ui.filter();
 
-    if let Some(ref select_str) = cli.dmenu_select {
-        let select_lower = select_str.to_lowercase();
-        for (idx, item) in ui.shown.iter().enumerate() {
-            if item.display_text.to_lowercase().contains(&select_lower) {
-                ui.selected = Some(idx);
-                break;
-            }
-        }
-    } else if let Some(select_idx) = cli.dmenu_select_index
-        && select_idx < ui.shown.len()
-    {
-        ui.selected = Some(select_idx);
-    }
-
     if !ui.shown.is_empty() && ui.selected.is_none() {
         ui.selected = Some(0);
     }
@@ -162,6 +220,28 @@ fn build_ui<'a>(
     ui
 }
 
+/// Applies `--select` or `--select-index`, returning whether the requested
+/// row (if any) was found among the items read so far.
+fn preselect(ui: &mut DmenuUI, cli: &Opts) -> bool {
+    if let Some(ref select_str) = cli.dmenu_select {
+        let select_lower = select_str.to_lowercase();
+        let Some(idx) = ui
+            .shown
+            .iter()
+            .position(|item| item.display_text.to_lowercase().contains(&select_lower))
+        else {
+            return false;
+        };
+        ui.selected = Some(idx);
+    } else if let Some(select_idx) = cli.dmenu_select_index {
+        if select_idx >= ui.shown.len() {
+            return false;
+        }
+        ui.selected = Some(select_idx);
+    }
+    true
+}
+
 fn prepare_terminal_for_output(
     terminal: &mut ratatui::Terminal<CrosstermBackend<io::Stderr>>,
 ) -> Result<()> {
@@ -0,0 +1,155 @@
+//! Background stdin reader that hands items to the dmenu UI while it runs.
+
+use crate::common::Item;
+use std::io::{self, BufRead, BufReader, Read};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use super::parse::ItemParser;
+
+/// Most items sent to the UI in one chunk.
+const MAX_CHUNK_ITEMS: usize = 4096;
+
+/// Items arriving from stdin on a reader thread.
+pub(super) struct StdinStream {
+    rx: Receiver<io::Result<Vec<Item>>>,
+    done: bool,
+}
+
+impl StdinStream {
+    /// Starts reading `reader` on a background thread.
+    pub(super) fn spawn(reader: impl Read + Send + 'static, parser: ItemParser) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || read_chunks(BufReader::with_capacity(64 * 1024, reader), parser, tx));
+        Self { rx, done: false }
+    }
+
+    /// Whether stdin has ended (or failed) and every item has been received.
+    pub(super) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Items received since the last call, without waiting for more.
+    pub(super) fn try_items(&mut self) -> io::Result<Vec<Item>> {
+        let mut items = Vec::new();
+        while !self.done {
+            match self.rx.try_recv() {
+                Ok(chunk) => items.extend(self.accept(chunk)?),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => self.done = true,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Waits until at least one item arrives or stdin ends.
+    pub(super) fn wait_items(&mut self) -> io::Result<Vec<Item>> {
+        let mut items = Vec::new();
+        while items.is_empty() && !self.done {
+            match self.rx.recv() {
+                Ok(chunk) => items.extend(self.accept(chunk)?),
+                Err(_) => self.done = true,
+            }
+        }
+        items.extend(self.try_items()?);
+        Ok(items)
+    }
+
+    /// Waits for stdin to end and returns every item.
+    pub(super) fn read_all(mut self) -> io::Result<Vec<Item>> {
+        let mut items = Vec::new();
+        while !self.done {
+            items.extend(self.wait_items()?);
+        }
+        Ok(items)
+    }
+
+    fn accept(&mut self, chunk: io::Result<Vec<Item>>) -> io::Result<Vec<Item>> {
+        if chunk.is_err() {
+            self.done = true;
+        }
+        chunk
+    }
+}
+
+/// Reads entries until EOF, sending items whenever reading further could block.
+fn read_chunks(
+    mut reader: BufReader<impl Read>,
+    mut parser: ItemParser,
+    tx: Sender<io::Result<Vec<Item>>>,
+) {
+    let separator = parser.separator();
+    let mut chunk = Vec::new();
+    let mut raw = Vec::new();
+    loop {
+        raw.clear();
+        match reader.read_until(separator, &mut raw) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                let _ = tx.send(Err(error));
+                return;
+            }
+        }
+        chunk.extend(parser.parse(&raw));
+
+        // An empty buffer means the next read may wait on a slow producer.
+        let should_send =
+            chunk.len() >= MAX_CHUNK_ITEMS || (!chunk.is_empty() && reader.buffer().is_empty());
+        if should_send && tx.send(Ok(std::mem::take(&mut chunk))).is_err() {
+            return;
+        }
+    }
+    if !chunk.is_empty() {
+        let _ = tx.send(Ok(chunk));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ItemParser, StdinStream};
+    use std::io::{self, Read};
+    use std::sync::mpsc;
+
+    /// Reader that yields one queued block per read and ends when the queue closes.
+    struct Blocks(mpsc::Receiver<&'static [u8]>);
+
+    impl Read for Blocks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Ok(block) = self.0.recv() else {
+                return Ok(0);
+            };
+            buf[..block.len()].copy_from_slice(block);
+            Ok(block.len())
+        }
+    }
+
+    fn lines(items: Vec<crate::common::Item>) -> Vec<String> {
+        items.into_iter().map(|item| item.original_line).collect()
+    }
+
+    #[test]
+    fn items_arrive_before_stdin_ends() {
+        let (tx, rx) = mpsc::channel();
+        let mut stream = StdinStream::spawn(Blocks(rx), ItemParser::new(" ", None, false));
+
+        tx.send(b"one\ntwo\n").unwrap();
+        assert_eq!(lines(stream.wait_items().unwrap()), ["one", "two"]);
+        assert!(!stream.is_done());
+
+        tx.send(b"\nthree").unwrap();
+        drop(tx);
+        let rest = stream.read_all().unwrap();
+        assert_eq!(rest[0].line_number, 4);
+        assert_eq!(lines(rest), ["three"]);
+    }
+
+    #[test]
+    fn wait_items_reports_the_end_of_empty_input() {
+        let mut stream = StdinStream::spawn(io::empty(), ItemParser::new(" ", None, false));
+        assert!(stream.wait_items().unwrap().is_empty());
+        assert!(stream.is_done());
+        assert!(stream.try_items().unwrap().is_empty());
+    }
+}
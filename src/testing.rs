@@ -0,0 +1,25 @@
+//! Fixtures shared by the unit tests.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Creates a fresh directory under the system temp dir for one test.
+///
+/// The name carries the process id, a timestamp, and a per-process counter, so
+/// reruns and tests sharing a `label` never see each other's files.
+pub(crate) fn temp_dir(label: &str) -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock should be after unix epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "fsel-{label}-{}-{unique}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).expect("test temp dir should be created");
+    dir
+}
@@ -1,8 +1,11 @@
+use super::icons::{AppIcons, ICON_WIDTH};
+use crate::desktop::EntryKind;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, Paragraph};
+use ratatui_image::Image;
 
 pub(crate) fn effective_title_height(total_height: u16, title_panel_height_percent: u16) -> u16 {
     if title_panel_height_percent == 0 {
@@ -22,7 +25,15 @@ impl UI {
     }
 
     /// Render the UI using the centralized State
-    pub fn render(&self, f: &mut Frame, state: &crate::core::state::State, cli: &crate::cli::Opts) {
+    ///
+    /// `icons` is only passed when the terminal can draw them.
+    pub fn render(
+        &self,
+        f: &mut Frame,
+        state: &crate::core::state::State,
+        cli: &crate::cli::Opts,
+        icons: Option<&mut AppIcons>,
+    ) {
         let size = f.area();
         let title_height = effective_title_height(size.height, cli.title_panel_height_percent);
         let should_render_border = title_height > 0;
@@ -66,18 +77,19 @@ impl UI {
         // Render Title/Info Panel
         if should_render_border {
             // Determine dynamic title
+            let base_title = match (state.action_parent(), state.category()) {
+                (Some(parent), _) => format!("Fsel › {parent}"),
+                (None, Some(category)) => format!("Fsel [{category}]"),
+                (None, None) => "Fsel".to_string(),
+            };
             let title = if cli.fancy_mode {
-                if let Some(selected) = state.selected {
-                    state
-                        .shown
-                        .get(selected)
-                        .map(|a| a.name.clone())
-                        .unwrap_or_else(|| "Fsel".to_string())
-                } else {
-                    "Fsel".to_string()
-                }
+                state
+                    .selected
+                    .and_then(|selected| state.shown.get(selected))
+                    .map(|a| a.name.clone())
+                    .unwrap_or(base_title)
             } else {
-                "Fsel".to_string()
+                base_title
             };
 
             let info_block = Block::default()
@@ -161,7 +173,7 @@ impl UI {
         let max_visible = apps_area.height.saturating_sub(2) as usize;
 
         // Apps block with border
-        let apps_block = Block::default()
+        let mut apps_block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(cli.apps_border_color))
             .title(Span::styled(
@@ -174,6 +186,13 @@ impl UI {
                 BorderType::Plain
             });
 
+        // The category strip sits in the bottom border while it is open
+        if let Some((entries, cursor)) = state.category_picker() {
+            let labels: Vec<&str> = entries.iter().map(|entry| entry.unwrap_or("All")).collect();
+            let width = usize::from(apps_area.width.saturating_sub(2));
+            apps_block = apps_block.title_bottom(category_strip(&labels, cursor, width, cli));
+        }
+
         // only render whats on screen, not the whole dang list
         let items: Vec<ListItem> = state
             .shown
@@ -183,6 +202,11 @@ impl UI {
             .map(|app| {
                 let mut spans = Vec::new();
 
+                // Blank cells the icon image is drawn over after the list
+                if icons.is_some() {
+                    spans.push(Span::raw(" ".repeat(usize::from(ICON_WIDTH) + 1)));
+                }
+
                 // Pin support
                 if app.pinned {
                     spans.push(Span::styled(
@@ -192,10 +216,26 @@ impl UI {
                     spans.push(Span::raw(" "));
                 }
 
-                spans.push(Span::styled(
-                    &app.name,
-                    Style::default().fg(cli.apps_text_color),
-                ));
+                let name_style = Style::default().fg(cli.apps_text_color);
+                match app.breakdown.as_ref() {
+                    Some(breakdown) if !breakdown.name_indices.is_empty() => spans.extend(
+                        super::match_spans(&app.name, &breakdown.name_indices, name_style),
+                    ),
+                    _ => spans.push(Span::styled(&app.name, name_style)),
+                }
+
+                // Entries that do not launch a program say what they do instead
+                let marker = match app.kind() {
+                    EntryKind::Application => None,
+                    EntryKind::Link => Some(" ↗"),
+                    EntryKind::Directory => Some(" ▸"),
+                };
+                if let Some(marker) = marker {
+                    let style = Style::default()
+                        .fg(cli.apps_text_color)
+                        .add_modifier(Modifier::DIM);
+                    spans.push(Span::styled(marker, style));
+                }
 
                 ListItem::new(Line::from(spans))
             })
@@ -219,13 +259,98 @@ impl UI {
             }
         }
 
+        let highlight_width = if list_state.selected().is_some() {
+            2
+        } else {
+            0
+        };
         f.render_stateful_widget(list, apps_area, &mut list_state);
+
+        if let Some(icons) = icons {
+            let visible = state
+                .shown
+                .iter()
+                .skip(state.scroll_offset)
+                .take(max_visible);
+            for (row, app) in visible.enumerate() {
+                let Some(image) = app.icon.as_deref().and_then(|icon| icons.image(icon)) else {
+                    continue;
+                };
+                let area = Rect::new(
+                    apps_area.x + 1 + highlight_width,
+                    apps_area.y + 1 + row as u16,
+                    ICON_WIDTH,
+                    1,
+                )
+                .intersection(apps_area);
+                f.render_widget(Image::new(image), area);
+            }
+        }
     }
 }
 
+/// The category strip, scrolled so the highlighted label fits in `width`.
+fn category_strip<'a>(
+    labels: &[&'a str],
+    cursor: usize,
+    width: usize,
+    cli: &crate::cli::Opts,
+) -> Line<'a> {
+    let (start, end) = strip_window(labels, cursor, width);
+    let text_style = Style::default().fg(cli.apps_text_color);
+    let mut spans = Vec::new();
+    if start > 0 {
+        spans.push(Span::styled("‹", text_style));
+    }
+    for (index, label) in labels.iter().enumerate().take(end).skip(start) {
+        let style = if index == cursor {
+            Style::default()
+                .fg(cli.highlight_color)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            text_style
+        };
+        spans.push(Span::styled(format!(" {label} "), style));
+    }
+    if end < labels.len() {
+        spans.push(Span::styled("›", text_style));
+    }
+    Line::from(spans)
+}
+
+/// Range of `labels` shown in a strip `width` cells wide: as many as fit from
+/// the start, moved along just enough to include `cursor`.
+fn strip_window(labels: &[&str], cursor: usize, width: usize) -> (usize, usize) {
+    // Each label is padded by a space on both sides; scroll markers take one cell
+    let label_width = |label: &&str| label.chars().count() + 2;
+    let fits = |start: usize, end: usize| {
+        let markers = usize::from(start > 0) + usize::from(end < labels.len());
+        labels[start..end].iter().map(label_width).sum::<usize>() + markers <= width
+    };
+
+    let mut start = 0;
+    while start < cursor && !fits(start, cursor + 1) {
+        start += 1;
+    }
+    let mut end = (cursor + 1).min(labels.len());
+    while end < labels.len() && fits(start, end + 1) {
+        end += 1;
+    }
+    (start, end)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::effective_title_height;
+    use super::{effective_title_height, strip_window};
+
+    #[test]
+    fn category_strip_scrolls_to_keep_the_highlight_visible() {
+        let labels = ["All", "Development", "Graphics", "Network", "Utility"];
+
+        assert_eq!(strip_window(&labels, 0, 80), (0, 5));
+        assert_eq!(strip_window(&labels, 0, 30), (0, 3));
+        assert_eq!(strip_window(&labels, 4, 30), (2, 5));
+    }
 
     #[test]
     fn effective_title_height_allows_zero() {
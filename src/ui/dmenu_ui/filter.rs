@@ -1,75 +1,96 @@
 use super::DmenuUI;
+use crate::cli::MatchMode;
+use crate::common::Item;
+use crate::core::ranking::{ExtendedQuery, TextFolding};
+use crate::ui::PendingKeys;
+use nucleo_matcher::{Config, Matcher};
+use rayon::prelude::*;
+use std::cell::RefCell;
 
-impl<'a> DmenuUI<'a> {
-    /// Updates shown and hidden items with matching (fuzzy or exact).
-    pub fn filter(&mut self) {
-        let query_is_empty = self.query.is_empty();
-
-        let mut index = 0;
-        while index < self.shown.len() {
-            let score = if query_is_empty {
-                Some(0)
-            } else if let Some(ref match_cols) = self.match_nth {
-                self.shown[index].calculate_score_with_match_nth(
-                    &self.query,
-                    &mut self.matcher,
-                    match_cols,
-                )
-            } else {
-                match self.match_mode {
-                    crate::cli::MatchMode::Exact => {
-                        self.shown[index].calculate_exact_score(&self.query)
-                    }
-                    crate::cli::MatchMode::Fuzzy => {
-                        self.shown[index].calculate_score(&self.query, &mut self.matcher)
-                    }
-                }
-            };
+/// Fewest candidates scored on the rayon pool; below this, spreading the work
+/// costs more than it saves.
+pub(super) const PARALLEL_MIN_ITEMS: usize = 16_384;
 
-            match score {
-                None => {
-                    self.shown[index].set_score(0);
-                    let item = self.shown.swap_remove(index);
-                    self.hidden.push(item);
-                }
-                Some(score) => {
-                    self.shown[index].set_score(score);
-                    index += 1;
+/// Candidates scored between checks for a newer key press.
+const BATCH_ITEMS: usize = 8_192;
+
+thread_local! {
+    /// Matcher of each rayon worker, kept across filter runs.
+    static WORKER_MATCHER: RefCell<Matcher> =
+        RefCell::new(Matcher::new(Config::DEFAULT.match_paths()));
+}
+
+/// The query and match settings every candidate is scored against.
+struct Scorer<'q> {
+    query: &'q str,
+    extended: ExtendedQuery,
+    match_mode: MatchMode,
+    match_nth: Option<&'q [usize]>,
+    folding: &'q TextFolding,
+}
+
+impl<'q> Scorer<'q> {
+    fn new(
+        query: &'q str,
+        match_mode: MatchMode,
+        match_nth: Option<&'q [usize]>,
+        folding: &'q TextFolding,
+    ) -> Self {
+        Self {
+            query,
+            extended: ExtendedQuery::parse(&folding.fold(query)),
+            match_mode,
+            match_nth,
+            folding,
+        }
+    }
+
+    fn score(&self, item: &mut Item, matcher: &mut Matcher) -> Option<i64> {
+        item.match_indices.clear();
+        if self.query.is_empty() {
+            Some(0)
+        } else if let Some(match_cols) = self.match_nth {
+            item.calculate_score_with_match_nth(self.query, matcher, match_cols, self.folding)
+        } else {
+            match self.match_mode {
+                MatchMode::Exact => item.calculate_exact_score(self.query, self.folding),
+                MatchMode::Fuzzy => item.calculate_score(self.query, matcher, self.folding),
+                MatchMode::Extended => {
+                    item.calculate_extended_score(&self.extended, matcher, self.folding)
                 }
             }
         }
+    }
+}
 
-        let mut index = 0;
-        while index < self.hidden.len() {
-            let score = if query_is_empty {
-                Some(0)
-            } else if let Some(ref match_cols) = self.match_nth {
-                self.hidden[index].calculate_score_with_match_nth(
-                    &self.query,
-                    &mut self.matcher,
-                    match_cols,
-                )
-            } else {
-                match self.match_mode {
-                    crate::cli::MatchMode::Exact => {
-                        self.hidden[index].calculate_exact_score(&self.query)
-                    }
-                    crate::cli::MatchMode::Fuzzy => {
-                        self.hidden[index].calculate_score(&self.query, &mut self.matcher)
-                    }
-                }
-            };
+impl<'a> DmenuUI<'a> {
+    /// Updates shown and hidden items with matching (fuzzy, exact, or extended).
+    ///
+    /// When the query only grew since the last run, just the current matches
+    /// are rescored. If a key press arrives meanwhile (see
+    /// [`Self::set_pending_keys`]), the previous results are kept and
+    /// [`Self::is_filtered`] stays false until a later run completes.
+    pub fn filter(&mut self) {
+        let pending_keys = self.pending_keys.clone();
+        self.filter_with(pending_keys.as_ref());
+    }
 
-            if let Some(score) = score {
-                self.hidden[index].set_score(score);
-                let item = self.hidden.swap_remove(index);
-                self.shown.push(item);
-            } else {
-                index += 1;
-            }
+    /// Completes a filter run that a key press cut short.
+    pub fn finish_filter(&mut self) {
+        if !self.is_filtered() {
+            self.filter_with(None);
         }
+    }
 
-        self.shown.sort();
+    /// Whether `shown` and `hidden` reflect the current query.
+    pub fn is_filtered(&self) -> bool {
+        self.filtered_query.as_deref() == Some(self.query.as_str())
+    }
+
+    fn filter_with(&mut self, interrupt: Option<&PendingKeys>) {
+        if !self.rank(interrupt) {
+            return;
+        }
 
         if self.shown.is_empty() {
             self.selected = None;
@@ -79,4 +100,359 @@ impl<'a> DmenuUI<'a> {
             self.scroll_offset = 0;
         }
     }
+
+    /// Matches newly arrived `items` against the current query without
+    /// rescoring the rest.
+    ///
+    /// A selection below the first row stays on the same item; the first row
+    /// stays selected so the best match is what Enter picks.
+    pub fn append_items(&mut self, items: Vec<Item>) {
+        if !self.is_filtered() {
+            // The next run matches everything anyway, new items included.
+            self.hidden.extend(items);
+            self.filtered_query = None;
+            return;
+        }
+
+        let selected_line = self
+            .selected
+            .filter(|&selected| selected > 0)
+            .and_then(|selected| self.shown.get(selected))
+            .map(|item| item.line_number);
+
+        self.place_items(items);
+
+        self.selected = match selected_line {
+            Some(line_number) => self
+                .shown
+                .iter()
+                .position(|item| item.line_number == line_number),
+            None => None,
+        }
+        .or_else(|| (!self.shown.is_empty()).then_some(0));
+    }
+
+    /// Rescores the candidates for the current query, returning false without
+    /// changing anything if `interrupt` reports a pending key press first.
+    fn rank(&mut self, interrupt: Option<&PendingKeys>) -> bool {
+        // Hidden items cannot match a query that extends the one they failed.
+        let narrowing = self
+            .filtered_query
+            .as_deref()
+            .is_some_and(|previous| self.narrows(previous));
+        let candidates = self.shown.len() + if narrowing { 0 } else { self.hidden.len() };
+        let parallel = candidates >= self.parallel_min_items;
+
+        let scorer = Scorer::new(
+            &self.query,
+            self.match_mode,
+            self.match_nth.as_deref(),
+            &self.text_folding,
+        );
+        let Some(shown_scores) = score_items(
+            &mut self.shown,
+            &scorer,
+            &mut self.matcher,
+            parallel,
+            interrupt,
+        ) else {
+            return false;
+        };
+        let hidden_scores = if narrowing {
+            None
+        } else {
+            let Some(scores) = score_items(
+                &mut self.hidden,
+                &scorer,
+                &mut self.matcher,
+                parallel,
+                interrupt,
+            ) else {
+                return false;
+            };
+            Some(scores)
+        };
+
+        let mut shown = Vec::new();
+        let mut hidden = if narrowing {
+            std::mem::take(&mut self.hidden)
+        } else {
+            Vec::new()
+        };
+        place(
+            std::mem::take(&mut self.shown),
+            shown_scores,
+            &mut shown,
+            &mut hidden,
+        );
+        if let Some(scores) = hidden_scores {
+            place(
+                std::mem::take(&mut self.hidden),
+                scores,
+                &mut shown,
+                &mut hidden,
+            );
+        }
+        if parallel {
+            shown.par_sort();
+        } else {
+            shown.sort();
+        }
+
+        self.shown = shown;
+        self.hidden = hidden;
+        self.filtered_query = Some(self.query.clone());
+        true
+    }
+
+    /// Whether anything matching the current query must also have matched
+    /// `previous`, so items hidden by it can stay hidden.
+    fn narrows(&self, previous: &str) -> bool {
+        let is_quoted = |query: &str| query.starts_with(['"', '\'']);
+        let narrowable = match self.match_mode {
+            _ if self.match_nth.is_some() => true,
+            MatchMode::Fuzzy => true,
+            // A closing quote turns a substring search into an exact one.
+            MatchMode::Exact => !is_quoted(&self.query) && !is_quoted(previous),
+            // Negated and OR terms match more as they grow.
+            MatchMode::Extended => false,
+        };
+        let fold = |query: &str| self.text_folding.fold(query).to_lowercase();
+        narrowable && self.query != previous && fold(&self.query).starts_with(&fold(previous))
+    }
+
+    /// Scores `items`, moving matches to `shown` and the rest to `hidden`.
+    fn place_items(&mut self, mut items: Vec<Item>) {
+        let parallel = items.len() >= self.parallel_min_items;
+        let scorer = Scorer::new(
+            &self.query,
+            self.match_mode,
+            self.match_nth.as_deref(),
+            &self.text_folding,
+        );
+        let scores = score_items(&mut items, &scorer, &mut self.matcher, parallel, None)
+            .expect("scoring without an interrupt always completes");
+        place(items, scores, &mut self.shown, &mut self.hidden);
+
+        self.shown.sort();
+    }
+}
+
+/// Scores `items` in order, in batches so that a pending key press reported by
+/// `interrupt` can stop the run early with `None`.
+fn score_items(
+    items: &mut [Item],
+    scorer: &Scorer,
+    matcher: &mut Matcher,
+    parallel: bool,
+    interrupt: Option<&PendingKeys>,
+) -> Option<Vec<Option<i64>>> {
+    let mut scores = Vec::with_capacity(items.len());
+    for batch in items.chunks_mut(BATCH_ITEMS) {
+        if interrupt.is_some_and(PendingKeys::any) {
+            return None;
+        }
+        if parallel {
+            scores.par_extend(
+                batch.par_iter_mut().map(|item| {
+                    WORKER_MATCHER.with_borrow_mut(|matcher| scorer.score(item, matcher))
+                }),
+            );
+        } else {
+            scores.extend(batch.iter_mut().map(|item| scorer.score(item, matcher)));
+        }
+    }
+    Some(scores)
+}
+
+/// Moves each of `items` to `shown` or `hidden` by whether it was given a score.
+fn place(
+    items: Vec<Item>,
+    scores: Vec<Option<i64>>,
+    shown: &mut Vec<Item>,
+    hidden: &mut Vec<Item>,
+) {
+    for (mut item, score) in items.into_iter().zip(scores) {
+        match score {
+            Some(score) => {
+                item.set_score(score);
+                shown.push(item);
+            }
+            None => {
+                item.set_score(0);
+                hidden.push(item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DmenuUI;
+    use crate::cli::MatchMode;
+    use crate::common::Item;
+    use crate::core::ranking::TextFolding;
+    use crate::ui::PendingKeys;
+
+    /// `count` lines of pseudo-random words, reproducible across runs.
+    fn generated_items(count: usize) -> Vec<Item> {
+        const SYLLABLES: [&str; 8] = ["fo", "ba", "r ", "qu", "x.", "ab", "c/", "de"];
+        let mut seed: u64 = 0x5eed;
+        (1..=count)
+            .map(|line_number| {
+                let line: String = (0..6)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        SYLLABLES[(seed >> 61) as usize]
+                    })
+                    .collect();
+                Item::new(line, line_number, " ", None)
+            })
+            .collect()
+    }
+
+    fn ranking(ui: &DmenuUI) -> Vec<(usize, i64, Vec<u32>)> {
+        ui.shown
+            .iter()
+            .map(|item| (item.line_number, item.score, item.match_indices.clone()))
+            .collect()
+    }
+
+    fn filtered(mode: MatchMode, query: &str, parallel_min_items: usize) -> DmenuUI<'static> {
+        let mut ui = DmenuUI::new(generated_items(10_000), false, false);
+        ui.parallel_min_items = parallel_min_items;
+        ui.set_match_mode(mode);
+        ui.query = query.to_string();
+        ui.filter();
+        ui
+    }
+
+    #[test]
+    fn serial_and_parallel_scoring_rank_identically() {
+        for (mode, query) in [
+            (MatchMode::Fuzzy, "fob"),
+            (MatchMode::Exact, "ab"),
+            (MatchMode::Extended, "'qu !x. | de$"),
+        ] {
+            let serial = filtered(mode, query, usize::MAX);
+            let parallel = filtered(mode, query, 0);
+            assert!(!serial.shown.is_empty(), "{query:?} should match");
+            assert_eq!(ranking(&serial), ranking(&parallel), "{query:?}");
+        }
+    }
+
+    #[test]
+    fn growing_query_keeps_hidden_items_and_matches_a_full_run() {
+        for (mode, typed) in [(MatchMode::Fuzzy, "foqab"), (MatchMode::Exact, "\"foba\"")] {
+            let mut ui = filtered(mode, "", usize::MAX);
+            for end in 1..=typed.len() {
+                ui.query = typed[..end].to_string();
+                ui.filter();
+                assert_eq!(
+                    ranking(&ui),
+                    ranking(&filtered(mode, &ui.query, usize::MAX)),
+                    "{:?}",
+                    ui.query
+                );
+                assert_eq!(ui.shown.len() + ui.hidden.len(), 10_000);
+            }
+        }
+    }
+
+    #[test]
+    fn pending_key_press_keeps_previous_results_until_finished() {
+        let pending_keys = PendingKeys::default();
+        let mut ui = filtered(MatchMode::Fuzzy, "fo", usize::MAX);
+        ui.set_pending_keys(pending_keys.clone());
+        let before = ranking(&ui);
+
+        pending_keys.push();
+        ui.query = "fox".to_string();
+        ui.filter();
+        assert!(!ui.is_filtered());
+        assert_eq!(ranking(&ui), before);
+
+        ui.finish_filter();
+        assert!(ui.is_filtered());
+        assert_eq!(ranking(&ui), ranking(&filtered(MatchMode::Fuzzy, "fox", 0)));
+    }
+
+    #[test]
+    fn extended_mode_filters_items_and_records_matched_positions() {
+        let items = ["alpha.txt", "alpha.rs", "beta.rs"]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| Item::new(line.to_string(), index + 1, " ", None))
+            .collect();
+        let mut ui = DmenuUI::new(items, false, false);
+        ui.set_match_mode(MatchMode::Extended);
+
+        ui.query = ".rs$ !beta".to_string();
+        ui.filter();
+        let shown: Vec<&str> = ui
+            .shown
+            .iter()
+            .map(|item| item.display_text.as_str())
+            .collect();
+        assert_eq!(shown, ["alpha.rs"]);
+        assert_eq!(ui.shown[0].match_indices, [5, 6, 7]);
+
+        ui.query.clear();
+        ui.filter();
+        assert_eq!(ui.shown.len(), 3);
+        assert!(ui.shown.iter().all(|item| item.match_indices.is_empty()));
+    }
+
+    #[test]
+    fn folding_matches_accents_and_maps_highlights_to_original_chars() {
+        let items = ["Cafe\u{301} noir", "Creme brulee"]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| Item::new(line.to_string(), index + 1, " ", None))
+            .collect();
+        let mut ui = DmenuUI::new(items, false, false);
+        ui.set_match_mode(MatchMode::Exact);
+        ui.set_text_folding(TextFolding {
+            normalize: true,
+            ..TextFolding::default()
+        });
+
+        ui.query = "cafe n".to_string();
+        ui.filter();
+        assert_eq!(ui.shown.len(), 1);
+
+        ui.set_match_mode(MatchMode::Extended);
+        ui.query = "'e\\ n".to_string();
+        ui.filter();
+        assert_eq!(ui.shown.len(), 1);
+        assert_eq!(ui.shown[0].match_indices, [3, 5, 6]);
+    }
+
+    #[test]
+    fn appended_items_are_matched_and_merged_into_the_ranking() {
+        let items = |lines: &[(usize, &str)]| {
+            lines
+                .iter()
+                .map(|(line_number, line)| Item::new(line.to_string(), *line_number, " ", None))
+                .collect::<Vec<_>>()
+        };
+        let mut ui = DmenuUI::new(items(&[(1, "f_o_o"), (2, "bar")]), false, false);
+        ui.query = "foo".to_string();
+        ui.filter();
+        assert_eq!(ui.shown.len(), 1);
+
+        ui.append_items(items(&[(3, "foo"), (4, "baz")]));
+        let shown: Vec<&str> = ui
+            .shown
+            .iter()
+            .map(|item| item.original_line.as_str())
+            .collect();
+        assert_eq!(shown, ["foo", "f_o_o"]);
+        assert_eq!(ui.hidden.len(), 2);
+        assert_eq!(ui.selected, Some(0));
+
+        ui.selected = Some(1);
+        ui.append_items(items(&[(5, "fooo")]));
+        assert_eq!(ui.shown[ui.selected.unwrap()].original_line, "f_o_o");
+    }
 }
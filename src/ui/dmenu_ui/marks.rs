@@ -0,0 +1,169 @@
+use std::collections::BTreeSet;
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Span;
+use ratatui::widgets::ListItem;
+
+use super::DmenuUI;
+use crate::common::Item;
+
+/// Marker column shown before marked rows in multi-select mode.
+const MARKED: &str = "● ";
+/// Marker column of unmarked rows, keeping every row aligned.
+const UNMARKED: &str = "  ";
+
+/// Multi-select state: whether marking is enabled and which items are marked.
+#[derive(Debug, Default)]
+pub(super) struct Marks {
+    enabled: bool,
+    limit: Option<usize>,
+    /// Line numbers of the marked items, which stay put as the query changes.
+    lines: BTreeSet<usize>,
+}
+
+impl<'a> DmenuUI<'a> {
+    /// Lets items be marked, up to `limit` of them when given.
+    pub fn enable_multi_select(&mut self, limit: Option<usize>) {
+        self.marks.enabled = true;
+        self.marks.limit = limit;
+    }
+
+    /// Whether items can be marked.
+    pub fn multi_select(&self) -> bool {
+        self.marks.enabled
+    }
+
+    /// Marks the selected item, or unmarks it if it already is.
+    ///
+    /// Returns false if nothing is selected or the limit is already reached.
+    pub fn toggle_mark(&mut self) -> bool {
+        let Some(line_number) = self
+            .selected
+            .and_then(|selected| self.shown.get(selected))
+            .map(|item| item.line_number)
+        else {
+            return false;
+        };
+        if self.marks.lines.remove(&line_number) {
+            return true;
+        }
+        if self
+            .marks
+            .limit
+            .is_some_and(|limit| self.marks.lines.len() >= limit)
+        {
+            return false;
+        }
+        self.marks.lines.insert(line_number)
+    }
+
+    /// Number of marked items.
+    pub fn marked_count(&self) -> usize {
+        self.marks.lines.len()
+    }
+
+    /// Whether `item` is marked.
+    pub fn is_marked(&self, item: &Item) -> bool {
+        self.marks.lines.contains(&item.line_number)
+    }
+
+    /// Marked items in input order, including those the query now hides.
+    pub fn marked_items(&self) -> Vec<&Item> {
+        if self.marks.lines.is_empty() {
+            return Vec::new();
+        }
+        let mut items: Vec<&Item> = self
+            .shown
+            .iter()
+            .chain(&self.hidden)
+            .filter(|item| self.is_marked(item))
+            .collect();
+        items.sort_by_key(|item| item.line_number);
+        items
+    }
+
+    /// How many items are marked, and of how many allowed, for the input
+    /// line; `None` outside multi-select mode.
+    pub fn marked_status(&self) -> Option<String> {
+        self.marks.enabled.then(|| match self.marks.limit {
+            Some(limit) => format!("[{}/{limit}] ", self.marks.lines.len()),
+            None => format!("[{}] ", self.marks.lines.len()),
+        })
+    }
+
+    /// Unmarks every item.
+    pub fn clear_marks(&mut self) {
+        self.marks.lines.clear();
+    }
+
+    /// A list row for `item`, led by the marker column in multi-select mode.
+    pub fn marked_list_item<'i>(
+        &self,
+        item: &'i Item,
+        tag_metadata: Option<&'i crate::modes::cclip::TagMetadataFormatter>,
+    ) -> ListItem<'i> {
+        let mut text = item.to_text(tag_metadata);
+        if self.marks.enabled {
+            let marked = self.is_marked(item);
+            for (index, line) in text.lines.iter_mut().enumerate() {
+                let marker = if marked && index == 0 {
+                    Span::styled(MARKED, Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    Span::raw(UNMARKED)
+                };
+                line.spans.insert(0, marker);
+            }
+        }
+        ListItem::new(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DmenuUI;
+    use crate::common::Item;
+
+    fn ui(lines: &[&str]) -> DmenuUI<'static> {
+        let items = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| Item::new(line.to_string(), index + 1, " ", None))
+            .collect();
+        DmenuUI::new(items, false, false)
+    }
+
+    #[test]
+    fn marks_survive_filtering_and_come_back_in_input_order() {
+        let mut ui = ui(&["alpha", "beta", "gamma"]);
+        ui.enable_multi_select(None);
+        ui.selected = Some(2);
+        assert!(ui.toggle_mark());
+        ui.selected = Some(0);
+        assert!(ui.toggle_mark());
+
+        ui.query = "gam".to_string();
+        ui.filter();
+        let marked: Vec<&str> = ui
+            .marked_items()
+            .iter()
+            .map(|item| item.original_line.as_str())
+            .collect();
+        assert_eq!(marked, ["alpha", "gamma"]);
+
+        assert!(ui.toggle_mark());
+        assert_eq!(ui.marked_count(), 1);
+    }
+
+    #[test]
+    fn marking_stops_at_the_limit_but_unmarking_still_works() {
+        let mut ui = ui(&["one", "two"]);
+        ui.enable_multi_select(Some(1));
+        ui.selected = Some(0);
+        assert!(ui.toggle_mark());
+        ui.selected = Some(1);
+        assert!(!ui.toggle_mark());
+        ui.selected = Some(0);
+        assert!(ui.toggle_mark());
+        assert_eq!(ui.marked_count(), 0);
+    }
+}
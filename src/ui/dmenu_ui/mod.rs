@@ -1,5 +1,6 @@
 mod content;
 mod filter;
+mod marks;
 mod tag_mode;
 
 use std::collections::HashMap;
@@ -10,6 +11,8 @@ use nucleo_matcher::{Config, Matcher};
 use ratatui::text::Line;
 
 use crate::common::Item;
+use crate::core::ranking::TextFolding;
+use crate::ui::PendingKeys;
 
 pub use tag_mode::TagMode;
 
@@ -35,8 +38,12 @@ pub struct DmenuUI<'a> {
     pub match_mode: crate::cli::MatchMode,
     /// Match against specific columns.
     pub match_nth: Option<Vec<usize>>,
+    /// How query and item text are folded before matching.
+    pub text_folding: TextFolding,
     /// Tag mode state.
     pub tag_mode: TagMode,
+    /// Whether more items are still arriving, as when stdin is streamed.
+    pub loading: bool,
     /// Cache for clipboard content to avoid repeated cclip calls.
     content_cache: HashMap<String, String>,
     /// In-flight clipboard content fetches keyed by row ID.
@@ -45,6 +52,14 @@ pub struct DmenuUI<'a> {
     pub temp_message: Option<(String, Instant)>,
     #[doc(hidden)]
     matcher: Matcher,
+    /// Query that `shown` and `hidden` were last fully matched against.
+    filtered_query: Option<String>,
+    /// Key presses that make a running filter give way, if installed.
+    pending_keys: Option<PendingKeys>,
+    /// Fewest candidates scored on the rayon pool rather than this thread.
+    parallel_min_items: usize,
+    /// Items marked for multi-select.
+    marks: marks::Marks,
 }
 
 impl<'a> DmenuUI<'a> {
@@ -61,11 +76,17 @@ impl<'a> DmenuUI<'a> {
             show_line_numbers,
             match_mode: crate::cli::MatchMode::Fuzzy,
             match_nth: None,
+            text_folding: TextFolding::default(),
             tag_mode: TagMode::Normal,
+            loading: false,
             content_cache: HashMap::new(),
             content_requests: HashMap::new(),
             temp_message: None,
             matcher: Matcher::new(Config::DEFAULT.match_paths()),
+            filtered_query: None,
+            pending_keys: None,
+            parallel_min_items: filter::PARALLEL_MIN_ITEMS,
+            marks: marks::Marks::default(),
         };
         ui.filter();
         ui
@@ -74,11 +95,25 @@ impl<'a> DmenuUI<'a> {
     /// Set match mode.
     pub fn set_match_mode(&mut self, mode: crate::cli::MatchMode) {
         self.match_mode = mode;
+        self.filtered_query = None;
     }
 
     /// Set match_nth columns.
     pub fn set_match_nth(&mut self, columns: Option<Vec<usize>>) {
         self.match_nth = columns;
+        self.filtered_query = None;
+    }
+
+    /// Set how query and item text are folded before matching.
+    pub fn set_text_folding(&mut self, folding: TextFolding) {
+        self.text_folding = folding;
+        self.filtered_query = None;
+    }
+
+    /// Lets [`Self::filter`] stop early, keeping the previous results, when a
+    /// key press is waiting behind the one being handled.
+    pub fn set_pending_keys(&mut self, pending_keys: PendingKeys) {
+        self.pending_keys = Some(pending_keys);
     }
 
     /// Set a temporary message that expires after 2 seconds.
@@ -102,6 +137,7 @@ impl<'a> DmenuUI<'a> {
     }
 
     /// Replace the underlying items while preserving the current query and match settings.
+    /// Marks are dropped along with the items they pointed at.
     #[allow(dead_code)]
     pub fn set_items(&mut self, items: Vec<Item>) {
         self.hidden = items;
@@ -110,6 +146,8 @@ impl<'a> DmenuUI<'a> {
         self.scroll_offset = 0;
         self.content_cache.clear();
         self.content_requests.clear();
+        self.clear_marks();
+        self.filtered_query = None;
         self.filter();
     }
 
@@ -0,0 +1,53 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Span;
+
+/// Splits `text` into spans, emphasizing the chars at sorted `indices`.
+pub(crate) fn match_spans<'a>(text: &'a str, indices: &[u32], style: Style) -> Vec<Span<'a>> {
+    let matched_style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut indices = indices.iter().copied().peekable();
+    let mut run_start = 0;
+    let mut run_matched = false;
+
+    for (position, (byte_index, _)) in text.char_indices().enumerate() {
+        let matched = indices.next_if_eq(&(position as u32)).is_some();
+        if matched != run_matched && byte_index > run_start {
+            let run_style = if run_matched { matched_style } else { style };
+            spans.push(Span::styled(&text[run_start..byte_index], run_style));
+            run_start = byte_index;
+        }
+        run_matched = matched;
+    }
+    if run_start < text.len() {
+        let run_style = if run_matched { matched_style } else { style };
+        spans.push(Span::styled(&text[run_start..], run_style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_spans;
+    use ratatui::style::{Modifier, Style};
+    use ratatui::text::Span;
+
+    #[test]
+    fn matched_chars_are_grouped_into_emphasized_runs() {
+        let style = Style::default();
+        let matched = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+        assert_eq!(
+            match_spans("Firéfox", &[0, 1, 3, 4], style),
+            [
+                Span::styled("Fi", matched),
+                Span::styled("r", style),
+                Span::styled("éf", matched),
+                Span::styled("ox", style),
+            ]
+        );
+        assert_eq!(
+            match_spans("Files", &[], style),
+            [Span::styled("Files", style)]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::core::cache::IconCache;
+use crate::desktop::IconLookup;
+use ratatui::layout::Rect;
+use ratatui_image::Resize;
+use ratatui_image::picker::{Picker, ProtocolType};
+use ratatui_image::protocol::Protocol;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Terminal cells one list icon occupies; two columns make a roughly square cell.
+pub(crate) const ICON_WIDTH: u16 = 2;
+
+/// Inline launcher icons: theme lookups backed by the icon cache, plus the
+/// encoded images for the terminal's graphics protocol.
+pub struct AppIcons {
+    picker: Picker,
+    lookup: IconLookup,
+    cache: IconCache,
+    cache_key: String,
+    watched_dirs: Vec<PathBuf>,
+    resolved: HashMap<String, Option<PathBuf>>,
+    unsaved: bool,
+    images: HashMap<PathBuf, Option<Protocol>>,
+}
+
+impl AppIcons {
+    /// Query the terminal for a graphics protocol and set up icon lookups.
+    ///
+    /// Returns `None` when the terminal can only draw half-blocks, which are
+    /// unreadable at icon size. Must run after the terminal enters raw mode.
+    pub fn detect(theme: Option<&str>, db: Arc<redb::Database>) -> Option<Self> {
+        let picker = Picker::from_query_stdio().ok()?;
+        if picker.protocol_type() == ProtocolType::Halfblocks {
+            return None;
+        }
+
+        let (_, font_height) = picker.font_size();
+        let mut lookup = IconLookup::new(theme, u32::from(font_height), 1);
+        let cache = IconCache::new(db).ok()?;
+        let cache_key = lookup.cache_key();
+        let watched_dirs = lookup.watched_dirs();
+        let resolved = cache.load(&cache_key, &watched_dirs).unwrap_or_default();
+
+        Some(Self {
+            picker,
+            lookup,
+            cache,
+            cache_key,
+            watched_dirs,
+            resolved,
+            unsaved: false,
+            images: HashMap::new(),
+        })
+    }
+
+    /// The encoded image for an entry's `Icon` value, if one can be found.
+    pub(crate) fn image(&mut self, icon: &str) -> Option<&Protocol> {
+        let path = match self.resolved.get(icon) {
+            Some(path) => path.clone(),
+            None => {
+                let path = self.lookup.find(icon);
+                self.resolved.insert(icon.to_string(), path.clone());
+                self.unsaved = true;
+                path
+            }
+        }?;
+
+        if !self.images.contains_key(&path) {
+            let protocol = encode(&self.picker, &path);
+            self.images.insert(path.clone(), protocol);
+        }
+        self.images.get(&path)?.as_ref()
+    }
+
+    /// Persist lookups made since the last save so the next start can skip them.
+    pub fn save(&mut self) {
+        if !self.unsaved {
+            return;
+        }
+        if let Err(error) = self
+            .cache
+            .store(&self.cache_key, &self.watched_dirs, &self.resolved)
+            && crate::cli::DEBUG_ENABLED.load(std::sync::atomic::Ordering::Relaxed)
+        {
+            crate::core::debug_logger::log_event(&format!("Failed to cache icons: {error}"));
+        }
+        self.unsaved = false;
+    }
+}
+
+fn encode(picker: &Picker, path: &Path) -> Option<Protocol> {
+    let image = image::open(path).ok()?;
+    picker
+        .new_protocol(image, Rect::new(0, 0, ICON_WIDTH, 1), Resize::Fit(None))
+        .ok()
+}
@@ -165,12 +165,39 @@ impl Drop for AsyncInput {
 // LEGACY SYNC INPUT (kept for backwards compatibility with dmenu/cclip modes)
 // =============================================================================
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc as std_mpsc;
 use std::thread;
 
+/// Count of key presses read from the terminal but not yet taken from an
+/// `Input`, so long-running work can give way to newer keystrokes.
+#[derive(Debug, Clone, Default)]
+pub struct PendingKeys(Arc<AtomicUsize>);
+
+impl PendingKeys {
+    /// Whether a key press is waiting to be handled.
+    pub fn any(&self) -> bool {
+        self.0.load(Ordering::Acquire) > 0
+    }
+
+    pub(super) fn push(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn pop(&self) {
+        let _ = self
+            .0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            });
+    }
+}
+
 /// Legacy sync input handler (for modes not yet migrated to async)
 pub struct Input {
     rx: std_mpsc::Receiver<Event<KeyEvent>>,
+    pending_keys: PendingKeys,
     _input_handle: thread::JoinHandle<()>,
     _tick_handle: thread::JoinHandle<()>,
 }
@@ -178,9 +205,11 @@ pub struct Input {
 impl Input {
     pub fn with_config(config: Config) -> Self {
         let (tx, rx) = std_mpsc::channel();
+        let pending_keys = PendingKeys::default();
 
         let _input_handle = {
             let tx = tx.clone();
+            let pending_keys = pending_keys.clone();
 
             thread::spawn(move || {
                 loop {
@@ -189,6 +218,7 @@ impl Input {
                     {
                         match event {
                             CrosstermEvent::Key(key) => {
+                                pending_keys.push();
                                 if tx.send(Event::Input(key)).is_err() {
                                     return;
                                 }
@@ -222,6 +252,7 @@ impl Input {
 
         Self {
             rx,
+            pending_keys,
             _input_handle,
             _tick_handle,
         }
@@ -229,15 +260,27 @@ impl Input {
 
     /// Next key pressed by user.
     pub fn next(&self) -> Result<Event<KeyEvent>, std_mpsc::RecvError> {
-        self.rx.recv()
+        self.rx.recv().inspect(|event| self.received(event))
     }
 
     /// Next key pressed by user with timeout.
-    #[allow(dead_code)]
     pub fn next_timeout(
         &self,
         timeout: Duration,
     ) -> Result<Event<KeyEvent>, std_mpsc::RecvTimeoutError> {
-        self.rx.recv_timeout(timeout)
+        self.rx
+            .recv_timeout(timeout)
+            .inspect(|event| self.received(event))
+    }
+
+    /// Handle that reports whether key presses are queued behind the current one.
+    pub fn pending_keys(&self) -> PendingKeys {
+        self.pending_keys.clone()
+    }
+
+    fn received(&self, event: &Event<KeyEvent>) {
+        if matches!(event, Event::Input(_)) {
+            self.pending_keys.pop();
+        }
     }
 }
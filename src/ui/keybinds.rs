@@ -19,10 +19,16 @@ pub struct Keybinds {
     pub exit: Vec<KeyBind>,
     #[serde(default = "default_pin")]
     pub pin: Vec<KeyBind>,
+    #[serde(default = "default_move_pin_up")]
+    pub move_pin_up: Vec<KeyBind>,
+    #[serde(default = "default_move_pin_down")]
+    pub move_pin_down: Vec<KeyBind>,
     #[serde(default = "default_hide")]
     pub hide: Vec<KeyBind>,
     #[serde(default = "default_unhide_last")]
     pub unhide_last: Vec<KeyBind>,
+    #[serde(default = "default_category")]
+    pub category: Vec<KeyBind>,
     #[serde(default = "default_backspace")]
     pub backspace: Vec<KeyBind>,
     #[serde(default = "default_image_preview")]
@@ -31,6 +37,8 @@ pub struct Keybinds {
     pub tag: Vec<KeyBind>,
     #[serde(default = "default_cclip_delete")]
     pub cclip_delete: Vec<KeyBind>,
+    #[serde(default = "default_toggle_mark")]
+    pub toggle_mark: Vec<KeyBind>,
 }
 
 impl Default for Keybinds {
@@ -43,12 +51,16 @@ impl Default for Keybinds {
             select: default_select(),
             exit: default_exit(),
             pin: default_pin(),
+            move_pin_up: default_move_pin_up(),
+            move_pin_down: default_move_pin_down(),
             hide: default_hide(),
             unhide_last: default_unhide_last(),
+            category: default_category(),
             backspace: default_backspace(),
             image_preview: default_image_preview(),
             tag: default_tag(),
             cclip_delete: default_cclip_delete(),
+            toggle_mark: default_toggle_mark(),
         }
     }
 }
@@ -193,6 +205,20 @@ fn default_pin() -> Vec<KeyBind> {
     }]
 }
 
+fn default_move_pin_up() -> Vec<KeyBind> {
+    vec![KeyBind::WithMod {
+        key: "up".to_string(),
+        modifiers: "alt".to_string(),
+    }]
+}
+
+fn default_move_pin_down() -> Vec<KeyBind> {
+    vec![KeyBind::WithMod {
+        key: "down".to_string(),
+        modifiers: "alt".to_string(),
+    }]
+}
+
 fn default_hide() -> Vec<KeyBind> {
     vec![KeyBind::WithMod {
         key: "delete".to_string(),
@@ -207,6 +233,13 @@ fn default_unhide_last() -> Vec<KeyBind> {
     }]
 }
 
+fn default_category() -> Vec<KeyBind> {
+    vec![KeyBind::WithMod {
+        key: "c".to_string(),
+        modifiers: "alt".to_string(),
+    }]
+}
+
 fn default_backspace() -> Vec<KeyBind> {
     vec![KeyBind::Simple("backspace".to_string())]
 }
@@ -233,6 +266,10 @@ fn default_cclip_delete() -> Vec<KeyBind> {
     }]
 }
 
+fn default_toggle_mark() -> Vec<KeyBind> {
+    vec![KeyBind::Simple("tab".to_string())]
+}
+
 impl Keybinds {
     pub fn matches_up(&self, code: KeyCode, mods: KeyModifiers) -> bool {
         self.up.iter().any(|kb| kb.matches(code, mods))
@@ -262,6 +299,20 @@ impl Keybinds {
         self.pin.iter().any(|kb| kb.matches(code, mods))
     }
 
+    /// Move-pin-up keybind for the app launcher's manual pinned order
+    pub fn matches_move_pin_up(&self, code: KeyCode, mods: KeyModifiers) -> bool {
+        self.move_pin_up
+            .iter()
+            .any(|binding| binding.matches(code, mods))
+    }
+
+    /// Move-pin-down keybind for the app launcher's manual pinned order
+    pub fn matches_move_pin_down(&self, code: KeyCode, mods: KeyModifiers) -> bool {
+        self.move_pin_down
+            .iter()
+            .any(|binding| binding.matches(code, mods))
+    }
+
     pub fn matches_hide(&self, code: KeyCode, mods: KeyModifiers) -> bool {
         self.hide.iter().any(|binding| binding.matches(code, mods))
     }
@@ -272,6 +323,13 @@ impl Keybinds {
             .any(|binding| binding.matches(code, mods))
     }
 
+    /// Category keybind for the app launcher
+    pub fn matches_category(&self, code: KeyCode, mods: KeyModifiers) -> bool {
+        self.category
+            .iter()
+            .any(|binding| binding.matches(code, mods))
+    }
+
     pub fn matches_backspace(&self, code: KeyCode, mods: KeyModifiers) -> bool {
         self.backspace.iter().any(|kb| kb.matches(code, mods))
     }
@@ -301,6 +359,11 @@ impl Keybinds {
     pub fn matches_cclip_delete(&self, code: KeyCode, mods: KeyModifiers) -> bool {
         self.cclip_delete.iter().any(|kb| kb.matches(code, mods))
     }
+
+    /// Mark/unmark keybind for dmenu and cclip multi-select
+    pub fn matches_toggle_mark(&self, code: KeyCode, mods: KeyModifiers) -> bool {
+        self.toggle_mark.iter().any(|kb| kb.matches(code, mods))
+    }
 }
 
 #[cfg(test)]
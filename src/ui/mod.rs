@@ -1,6 +1,8 @@
 mod app_ui;
 mod dmenu_ui;
 mod graphics;
+mod highlight;
+mod icons;
 mod input;
 mod keybinds;
 mod panel_layout;
@@ -11,8 +13,10 @@ pub use app_ui::UI;
 pub(crate) use app_ui::effective_title_height;
 pub use dmenu_ui::{DmenuUI, TagMode};
 pub use graphics::{DISPLAY_STATE, DisplayState, GraphicsAdapter, ImageManager};
+pub(crate) use highlight::match_spans;
+pub use icons::AppIcons;
 #[allow(unused_imports)]
-pub use input::{AsyncInput, Config as InputConfig, Event as InputEvent, Input};
+pub use input::{AsyncInput, Config as InputConfig, Event as InputEvent, Input, PendingKeys};
 pub use keybinds::Keybinds;
 pub(crate) use panel_layout::{
     PanelLayout, effective_content_height, items_panel_bounds, items_panel_height,
@@ -220,3 +220,107 @@ fn automatic_duplicate_hiding_is_opt_in_and_uses_xdg_precedence() {
 
     fs::remove_dir_all(runtime_dir).expect("isolated runtime directory should be removed");
 }
+
+#[test]
+fn no_exec_program_launch_expands_file_targets_per_process() {
+    let runtime_dir = isolated_runtime_dir("launch-targets");
+    let user_data = runtime_dir.join("user-data");
+    let user_apps = user_data.join("applications");
+    fs::create_dir_all(&user_apps).expect("user application directory should be created");
+    fs::write(
+        user_apps.join("viewer.desktop"),
+        "[Desktop Entry]\nType=Application\nName=Viewer\nExec=viewer --single %f\n",
+    )
+    .expect("desktop entry should be written");
+
+    let output = isolated_command(&runtime_dir)
+        .env("XDG_DATA_HOME", &user_data)
+        .env("XDG_DATA_DIRS", runtime_dir.join("no-system-data"))
+        .current_dir(&runtime_dir)
+        .args([
+            "--no-exec",
+            "-p",
+            "Viewer",
+            "--",
+            "a.png",
+            "https://example.com/b.png",
+        ])
+        .output()
+        .expect("test binary should run");
+
+    assert!(
+        output.status.success(),
+        "expected launch with targets to succeed, stderr was: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    let local_file = runtime_dir.join("a.png");
+    assert_eq!(
+        lines,
+        [
+            format!("viewer --single {}", local_file.display()),
+            "viewer --single https://example.com/b.png".to_string(),
+        ]
+    );
+
+    fs::remove_dir_all(runtime_dir).expect("isolated runtime directory should be removed");
+}
+
+#[test]
+fn open_with_lists_handlers_with_mimeapps_defaults_first() {
+    let runtime_dir = isolated_runtime_dir("open-with");
+    let user_data = runtime_dir.join("user-data");
+    let user_apps = user_data.join("applications");
+    let config_home = runtime_dir.join("config");
+    fs::create_dir_all(&user_apps).expect("user application directory should be created");
+    fs::create_dir_all(&config_home).expect("config directory should be created");
+    let entries = [
+        ("aviewer", "Exec=aviewer %f\nMimeType=image/png;"),
+        ("zviewer", "Exec=zviewer %f\nMimeType=image/png;"),
+        ("editor", "Exec=editor %F\nMimeType=text/plain;"),
+    ];
+    for (name, body) in entries {
+        fs::write(
+            user_apps.join(format!("{name}.desktop")),
+            format!("[Desktop Entry]\nType=Application\nName={name}\n{body}\n"),
+        )
+        .expect("desktop entry should be written");
+    }
+    fs::write(
+        config_home.join("mimeapps.list"),
+        "[Default Applications]\nimage/png=zviewer.desktop;\n",
+    )
+    .expect("mimeapps.list should be written");
+
+    let output = isolated_command(&runtime_dir)
+        .env("XDG_DATA_HOME", &user_data)
+        .env("XDG_DATA_DIRS", runtime_dir.join("no-system-data"))
+        .env("XDG_CONFIG_DIRS", runtime_dir.join("no-system-config"))
+        .args(["--open-with", "shot.png", "--stdout"])
+        .output()
+        .expect("test binary should run");
+
+    assert!(
+        output.status.success(),
+        "expected --open-with to succeed, stderr was: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let zviewer = stdout
+        .find("\"zviewer\"")
+        .expect("default handler should be listed");
+    let aviewer = stdout
+        .find("\"aviewer\"")
+        .expect("declared handler should be listed");
+    assert!(
+        zviewer < aviewer,
+        "default handler should come first: {stdout}"
+    );
+    assert!(
+        !stdout.contains("\"editor\""),
+        "non-handlers should be filtered: {stdout}"
+    );
+
+    fs::remove_dir_all(runtime_dir).expect("isolated runtime directory should be removed");
+}